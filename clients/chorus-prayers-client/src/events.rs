@@ -0,0 +1,168 @@
+//! Event decoders. Anchor's `emit!` writes each event as a `Program data:`
+//! log line holding base64(discriminator || borsh(event)).
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chorus_prayers::{
+    AgentKeyRotated, AnswerRemoved, AnswerSubmitted, AuthorityProposed, AuthorityTransferred,
+    BountyToppedUp, ClaimApproved, ClaimHeartbeat, ClaimRejected, ClaimRemoved, ClaimWeightsSet,
+    CompetitionSettled, ConfigUpdated, ConfirmVoted, ContentDelivered, ContributionWithdrawn,
+    DisputeExpired, DisputeOpened, DisputeResolved, PauseUpdated, PrayerAnswered, PrayerCancelled,
+    PrayerClaimed, PrayerConfirmed, PrayerExpired, PrayerExtended, PrayerFinalized, PrayerFunded,
+    PrayerPosted, WinnersSelected, ID,
+};
+
+macro_rules! chorus_events {
+    ($($event:ident),* $(,)?) => {
+        /// Any event emitted by the program
+        pub enum ChorusEvent {
+            $($event($event),)*
+        }
+
+        impl ChorusEvent {
+            /// Decode event bytes (discriminator included). Returns None for
+            /// data that isn't a known event.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(mut body) = data.strip_prefix($event::DISCRIMINATOR) {
+                        return $event::deserialize(&mut body).ok().map(Self::$event);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+chorus_events!(
+    AuthorityProposed,
+    AuthorityTransferred,
+    ConfigUpdated,
+    AgentKeyRotated,
+    PauseUpdated,
+    PrayerPosted,
+    PrayerExtended,
+    BountyToppedUp,
+    PrayerFunded,
+    PrayerClaimed,
+    ClaimApproved,
+    ClaimRejected,
+    ClaimHeartbeat,
+    ContentDelivered,
+    PrayerAnswered,
+    AnswerSubmitted,
+    WinnersSelected,
+    ClaimWeightsSet,
+    CompetitionSettled,
+    PrayerConfirmed,
+    PrayerFinalized,
+    ConfirmVoted,
+    DisputeOpened,
+    DisputeResolved,
+    DisputeExpired,
+    PrayerCancelled,
+    PrayerExpired,
+    ClaimRemoved,
+    AnswerRemoved,
+    ContributionWithdrawn,
+);
+
+/// Extract this program's events from a transaction's log messages.
+/// Tracks the invoke stack so `Program data:` lines written by other programs
+/// (e.g. during a token CPI) are skipped.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ChorusEvent> {
+    let program_id = ID.to_string();
+    let mut stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if stack.last() == Some(&true) {
+                if let Some(event) = STANDARD
+                    .decode(data)
+                    .ok()
+                    .and_then(|d| ChorusEvent::decode(&d))
+                {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let id = words.next();
+            match words.next() {
+                Some("invoke") => stack.push(id == Some(program_id.as_str())),
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::Event;
+
+    fn data_line(event: &impl Event) -> String {
+        format!("Program data: {}", STANDARD.encode(event.data()))
+    }
+
+    #[test]
+    fn parse_logs_keeps_only_this_programs_events() {
+        let requester = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let cancelled = PrayerCancelled { id: 7, requester };
+        let paused = PauseUpdated {
+            authority: requester,
+            paused: 3,
+        };
+        let logs = [
+            format!("Program {other_program} invoke [1]"),
+            data_line(&cancelled),
+            format!("Program {other_program} success"),
+            format!("Program {ID} invoke [1]"),
+            "Program log: Instruction: CancelPrayer".to_string(),
+            format!("Program {token_program} invoke [2]"),
+            data_line(&paused),
+            format!("Program {token_program} failed: custom program error: 0x1"),
+            data_line(&cancelled),
+            "Program data: not base64!".to_string(),
+            format!("Program data: {}", STANDARD.encode([0u8; 16])),
+            data_line(&paused),
+            format!("Program {ID} consumed 12345 of 200000 compute units"),
+            format!("Program {ID} success"),
+            data_line(&paused),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            ChorusEvent::PrayerCancelled(event) if event.id == 7 && event.requester == requester
+        ));
+        assert!(matches!(
+            &events[1],
+            ChorusEvent::PauseUpdated(event) if event.paused == 3
+        ));
+    }
+
+    #[test]
+    fn decode_rejects_unknown_and_truncated_events() {
+        let event = PrayerCancelled {
+            id: 1,
+            requester: Pubkey::new_unique(),
+        };
+        let data = event.data();
+        assert!(ChorusEvent::decode(&data).is_some());
+        assert!(ChorusEvent::decode(&data[..data.len() - 1]).is_none());
+        assert!(ChorusEvent::decode(&[0u8; 40]).is_none());
+        assert!(ChorusEvent::decode(&[]).is_none());
+    }
+}
@@ -0,0 +1,704 @@
+//! Instruction builders, one per program instruction. Accounts are derived
+//! from the ids and wallets passed in; callers only sign and send.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use chorus_prayers::{accounts, instruction, ProtocolConfig, ID};
+
+use crate::pda::{
+    agent_pda, answer_pda, associated_token_address, claim_pda, contribution_pda, escrow_pda,
+    prayer_chain_pda, prayer_pda,
+};
+
+/// Token accounts for an SPL / Token-2022 bounty. Leave as None for native SOL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenBounty {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    /// Funds the bounty and receives refunds (post, cancel, close, resolve_dispute)
+    pub requester_token_account: Pubkey,
+}
+
+impl TokenBounty {
+    /// Bounty funded from and refunded to the requester's associated token account
+    pub fn new(mint: Pubkey, token_program: Pubkey, requester: &Pubkey) -> Self {
+        Self {
+            mint,
+            token_program,
+            requester_token_account: associated_token_address(requester, &mint, &token_program),
+        }
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Remaining accounts for a payout: [claim PDA, recipient (mut)] per claimer.
+/// The recipient is the claimer wallet for SOL bounties, or the claimer's
+/// token account for token bounties.
+pub fn payout_accounts(prayer_id: u64, payouts: &[(Pubkey, Pubkey)]) -> Vec<AccountMeta> {
+    payouts
+        .iter()
+        .flat_map(|(claimer, recipient)| {
+            [
+                AccountMeta::new_readonly(claim_pda(prayer_id, claimer).0, false),
+                AccountMeta::new(*recipient, false),
+            ]
+        })
+        .collect()
+}
+
+// ── Authority ─────────────────────────────────────────────
+
+pub fn initialize(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::Initialize {
+            prayer_chain: prayer_chain_pda().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize {},
+    )
+}
+
+pub fn propose_authority(authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAuthority {
+            prayer_chain: prayer_chain_pda().0,
+            authority: *authority,
+        },
+        instruction::ProposeAuthority { new_authority },
+    )
+}
+
+pub fn accept_authority(new_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAuthority {
+            prayer_chain: prayer_chain_pda().0,
+            new_authority: *new_authority,
+        },
+        instruction::AcceptAuthority {},
+    )
+}
+
+pub fn renounce_authority(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::RenounceAuthority {
+            prayer_chain: prayer_chain_pda().0,
+            authority: *authority,
+        },
+        instruction::RenounceAuthority {},
+    )
+}
+
+pub fn update_config(authority: &Pubkey, config: ProtocolConfig) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            prayer_chain: prayer_chain_pda().0,
+            authority: *authority,
+        },
+        instruction::UpdateConfig { config },
+    )
+}
+
+pub fn set_arbiters(authority: &Pubkey, arbiters: Vec<Pubkey>) -> Instruction {
+    build(
+        accounts::SetArbiters {
+            prayer_chain: prayer_chain_pda().0,
+            authority: *authority,
+        },
+        instruction::SetArbiters { arbiters },
+    )
+}
+
+/// `paused` is a combination of the PAUSE_* bits (0 unpauses everything)
+pub fn set_pause(authority: &Pubkey, paused: u16) -> Instruction {
+    build(
+        accounts::SetPause {
+            prayer_chain: prayer_chain_pda().0,
+            authority: *authority,
+        },
+        instruction::SetPause { paused },
+    )
+}
+
+// ── Agents ────────────────────────────────────────────────
+
+pub fn register_agent(
+    wallet: &Pubkey,
+    name: String,
+    skills: String,
+    encryption_key: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::RegisterAgent {
+            prayer_chain: prayer_chain_pda().0,
+            agent: agent_pda(wallet).0,
+            wallet: *wallet,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterAgent {
+            name,
+            skills,
+            encryption_key,
+        },
+    )
+}
+
+/// None leaves a field unchanged
+pub fn update_agent(
+    wallet: &Pubkey,
+    name: Option<String>,
+    skills: Option<String>,
+    encryption_key: Option<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::UpdateAgent {
+            prayer_chain: prayer_chain_pda().0,
+            agent: agent_pda(wallet).0,
+            wallet: *wallet,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateAgent {
+            name,
+            skills,
+            encryption_key,
+        },
+    )
+}
+
+pub fn close_agent(wallet: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseAgent {
+            prayer_chain: prayer_chain_pda().0,
+            agent: agent_pda(wallet).0,
+            wallet: *wallet,
+        },
+        instruction::CloseAgent {},
+    )
+}
+
+// ── Prayers ───────────────────────────────────────────────
+
+/// `prayer_id` must be the chain's current `total_prayers`
+pub fn post_prayer(
+    requester: &Pubkey,
+    prayer_id: u64,
+    args: instruction::PostPrayer,
+    token: Option<&TokenBounty>,
+) -> Instruction {
+    build(
+        accounts::PostPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            requester_agent: agent_pda(requester).0,
+            prayer: prayer_pda(prayer_id).0,
+            requester: *requester,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn extend_prayer(requester: &Pubkey, prayer_id: u64, extra_seconds: i64) -> Instruction {
+    build(
+        accounts::ExtendPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            requester: *requester,
+        },
+        instruction::ExtendPrayer { extra_seconds },
+    )
+}
+
+pub fn top_up_bounty(
+    requester: &Pubkey,
+    prayer_id: u64,
+    amount: u64,
+    token: Option<&TokenBounty>,
+) -> Instruction {
+    build(
+        accounts::TopUpBounty {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            requester: *requester,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::TopUpBounty { amount },
+    )
+}
+
+pub fn fund_prayer(funder: &Pubkey, prayer_id: u64, amount: u64) -> Instruction {
+    build(
+        accounts::FundPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            contribution: contribution_pda(prayer_id, funder).0,
+            funder_claim: claim_pda(prayer_id, funder).0,
+            funder: *funder,
+            system_program: system_program::ID,
+        },
+        instruction::FundPrayer { amount },
+    )
+}
+
+pub fn claim_prayer(claimer: &Pubkey, prayer_id: u64) -> Instruction {
+    build(
+        accounts::ClaimPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, claimer).0,
+            claimer_contribution: contribution_pda(prayer_id, claimer).0,
+            claimer_agent: agent_pda(claimer).0,
+            claimer: *claimer,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimPrayer {},
+    )
+}
+
+pub fn approve_claim(requester: &Pubkey, prayer_id: u64, claimer: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveClaim {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, claimer).0,
+            requester: *requester,
+        },
+        instruction::ApproveClaim {},
+    )
+}
+
+pub fn reject_claim(requester: &Pubkey, prayer_id: u64, claimer: &Pubkey) -> Instruction {
+    build(
+        accounts::RejectClaim {
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, claimer).0,
+            claimer_agent: agent_pda(claimer).0,
+            claimer_wallet: *claimer,
+            requester: *requester,
+        },
+        instruction::RejectClaim {},
+    )
+}
+
+pub fn heartbeat_claim(claimer: &Pubkey, prayer_id: u64) -> Instruction {
+    build(
+        accounts::HeartbeatClaim {
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, claimer).0,
+            claimer: *claimer,
+        },
+        instruction::HeartbeatClaim {},
+    )
+}
+
+pub fn deliver_content(
+    requester: &Pubkey,
+    prayer_id: u64,
+    claimer: &Pubkey,
+    encrypted_content: Vec<u8>,
+) -> Instruction {
+    build(
+        accounts::DeliverContent {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, claimer).0,
+            requester: *requester,
+        },
+        instruction::DeliverContent { encrypted_content },
+    )
+}
+
+pub fn answer_prayer(
+    answerer: &Pubkey,
+    prayer_id: u64,
+    answer_hash: [u8; 32],
+    encrypted_answer: Vec<u8>,
+) -> Instruction {
+    build(
+        accounts::AnswerPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, answerer).0,
+            answerer_agent: agent_pda(answerer).0,
+            answerer: *answerer,
+        },
+        instruction::AnswerPrayer {
+            answer_hash,
+            encrypted_answer,
+        },
+    )
+}
+
+pub fn submit_answer(
+    answerer: &Pubkey,
+    prayer_id: u64,
+    answer_hash: [u8; 32],
+    encrypted_answer: Vec<u8>,
+) -> Instruction {
+    build(
+        accounts::SubmitAnswer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, answerer).0,
+            answer: answer_pda(prayer_id, answerer).0,
+            answerer: *answerer,
+            system_program: system_program::ID,
+        },
+        instruction::SubmitAnswer {
+            answer_hash,
+            encrypted_answer,
+        },
+    )
+}
+
+/// `ranking` lists (answerer wallet, weight in bps), best answer first
+pub fn select_winners(
+    requester: &Pubkey,
+    prayer_id: u64,
+    ranking: &[(Pubkey, u16)],
+) -> Instruction {
+    let winner = ranking
+        .first()
+        .map(|(answerer, _)| *answerer)
+        .unwrap_or_default();
+    let mut ix = build(
+        accounts::SelectWinners {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            winner_agent: agent_pda(&winner).0,
+            requester: *requester,
+        },
+        instruction::SelectWinners {
+            weights_bps: ranking.iter().map(|(_, bps)| *bps).collect(),
+        },
+    );
+    ix.accounts.extend(ranking.iter().flat_map(|(answerer, _)| {
+        [
+            AccountMeta::new(answer_pda(prayer_id, answerer).0, false),
+            AccountMeta::new(claim_pda(prayer_id, answerer).0, false),
+        ]
+    }));
+    ix
+}
+
+/// `payouts` lists (answerer wallet, recipient) for every submitted answer;
+/// the recipient is the answerer's wallet, or its token account for `token`
+pub fn settle_competition(
+    caller: &Pubkey,
+    prayer_id: u64,
+    token: Option<&TokenBounty>,
+    payouts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleCompetition {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            caller: *caller,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::SettleCompetition {},
+    );
+    ix.accounts
+        .extend(payouts.iter().flat_map(|(answerer, recipient)| {
+            [
+                AccountMeta::new_readonly(answer_pda(prayer_id, answerer).0, false),
+                AccountMeta::new(*recipient, false),
+            ]
+        }));
+    ix
+}
+
+/// `weights` lists (claimer wallet, weight in bps) for every claimer
+pub fn set_claim_weights(
+    setter: &Pubkey,
+    prayer_id: u64,
+    weights: &[(Pubkey, u16)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SetClaimWeights {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            setter: *setter,
+        },
+        instruction::SetClaimWeights {
+            weights_bps: weights.iter().map(|(_, bps)| *bps).collect(),
+        },
+    );
+    ix.accounts.extend(
+        weights
+            .iter()
+            .map(|(claimer, _)| AccountMeta::new(claim_pda(prayer_id, claimer).0, false)),
+    );
+    ix
+}
+
+/// `payouts` lists (claimer wallet, recipient) for every claimer, see [`payout_accounts`]
+pub fn confirm_prayer(
+    requester: &Pubkey,
+    prayer_id: u64,
+    answerer: &Pubkey,
+    token: Option<&TokenBounty>,
+    payouts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::ConfirmPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            answerer_agent: agent_pda(answerer).0,
+            requester: *requester,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::ConfirmPrayer {},
+    );
+    ix.accounts.extend(payout_accounts(prayer_id, payouts));
+    ix
+}
+
+/// Permissionless once the review window has elapsed or funders voted to confirm
+pub fn finalize_prayer(
+    caller: &Pubkey,
+    prayer_id: u64,
+    answerer: &Pubkey,
+    token: Option<&TokenBounty>,
+    payouts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::FinalizePrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            answerer_agent: agent_pda(answerer).0,
+            caller: *caller,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::FinalizePrayer {},
+    );
+    ix.accounts.extend(payout_accounts(prayer_id, payouts));
+    ix
+}
+
+pub fn vote_confirm(funder: &Pubkey, prayer_id: u64) -> Instruction {
+    build(
+        accounts::VoteConfirm {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            contribution: Some(contribution_pda(prayer_id, funder).0),
+            funder: *funder,
+        },
+        instruction::VoteConfirm {},
+    )
+}
+
+/// The requester's own vote, weighted by the part of the bounty it put in
+pub fn vote_confirm_requester(requester: &Pubkey, prayer_id: u64) -> Instruction {
+    build(
+        accounts::VoteConfirm {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            contribution: None,
+            funder: *requester,
+        },
+        instruction::VoteConfirm {},
+    )
+}
+
+pub fn open_dispute(requester: &Pubkey, prayer_id: u64, reason_hash: [u8; 32]) -> Instruction {
+    build(
+        accounts::OpenDispute {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            requester: *requester,
+        },
+        instruction::OpenDispute { reason_hash },
+    )
+}
+
+/// `payouts` may be empty when `claimer_bps` is 0
+pub fn resolve_dispute(
+    arbiter: &Pubkey,
+    prayer_id: u64,
+    requester: &Pubkey,
+    answerer: &Pubkey,
+    claimer_bps: u16,
+    token: Option<&TokenBounty>,
+    payouts: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::ResolveDispute {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            answerer_agent: agent_pda(answerer).0,
+            requester: *requester,
+            arbiter: *arbiter,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::ResolveDispute { claimer_bps },
+    );
+    ix.accounts.extend(payout_accounts(prayer_id, payouts));
+    ix
+}
+
+/// Refund a dispute the arbiters didn't rule on in time. Anyone can call it.
+pub fn expire_dispute(
+    caller: &Pubkey,
+    requester: &Pubkey,
+    prayer_id: u64,
+    token: Option<&TokenBounty>,
+) -> Instruction {
+    build(
+        accounts::ExpireDispute {
+            prayer: prayer_pda(prayer_id).0,
+            requester: *requester,
+            caller: *caller,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::ExpireDispute {},
+    )
+}
+
+pub fn cancel_prayer(
+    requester: &Pubkey,
+    prayer_id: u64,
+    token: Option<&TokenBounty>,
+) -> Instruction {
+    build(
+        accounts::CancelPrayer {
+            prayer: prayer_pda(prayer_id).0,
+            requester: *requester,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::CancelPrayer {},
+    )
+}
+
+/// Close the claims `claimers` hold on a resolved prayer. Anyone can call it.
+pub fn sweep_claims(caller: &Pubkey, prayer_id: u64, claimers: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::SweepClaims {
+            prayer: prayer_pda(prayer_id).0,
+            caller: *caller,
+        },
+        instruction::SweepClaims {},
+    );
+    ix.accounts.extend(claimers.iter().flat_map(|claimer| {
+        [
+            AccountMeta::new(claim_pda(prayer_id, claimer).0, false),
+            AccountMeta::new(agent_pda(claimer).0, false),
+            AccountMeta::new(*claimer, false),
+        ]
+    }));
+    ix
+}
+
+/// Release `claimer`'s claim. `caller` is the claimer, or anyone once the
+/// claim has timed out or the prayer is resolved or expired.
+pub fn unclaim_prayer(caller: &Pubkey, prayer_id: u64, claimer: &Pubkey) -> Instruction {
+    build(
+        accounts::UnclaimPrayer {
+            prayer_chain: prayer_chain_pda().0,
+            prayer: prayer_pda(prayer_id).0,
+            claim: claim_pda(prayer_id, claimer).0,
+            claimer_agent: agent_pda(claimer).0,
+            claimer_wallet: *claimer,
+            caller: *caller,
+        },
+        instruction::UnclaimPrayer {},
+    )
+}
+
+/// Refund `funder`'s contribution to a resolved prayer. Anyone can call it.
+pub fn withdraw_contribution(caller: &Pubkey, prayer_id: u64, funder: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawContribution {
+            prayer: prayer_pda(prayer_id).0,
+            contribution: contribution_pda(prayer_id, funder).0,
+            funder_wallet: *funder,
+            caller: *caller,
+        },
+        instruction::WithdrawContribution {},
+    )
+}
+
+pub fn close_answer(caller: &Pubkey, prayer_id: u64, answerer: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseAnswer {
+            prayer: prayer_pda(prayer_id).0,
+            answer: answer_pda(prayer_id, answerer).0,
+            answerer_wallet: *answerer,
+            caller: *caller,
+        },
+        instruction::CloseAnswer {},
+    )
+}
+
+pub fn close_prayer(
+    requester: &Pubkey,
+    prayer_id: u64,
+    token: Option<&TokenBounty>,
+) -> Instruction {
+    build(
+        accounts::ClosePrayer {
+            prayer: prayer_pda(prayer_id).0,
+            requester_agent: agent_pda(requester).0,
+            requester: *requester,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::ClosePrayer {},
+    )
+}
+
+/// Expire `requester`'s prayer. `keeper` is any wallet; it earns the keeper
+/// fee if the prayer gets closed.
+pub fn expire_prayer(
+    keeper: &Pubkey,
+    requester: &Pubkey,
+    prayer_id: u64,
+    token: Option<&TokenBounty>,
+) -> Instruction {
+    build(
+        accounts::ExpirePrayer {
+            prayer: prayer_pda(prayer_id).0,
+            requester_agent: agent_pda(requester).0,
+            requester: *requester,
+            keeper: *keeper,
+            reward_mint: token.map(|t| t.mint),
+            escrow: token.map(|_| escrow_pda(prayer_id).0),
+            requester_token_account: token.map(|t| t.requester_token_account),
+            token_program: token.map(|t| t.token_program),
+        },
+        instruction::ExpirePrayer {},
+    )
+}
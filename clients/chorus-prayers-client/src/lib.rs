@@ -0,0 +1,27 @@
+//! Rust client for the CHORUS Prayer Chain program.
+//!
+//! Everything here is built from the program crate itself (compiled with the
+//! `cpi` / `no-entrypoint` features), so seeds, account layouts and
+//! instruction data can't drift from what runs on-chain.
+//!
+//! - [`pda`] — addresses for `prayer-chain`, `agent`, `prayer`, `claim`, `answer`,
+//!   `contribution` and `escrow`
+//! - [`instructions`] — one builder per program instruction
+//! - [`state`] — account decoders
+//! - [`events`] — event decoders and a transaction log parser
+
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod state;
+
+pub use chorus_prayers::{
+    Agent, Answer, Claim, ClaimRequirements, ClaimWeight, ClaimerPayout, Contribution, Prayer,
+    PrayerChain, PrayerError, PrayerStatus, PrayerType, ProtocolConfig, RemainderPolicy,
+    ID as PROGRAM_ID, KEEPER_FEE_LAMPORTS, OPTION_ALL, OPTION_APPROVAL, OPTION_COMPETITION,
+    OPTION_REQUIRE_DELIVERY, PAUSE_ALL, PAUSE_ANSWER, PAUSE_CLAIM, PAUSE_CONFIRM, PAUSE_DISPUTE,
+    PAUSE_POST, PAUSE_REGISTER,
+};
+pub use events::{parse_logs, ChorusEvent};
+pub use instructions::TokenBounty;
+pub use state::ChorusAccount;
@@ -0,0 +1,58 @@
+//! PDA derivation, mirroring the seeds in the program's account constraints.
+
+use anchor_lang::prelude::Pubkey;
+use chorus_prayers::ID;
+
+/// Associated Token Account program, for deriving default bounty token accounts
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    anchor_lang::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Global PrayerChain singleton
+pub fn prayer_chain_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"prayer-chain"], &ID)
+}
+
+/// Agent profile for a wallet
+pub fn agent_pda(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"agent", wallet.as_ref()], &ID)
+}
+
+/// Prayer by id (ids are assigned from PrayerChain.total_prayers)
+pub fn prayer_pda(prayer_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"prayer", &prayer_id.to_le_bytes()], &ID)
+}
+
+/// A claimer's Claim on a prayer
+pub fn claim_pda(prayer_id: u64, claimer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"claim", &prayer_id.to_le_bytes(), claimer.as_ref()], &ID)
+}
+
+/// An answerer's competing Answer on a competition prayer
+pub fn answer_pda(prayer_id: u64, answerer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"answer", &prayer_id.to_le_bytes(), answerer.as_ref()],
+        &ID,
+    )
+}
+
+/// A funder's Contribution to a crowdfunded prayer
+pub fn contribution_pda(prayer_id: u64, funder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"contribution", &prayer_id.to_le_bytes(), funder.as_ref()],
+        &ID,
+    )
+}
+
+/// Token escrow for an SPL / Token-2022 bounty (authority is the prayer PDA)
+pub fn escrow_pda(prayer_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"escrow", &prayer_id.to_le_bytes()], &ID)
+}
+
+/// Associated token account of `owner` for `mint` under `token_program`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
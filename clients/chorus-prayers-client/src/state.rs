@@ -0,0 +1,108 @@
+//! Account decoders.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{AccountDeserialize, Discriminator, Result};
+use chorus_prayers::{Agent, Answer, Claim, Contribution, Prayer, PrayerChain};
+
+/// Any account owned by the program, decoded by its discriminator
+pub enum ChorusAccount {
+    PrayerChain(PrayerChain),
+    Agent(Agent),
+    Prayer(Prayer),
+    Claim(Claim),
+    Answer(Answer),
+    Contribution(Contribution),
+}
+
+impl ChorusAccount {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.starts_with(PrayerChain::DISCRIMINATOR) {
+            decode(data).map(Self::PrayerChain)
+        } else if data.starts_with(Agent::DISCRIMINATOR) {
+            decode(data).map(Self::Agent)
+        } else if data.starts_with(Prayer::DISCRIMINATOR) {
+            decode(data).map(Self::Prayer)
+        } else if data.starts_with(Claim::DISCRIMINATOR) {
+            decode(data).map(Self::Claim)
+        } else if data.starts_with(Answer::DISCRIMINATOR) {
+            decode(data).map(Self::Answer)
+        } else if data.starts_with(Contribution::DISCRIMINATOR) {
+            decode(data).map(Self::Contribution)
+        } else {
+            Err(ErrorCode::AccountDiscriminatorMismatch.into())
+        }
+    }
+}
+
+/// Decode raw account data (discriminator included) as `T`
+pub fn decode<T: AccountDeserialize>(mut data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::AccountSerialize;
+
+    fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn decode_dispatches_on_discriminator() {
+        let funder = Pubkey::new_unique();
+        let contribution = Contribution {
+            prayer_id: 4,
+            funder,
+            amount: 1_000,
+            voted: true,
+            bump: 255,
+        };
+        let claim = Claim {
+            prayer_id: 4,
+            claimer: funder,
+            content_delivered: false,
+            claimed_at: 10,
+            weight_bps: 2_500,
+            approved: true,
+            stake: 50,
+            last_heartbeat: 20,
+            answered: false,
+            bump: 254,
+        };
+
+        match ChorusAccount::decode(&serialize(&contribution)) {
+            Ok(ChorusAccount::Contribution(decoded)) => {
+                assert_eq!(decoded.funder, funder);
+                assert_eq!(decoded.amount, 1_000);
+                assert!(decoded.voted);
+            }
+            _ => panic!("expected a Contribution"),
+        }
+        match ChorusAccount::decode(&serialize(&claim)) {
+            Ok(ChorusAccount::Claim(decoded)) => {
+                assert_eq!(decoded.weight_bps, 2_500);
+                assert_eq!(decoded.last_heartbeat, 20);
+            }
+            _ => panic!("expected a Claim"),
+        }
+    }
+
+    #[test]
+    fn decode_rejects_unknown_and_truncated_accounts() {
+        let contribution = Contribution {
+            prayer_id: 1,
+            funder: Pubkey::new_unique(),
+            amount: 1,
+            voted: false,
+            bump: 0,
+        };
+        let data = serialize(&contribution);
+        assert!(ChorusAccount::decode(&data[..data.len() - 1]).is_err());
+        assert!(ChorusAccount::decode(&[7u8; 64]).is_err());
+        assert!(ChorusAccount::decode(&[]).is_err());
+    }
+}
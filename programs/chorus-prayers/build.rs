@@ -0,0 +1,28 @@
+//! Detects whether `anchor build` has produced the program binary the
+//! LiteSVM integration tests load. Without it the suites compile to nothing
+//! and a warning says why; set `CHORUS_REQUIRE_SBF=1` to make a missing
+//! binary a hard error instead.
+
+use std::path::Path;
+
+const PROGRAM_SO: &str = "../../target/deploy/chorus_prayers.so";
+
+fn main() {
+    println!("cargo:rerun-if-changed={PROGRAM_SO}");
+    println!("cargo:rerun-if-env-changed=CHORUS_REQUIRE_SBF");
+
+    // The on-chain build itself never needs the binary
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("solana") {
+        return;
+    }
+
+    if Path::new(PROGRAM_SO).exists() {
+        println!("cargo:rustc-cfg=sbf_program_built");
+    } else if std::env::var_os("CHORUS_REQUIRE_SBF").is_some_and(|v| v != "0") {
+        panic!("{PROGRAM_SO} is missing (run `anchor build` first)");
+    } else {
+        println!(
+            "cargo:warning=skipping LiteSVM integration tests: {PROGRAM_SO} is missing (run `anchor build` first)"
+        );
+    }
+}
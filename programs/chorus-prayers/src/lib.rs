@@ -1,13 +1,109 @@
-use anchor_lang::prelude::*;
+// Instruction arguments are flat (as in the IDL), and #[program] expands each
+// handler into several generated functions that take all of them.
+#![allow(clippy::too_many_arguments)]
 
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+// The PrayerChain, Agent, Prayer and Claim layouts have changed since the
+// original devnet deployment at this ID, and there is no migration
+// instruction: an in-place upgrade leaves every existing account undecodable
+// and the chain can't be re-initialized. This ID is for localnet only; devnet
+// and mainnet deploy under fresh program IDs (see README, "Deploying").
 declare_id!("Af61jGnh2AceK3E8FAxCh9j7Jt6JWtJz6PUtbciDjVJS");
 
+// Defaults written to ProtocolConfig at initialize. The authority can change
+// them later with update_config; instructions read the live config.
+
 /// Claim timeout: 1 hour. After this, anyone can unclaim a stale claim.
 const CLAIM_TIMEOUT_SECONDS: i64 = 3600;
 
 /// Maximum number of collaborators per prayer
 const MAX_CLAIMERS_LIMIT: u8 = 10;
 
+/// Longest prayer TTL: 7 days
+const MAX_TTL_SECONDS: i64 = 604_800;
+
+/// Longest review window a requester can set: 30 days.
+/// After the window, anyone can finalize a fulfilled prayer.
+const MAX_REVIEW_SECONDS: i64 = 2_592_000;
+
+/// Arbiters' window to rule on a dispute: 7 days.
+/// After it, anyone can refund the requester with expire_dispute.
+const DISPUTE_TIMEOUT_SECONDS: i64 = 604_800;
+
+// Ceilings on what update_config accepts, so deadlines computed from the
+// config can't overflow and a prayer's payouts still fit in one transaction.
+
+/// Longest duration any config field can set: 1 year
+const MAX_CONFIG_SECONDS: i64 = 31_536_000;
+
+/// Most collaborators per prayer the config can allow
+const MAX_CLAIMERS_CEILING: u8 = 20;
+
+/// Most reputation one answer or confirmation can award
+const MAX_REPUTATION_AWARD: u64 = 1_000;
+
+/// Reputation for submitting an answer
+const ANSWER_REPUTATION: u64 = 10;
+
+/// Bonus reputation when the answer is confirmed
+const CONFIRM_REPUTATION: u64 = 5;
+
+/// Maximum number of arbiters that can rule on disputes
+const MAX_ARBITERS: usize = 5;
+
+/// Most claims an approval-mode prayer can hold waiting for the requester
+const MAX_PENDING_CLAIMS: u8 = 32;
+
+/// Maximum number of wallets a prayer can be restricted to
+const MAX_INVITED: usize = 5;
+
+/// Basis-point denominator for dispute rulings
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Paid out of an expired prayer's rent to whoever closes it with expire_prayer
+pub const KEEPER_FEE_LAMPORTS: u64 = 100_000;
+
+// Pause bits for PrayerChain.paused, one per instruction family. Refund paths
+// (cancel_prayer, unclaim_prayer, reject_claim, sweep_claims, close_answer, close_prayer,
+// expire_prayer, expire_dispute, withdraw_contribution, close_agent) and authority
+// instructions are never paused.
+
+/// register_agent, update_agent
+pub const PAUSE_REGISTER: u16 = 1 << 0;
+/// post_prayer, extend_prayer, top_up_bounty, fund_prayer
+pub const PAUSE_POST: u16 = 1 << 1;
+/// claim_prayer, approve_claim
+pub const PAUSE_CLAIM: u16 = 1 << 2;
+/// deliver_content, answer_prayer, submit_answer
+pub const PAUSE_ANSWER: u16 = 1 << 3;
+/// select_winners, set_claim_weights, confirm_prayer, finalize_prayer, vote_confirm,
+/// settle_competition
+pub const PAUSE_CONFIRM: u16 = 1 << 4;
+/// open_dispute, resolve_dispute
+pub const PAUSE_DISPUTE: u16 = 1 << 5;
+/// Every pausable family
+pub const PAUSE_ALL: u16 =
+    PAUSE_REGISTER | PAUSE_POST | PAUSE_CLAIM | PAUSE_ANSWER | PAUSE_CONFIRM | PAUSE_DISPUTE;
+
+// Option bits for Prayer.options, chosen by the requester at post time.
+
+/// Every claimer submits its own Answer; the requester ranks them with select_winners
+pub const OPTION_COMPETITION: u8 = 1 << 0;
+/// Claims start pending until the requester runs approve_claim
+pub const OPTION_APPROVAL: u8 = 1 << 1;
+/// Only claimers whose content was delivered (deliver_content) can answer
+pub const OPTION_REQUIRE_DELIVERY: u8 = 1 << 2;
+/// Every known option
+pub const OPTION_ALL: u8 = OPTION_COMPETITION | OPTION_APPROVAL | OPTION_REQUIRE_DELIVERY;
+
 /// Prayer types
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum PrayerType {
@@ -25,8 +121,60 @@ pub enum PrayerStatus {
     Active,     // All slots filled, work in progress
     Fulfilled,  // Answer submitted, awaiting confirmation
     Confirmed,  // Requester approved, bounty distributed
-    Expired,    // TTL elapsed
-    Cancelled,  // Requester cancelled (only when 0 claims)
+    Expired,    // TTL elapsed and the bounty was refunded by expire_prayer
+    Cancelled,  // Requester cancelled (only when 0 claims), or refunded in full by an arbiter
+    Disputed,   // Requester rejected the answer, awaiting an arbiter ruling until ruling_deadline
+}
+
+/// Where the remainder of an uneven bounty split goes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RemainderPolicy {
+    Requester,  // Left in escrow and refunded by close_prayer
+    Answerer,   // Added to the answerer's share
+    RoundRobin, // One unit each to the first paid claimers, in wallet address order
+}
+
+/// Protocol limits and reputation amounts, set by the authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolConfig {
+    pub claim_timeout_seconds: i64, // Default claim timeout for prayers that don't set one
+    pub max_claimers: u8,           // Upper bound for a prayer's max_claimers
+    pub max_ttl_seconds: i64,       // Upper bound for a prayer's TTL
+    pub max_review_seconds: i64,    // Upper bound for a prayer's review window
+    pub dispute_timeout_seconds: i64, // Arbiters' window to rule before anyone can expire_dispute
+    pub answer_reputation: u64,     // Reputation for answering
+    pub confirm_reputation: u64,    // Bonus reputation when an answer is confirmed
+}
+
+impl ProtocolConfig {
+    pub const INIT_SPACE: usize = 8 + 1 + 8 + 8 + 8 + 8 + 8;
+}
+
+impl Default for ProtocolConfig {
+    fn default() -> Self {
+        Self {
+            claim_timeout_seconds: CLAIM_TIMEOUT_SECONDS,
+            max_claimers: MAX_CLAIMERS_LIMIT,
+            max_ttl_seconds: MAX_TTL_SECONDS,
+            max_review_seconds: MAX_REVIEW_SECONDS,
+            dispute_timeout_seconds: DISPUTE_TIMEOUT_SECONDS,
+            answer_reputation: ANSWER_REPUTATION,
+            confirm_reputation: CONFIRM_REPUTATION,
+        }
+    }
+}
+
+/// What an agent needs to claim a prayer, set by the requester at post time.
+/// Zero means no requirement.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClaimRequirements {
+    pub min_reputation: u64,        // Agent.reputation
+    pub min_confirmed: u64,         // Agent.prayers_confirmed
+    pub min_agent_age_seconds: i64, // Time since Agent.registered_at
+}
+
+impl ClaimRequirements {
+    pub const INIT_SPACE: usize = 8 + 8 + 8;
 }
 
 // ── Accounts ──────────────────────────────────────────────
@@ -34,15 +182,24 @@ pub enum PrayerStatus {
 /// Global protocol state (singleton PDA)
 #[account]
 pub struct PrayerChain {
-    pub authority: Pubkey,
+    pub authority: Pubkey,         // Default pubkey once renounced
+    pub pending_authority: Pubkey, // Proposed successor (default = none)
     pub total_prayers: u64,
     pub total_answered: u64,
     pub total_agents: u64,
+    pub arbiters: Vec<Pubkey>,   // Can rule on disputed prayers (max 5)
+    pub config: ProtocolConfig,
+    pub paused: u16,             // PAUSE_* bits for instruction families that are stopped
     pub bump: u8,
 }
 
 impl PrayerChain {
-    pub const INIT_SPACE: usize = 32 + 8 + 8 + 8 + 1;
+    pub const INIT_SPACE: usize =
+        32 + 32 + 8 + 8 + 8 + (4 + 32 * MAX_ARBITERS) + ProtocolConfig::INIT_SPACE + 2 + 1;
+
+    pub fn is_paused(&self, family: u16) -> bool {
+        self.paused & family != 0
+    }
 }
 
 /// Agent identity and reputation
@@ -52,18 +209,26 @@ pub struct Agent {
     pub name: String,            // max 32
     pub skills: String,          // max 256
     pub encryption_key: [u8; 32], // X25519 public key for private prayers
+    pub key_epoch: u32,          // Bumped each time encryption_key is rotated
     pub prayers_posted: u64,
     pub prayers_answered: u64,
     pub prayers_confirmed: u64,
     pub reputation: u64,
     pub registered_at: i64,
+    pub open_claims: u32,        // Live Claim PDAs held by this agent
+    pub open_prayers: u32,       // Prayers posted and not yet closed
     pub bump: u8,
 }
 
 impl Agent {
     pub const MAX_NAME: usize = 32;
     pub const MAX_SKILLS: usize = 256;
-    pub const INIT_SPACE: usize = 32 + 36 + 260 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Account size for the given name and skills lengths. Agents are sized to
+    /// fit and reallocated by update_agent when the strings change.
+    pub fn space(name_len: usize, skills_len: usize) -> usize {
+        32 + (4 + name_len) + (4 + skills_len) + 32 + 4 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 1
+    }
 }
 
 /// A prayer — supports multiple collaborating claimers
@@ -73,21 +238,89 @@ pub struct Prayer {
     pub requester: Pubkey,
     pub prayer_type: PrayerType,
     pub content_hash: [u8; 32],
-    pub reward_lamports: u64,
+    pub reward_lamports: u64,    // Bounty amount (lamports, or token base units if reward_mint is set)
+    pub reward_mint: Pubkey,     // SPL / Token-2022 mint for the bounty (default = native SOL)
     pub status: PrayerStatus,
     pub max_claimers: u8,        // How many agents can collaborate (1 = solo, >1 = collab)
-    pub num_claimers: u8,        // Current number of claims
+    pub num_claimers: u8,        // Current number of approved claims
     pub answerer: Pubkey,        // Who submitted the answer (must be a claimer)
     pub answer_hash: [u8; 32],
     pub created_at: i64,
     pub expires_at: i64,
     pub fulfilled_at: i64,
+    pub review_seconds: i64,     // Requester's window to confirm or dispute after fulfilled_at
+    pub ruling_deadline: i64,    // Once disputed, when anyone can expire_dispute an unruled prayer
+    pub remainder_policy: RemainderPolicy, // Where the split remainder goes
+    pub weighted: bool,          // Split by each Claim's weight_bps instead of equally
+    pub requester_weighted: bool, // The requester set the weights; the answerer can't replace them
+    pub options: u8,             // OPTION_* bits
+    pub num_answers: u8,         // Live Answer PDAs (competition prayers)
+    pub num_pending: u8,         // Claims awaiting approval (not counted in num_claimers)
+    pub invited: Vec<Pubkey>,    // Only these wallets can claim (max 5, empty = anyone)
+    pub requirements: ClaimRequirements, // Minimum agent standing to claim
+    pub claim_stake: u64,        // Lamports each claimer deposits into its Claim PDA
+    pub claim_timeout_seconds: i64, // Anyone can unclaim a claim this long past its last heartbeat
+    pub funded_lamports: u64,    // Part of reward_lamports added by other wallets with fund_prayer
+    pub num_funders: u16,        // Live Contribution PDAs
+    pub funder_refund: u64,      // Funders' pro-rata share of refunds, held for withdraw_contribution
+    pub confirm_votes: u64,      // Lamports whose holders voted to confirm the answer
+    pub requester_voted: bool,   // The requester voted too; funder votes alone wait out half the review window
     pub bump: u8,
 }
 
 impl Prayer {
-    // 8 + 32 + 1 + 32 + 8 + 1 + 1 + 1 + 32 + 32 + 8 + 8 + 8 + 1 = 173
-    pub const INIT_SPACE: usize = 8 + 32 + 1 + 32 + 8 + 1 + 1 + 1 + 32 + 32 + 8 + 8 + 8 + 1;
+    // 8 + 32 + 1 + 32 + 8 + 32 + 1 + 1 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 1 + 1 + 1
+    //   + (4 + 32 * 5) + 24 + 8 + 8 + 8 + 2 + 8 + 8 + 1 + 1 = 458
+    pub const INIT_SPACE: usize = 8 + 32 + 1 + 32 + 8 + 32 + 1 + 1 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8
+        + 1 + 1 + 1 + 1 + 1 + 1
+        + (4 + 32 * MAX_INVITED)
+        + ClaimRequirements::INIT_SPACE
+        + 8
+        + 8
+        + 8
+        + 2
+        + 8
+        + 8
+        + 1
+        + 1;
+
+    pub fn has_option(&self, option: u8) -> bool {
+        self.options & option != 0
+    }
+
+    /// Whether `wallet` may claim: anyone unless the prayer has an invite list
+    pub fn is_invited(&self, wallet: &Pubkey) -> bool {
+        self.invited.is_empty() || self.invited.contains(wallet)
+    }
+
+    /// Whether the bounty is escrowed in a token account rather than as lamports
+    pub fn is_token_bounty(&self) -> bool {
+        self.reward_mint != Pubkey::default()
+    }
+
+    /// Last moment the requester can confirm-or-dispute before anyone can finalize
+    pub fn review_deadline(&self) -> i64 {
+        self.fulfilled_at.checked_add(self.review_seconds).unwrap()
+    }
+
+    /// Last moment the requester of a competition can select_winners before
+    /// anyone can settle_competition
+    pub fn selection_deadline(&self) -> i64 {
+        self.expires_at.checked_add(self.review_seconds).unwrap()
+    }
+
+    /// Until when only a vote the requester joined can skip the review
+    /// window, so it keeps time to dispute before funders finalize without it
+    pub fn funder_vote_deadline(&self) -> i64 {
+        self.fulfilled_at.checked_add(self.review_seconds / 2).unwrap()
+    }
+
+    /// Whether voters holding more than half the bounty confirmed, with the
+    /// requester among them or past the funder_vote_deadline
+    pub fn funders_confirmed(&self, now: i64) -> bool {
+        (self.requester_voted || now > self.funder_vote_deadline())
+            && self.confirm_votes as u128 * 2 > self.reward_lamports as u128
+    }
 }
 
 /// A claim — one per claimer per prayer (separate PDA)
@@ -97,16 +330,107 @@ pub struct Claim {
     pub claimer: Pubkey,
     pub content_delivered: bool,
     pub claimed_at: i64,
+    pub weight_bps: u16,         // Contribution weight, used once the prayer is weighted
+    pub approved: bool,          // False while pending on an approval-mode prayer
+    pub stake: u64,              // Lamports deposited on claim, held above rent until returned or slashed
+    pub last_heartbeat: i64,     // Claim time, approval time or latest heartbeat_claim
+    pub answered: bool,          // Submitted a competition Answer; kept until the prayer resolves
     pub bump: u8,
 }
 
 impl Claim {
-    // 8 + 32 + 1 + 8 + 1 = 50
-    pub const INIT_SPACE: usize = 8 + 32 + 1 + 8 + 1;
+    // 8 + 32 + 1 + 8 + 2 + 1 + 8 + 8 + 1 + 1 = 70
+    pub const INIT_SPACE: usize = 8 + 32 + 1 + 8 + 2 + 1 + 8 + 8 + 1 + 1;
+
+    /// When anyone may remove this claim, given its prayer's claim timeout
+    pub fn deadline(&self, prayer: &Prayer) -> i64 {
+        self.last_heartbeat.checked_add(prayer.claim_timeout_seconds).unwrap()
+    }
+}
+
+/// A competing answer — one per claimer on a competition prayer (separate PDA)
+#[account]
+pub struct Answer {
+    pub prayer_id: u64,
+    pub answerer: Pubkey,
+    pub answer_hash: [u8; 32],
+    pub submitted_at: i64,
+    pub rank: u8,                // Position in the requester's ranking (1 = best, 0 = unranked)
+    pub bump: u8,
+}
+
+impl Answer {
+    // 8 + 32 + 32 + 8 + 1 + 1 = 82
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 1;
+}
+
+/// One funder's stake in a crowdfunded prayer (separate PDA)
+#[account]
+pub struct Contribution {
+    pub prayer_id: u64,
+    pub funder: Pubkey,
+    pub amount: u64,             // Lamports added with fund_prayer
+    pub voted: bool,             // Voted to confirm the answer (vote_confirm)
+    pub bump: u8,
+}
+
+impl Contribution {
+    // 8 + 32 + 8 + 1 + 1 = 50
+    pub const INIT_SPACE: usize = 8 + 32 + 8 + 1 + 1;
+
+    /// This funder's pro-rata share of the prayer's funder_refund
+    pub fn refund(&self, prayer: &Prayer) -> u64 {
+        (self.amount as u128 * prayer.funder_refund as u128 / prayer.funded_lamports as u128) as u64
+    }
 }
 
 // ── Events ────────────────────────────────────────────────
 
+/// What one claimer received from a confirmed or resolved prayer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimerPayout {
+    pub claimer: Pubkey,
+    pub amount: u64,
+}
+
+/// A claimer's share of a weighted split
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimWeight {
+    pub claimer: Pubkey,
+    pub weight_bps: u16,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey, // Default pubkey when renounced
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub config: ProtocolConfig,
+}
+
+#[event]
+pub struct AgentKeyRotated {
+    pub wallet: Pubkey,
+    pub encryption_key: [u8; 32],
+    pub key_epoch: u32,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub paused: u16,
+}
+
 #[event]
 pub struct PrayerPosted {
     pub id: u64,
@@ -114,8 +438,40 @@ pub struct PrayerPosted {
     pub prayer_type: PrayerType,
     pub content_hash: [u8; 32],
     pub reward_lamports: u64,
+    pub reward_mint: Pubkey,
     pub max_claimers: u8,
     pub ttl_seconds: i64,
+    pub review_seconds: i64,
+    pub remainder_policy: RemainderPolicy,
+    pub options: u8,
+    pub invited: Vec<Pubkey>,
+    pub requirements: ClaimRequirements,
+    pub claim_stake: u64,
+    pub claim_timeout_seconds: i64,
+}
+
+#[event]
+pub struct PrayerExtended {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct BountyToppedUp {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub amount: u64,                 // What actually landed (after any Token-2022 fee)
+    pub reward_lamports: u64,        // New bounty
+}
+
+#[event]
+pub struct PrayerFunded {
+    pub id: u64,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub contribution: u64,           // Funder's total so far
+    pub reward_lamports: u64,        // New bounty
 }
 
 #[event]
@@ -124,6 +480,30 @@ pub struct PrayerClaimed {
     pub claimer: Pubkey,
     pub num_claimers: u8,
     pub max_claimers: u8,
+    pub pending: bool,               // Awaiting approve_claim
+    pub stake: u64,
+}
+
+#[event]
+pub struct ClaimApproved {
+    pub prayer_id: u64,
+    pub claimer: Pubkey,
+    pub num_claimers: u8,
+    pub max_claimers: u8,
+}
+
+#[event]
+pub struct ClaimRejected {
+    pub prayer_id: u64,
+    pub claimer: Pubkey,
+    pub num_pending: u8,
+}
+
+#[event]
+pub struct ClaimHeartbeat {
+    pub prayer_id: u64,
+    pub claimer: Pubkey,
+    pub deadline: i64,               // New earliest time anyone can unclaim
 }
 
 #[event]
@@ -142,14 +522,84 @@ pub struct PrayerAnswered {
     pub encrypted_answer: Vec<u8>,   // XSalsa20-Poly1305
 }
 
+#[event]
+pub struct AnswerSubmitted {
+    pub id: u64,
+    pub answerer: Pubkey,
+    pub answer_hash: [u8; 32],
+    pub encrypted_answer: Vec<u8>,   // XSalsa20-Poly1305
+    pub num_answers: u8,
+}
+
+#[event]
+pub struct WinnersSelected {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub winners: Vec<ClaimWeight>,   // In rank order
+}
+
+#[event]
+pub struct CompetitionSettled {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub caller: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_per_answer: u64,
+    pub reward_total: u64,
+    pub payouts: Vec<ClaimerPayout>, // One per submitted answer
+}
+
+#[event]
+pub struct ClaimWeightsSet {
+    pub id: u64,
+    pub setter: Pubkey,
+    pub weights: Vec<ClaimWeight>,
+}
+
 #[event]
 pub struct PrayerConfirmed {
     pub id: u64,
     pub requester: Pubkey,
     pub answerer: Pubkey,
     pub num_claimers: u8,
+    pub reward_mint: Pubkey,
+    pub reward_per_claimer: u64,
+    pub reward_total: u64,
+    pub remainder_policy: RemainderPolicy,
+    pub payouts: Vec<ClaimerPayout>,
+    pub requester_remainder: u64, // Left in escrow for close_prayer to refund
+}
+
+#[event]
+pub struct PrayerFinalized {
+    pub id: u64,
+    pub caller: Pubkey,
+    pub fulfilled_at: i64,
+    pub review_seconds: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub answerer: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub ruling_deadline: i64,        // After this, anyone can refund the requester with expire_dispute
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub answerer: Pubkey,
+    pub arbiter: Pubkey,
+    pub claimer_bps: u16,
+    pub num_claimers: u8,
+    pub reward_mint: Pubkey,
     pub reward_per_claimer: u64,
     pub reward_total: u64,
+    pub payouts: Vec<ClaimerPayout>,
+    pub refunded: u64,
 }
 
 #[event]
@@ -158,11 +608,53 @@ pub struct PrayerCancelled {
     pub requester: Pubkey,
 }
 
+#[event]
+pub struct DisputeExpired {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub answerer: Pubkey,
+    pub caller: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct PrayerExpired {
+    pub id: u64,
+    pub requester: Pubkey,
+    pub keeper: Pubkey,
+    pub refund: u64,                 // Bounty returned to the requester (0 if already refunded)
+    pub keeper_fee: u64,             // 0 while claims or answers keep the prayer open
+}
+
 #[event]
 pub struct ClaimRemoved {
     pub prayer_id: u64,
     pub claimer: Pubkey,
     pub num_claimers: u8,
+    pub slashed: u64,                // Stake kept by the prayer (0 = returned to the claimer)
+}
+
+#[event]
+pub struct AnswerRemoved {
+    pub prayer_id: u64,
+    pub answerer: Pubkey,
+    pub num_answers: u8,
+}
+
+#[event]
+pub struct ConfirmVoted {
+    pub prayer_id: u64,
+    pub funder: Pubkey,              // The requester, for its own vote
+    pub confirm_votes: u64,
+    pub reward_lamports: u64,        // Votes must exceed half of this to confirm
+}
+
+#[event]
+pub struct ContributionWithdrawn {
+    pub prayer_id: u64,
+    pub funder: Pubkey,
+    pub refund: u64,
+    pub num_funders: u16,
 }
 
 // ── Instructions ──────────────────────────────────────────
@@ -175,13 +667,113 @@ pub mod chorus_prayers {
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let chain = &mut ctx.accounts.prayer_chain;
         chain.authority = ctx.accounts.authority.key();
+        chain.pending_authority = Pubkey::default();
         chain.total_prayers = 0;
         chain.total_answered = 0;
         chain.total_agents = 0;
+        chain.arbiters = Vec::new();
+        chain.config = ProtocolConfig::default();
+        chain.paused = 0;
         chain.bump = ctx.bumps.prayer_chain;
         Ok(())
     }
 
+    /// Propose a new authority (e.g. a multisig or governance PDA). Takes effect
+    /// once the proposed key calls accept_authority. Proposing the default
+    /// pubkey withdraws a pending proposal.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let chain = &mut ctx.accounts.prayer_chain;
+        chain.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: chain.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending authority proposal. Must be signed by the proposed key.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let chain = &mut ctx.accounts.prayer_chain;
+        let previous_authority = chain.authority;
+        chain.authority = chain.pending_authority;
+        chain.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            previous_authority,
+            new_authority: chain.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Give up authority permanently. Config, arbiters and authority can
+    /// never be changed again, so nothing may be paused: set_pause needs the
+    /// authority too.
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        let chain = &mut ctx.accounts.prayer_chain;
+        require!(chain.paused == 0, PrayerError::PausedRenounce);
+        let previous_authority = chain.authority;
+        chain.authority = Pubkey::default();
+        chain.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            previous_authority,
+            new_authority: Pubkey::default(),
+        });
+
+        Ok(())
+    }
+
+    /// Update protocol limits and reputation amounts. Authority only.
+    /// Applies to prayers posted and claims made from now on. Durations are
+    /// capped at a year, max_claimers at 20 and reputation awards at 1,000.
+    pub fn update_config(ctx: Context<UpdateConfig>, config: ProtocolConfig) -> Result<()> {
+        let seconds = 1..=MAX_CONFIG_SECONDS;
+        require!(
+            seconds.contains(&config.claim_timeout_seconds)
+                && (1..=MAX_CLAIMERS_CEILING).contains(&config.max_claimers)
+                && seconds.contains(&config.max_ttl_seconds)
+                && seconds.contains(&config.max_review_seconds)
+                && seconds.contains(&config.dispute_timeout_seconds)
+                && config.answer_reputation <= MAX_REPUTATION_AWARD
+                && config.confirm_reputation <= MAX_REPUTATION_AWARD,
+            PrayerError::InvalidConfig
+        );
+
+        ctx.accounts.prayer_chain.config = config;
+
+        emit!(ConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            config,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the arbiter set that rules on disputed prayers. Authority only.
+    pub fn set_arbiters(ctx: Context<SetArbiters>, arbiters: Vec<Pubkey>) -> Result<()> {
+        require!(arbiters.len() <= MAX_ARBITERS, PrayerError::TooManyArbiters);
+        ctx.accounts.prayer_chain.arbiters = arbiters;
+        Ok(())
+    }
+
+    /// Set which instruction families are paused (PAUSE_* bits). Authority only.
+    /// Pass 0 to unpause everything.
+    pub fn set_pause(ctx: Context<SetPause>, paused: u16) -> Result<()> {
+        require!(paused & !PAUSE_ALL == 0, PrayerError::InvalidPauseFlags);
+
+        ctx.accounts.prayer_chain.paused = paused;
+
+        emit!(PauseUpdated {
+            authority: ctx.accounts.authority.key(),
+            paused,
+        });
+
+        Ok(())
+    }
+
     /// Register a new agent on the prayer chain
     pub fn register_agent(
         ctx: Context<RegisterAgent>,
@@ -198,11 +790,14 @@ pub mod chorus_prayers {
         agent.name = name;
         agent.skills = skills;
         agent.encryption_key = encryption_key;
+        agent.key_epoch = 0;
         agent.prayers_posted = 0;
         agent.prayers_answered = 0;
         agent.prayers_confirmed = 0;
         agent.reputation = 0;
         agent.registered_at = Clock::get()?.unix_timestamp;
+        agent.open_claims = 0;
+        agent.open_prayers = 0;
         agent.bump = ctx.bumps.agent;
 
         let chain = &mut ctx.accounts.prayer_chain;
@@ -211,28 +806,124 @@ pub mod chorus_prayers {
         Ok(())
     }
 
-    /// Post a prayer. max_claimers controls collaboration (1 = solo, >1 = multi-agent).
-    pub fn post_prayer(
-        ctx: Context<PostPrayer>,
-        prayer_type: PrayerType,
-        content_hash: [u8; 32],
-        reward_lamports: u64,
-        ttl_seconds: i64,
-        max_claimers: u8,
-    ) -> Result<()> {
-        require!(ttl_seconds > 0 && ttl_seconds <= 604_800, PrayerError::InvalidTTL);
-        require!(max_claimers >= 1 && max_claimers <= MAX_CLAIMERS_LIMIT, PrayerError::InvalidMaxClaimers);
+    /// Deregister an agent and return its rent to the wallet. The agent must
+    /// have no live claims and must have closed every prayer it posted.
+    pub fn close_agent(ctx: Context<CloseAgent>) -> Result<()> {
+        let agent = &ctx.accounts.agent;
+        require!(
+            agent.open_claims == 0 && agent.open_prayers == 0,
+            PrayerError::AgentHasOpenWork
+        );
 
-        let now = Clock::get()?.unix_timestamp;
         let chain = &mut ctx.accounts.prayer_chain;
-        let prayer_id = chain.total_prayers;
+        chain.total_agents = chain.total_agents.checked_sub(1).unwrap();
 
-        let prayer = &mut ctx.accounts.prayer;
-        prayer.id = prayer_id;
-        prayer.requester = ctx.accounts.requester.key();
+        // Agent PDA is closed by the `close = wallet` constraint
+        Ok(())
+    }
+
+    /// Update an agent's profile. Omitted fields are left unchanged. The account
+    /// is reallocated to fit the new name and skills. A new encryption_key
+    /// bumps key_epoch so peers know to re-fetch it before encrypting.
+    pub fn update_agent(
+        ctx: Context<UpdateAgent>,
+        name: Option<String>,
+        skills: Option<String>,
+        encryption_key: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+
+        if let Some(name) = name {
+            require!(name.len() <= Agent::MAX_NAME, PrayerError::NameTooLong);
+            agent.name = name;
+        }
+        if let Some(skills) = skills {
+            require!(skills.len() <= Agent::MAX_SKILLS, PrayerError::SkillsTooLong);
+            agent.skills = skills;
+        }
+
+        if let Some(encryption_key) = encryption_key {
+            require!(encryption_key != [0u8; 32], PrayerError::InvalidEncryptionKey);
+            if encryption_key != agent.encryption_key {
+                agent.encryption_key = encryption_key;
+                agent.key_epoch = agent.key_epoch.checked_add(1).unwrap();
+
+                emit!(AgentKeyRotated {
+                    wallet: agent.wallet,
+                    encryption_key,
+                    key_epoch: agent.key_epoch,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Post a prayer. max_claimers controls collaboration (1 = solo, >1 = multi-agent).
+    /// review_seconds is how long the requester has to confirm or dispute an answer
+    /// before anyone can finalize it. remainder_policy decides who gets what's left
+    /// when the bounty doesn't split evenly; options holds OPTION_* bits. A non-empty
+    /// invited list (max 5) restricts claim_prayer to those wallets,
+    /// requirements sets the minimum standing a claimer's agent needs,
+    /// claim_stake is the SOL deposit each claimer puts up (see unclaim_prayer),
+    /// and claim_timeout_seconds (at most the TTL; 0 = the protocol default) is
+    /// how long a claim lasts without a heartbeat_claim before anyone can remove it.
+    /// Pass reward_mint (plus escrow, requester_token_account, token_program) for an
+    /// SPL / Token-2022 bounty; omit them to escrow native SOL.
+    pub fn post_prayer(
+        ctx: Context<PostPrayer>,
+        prayer_type: PrayerType,
+        content_hash: [u8; 32],
+        reward_lamports: u64,
+        ttl_seconds: i64,
+        max_claimers: u8,
+        review_seconds: i64,
+        remainder_policy: RemainderPolicy,
+        options: u8,
+        invited: Vec<Pubkey>,
+        requirements: ClaimRequirements,
+        claim_stake: u64,
+        claim_timeout_seconds: i64,
+    ) -> Result<()> {
+        let config = ctx.accounts.prayer_chain.config;
+        require!((1..=config.max_ttl_seconds).contains(&ttl_seconds), PrayerError::InvalidTTL);
+        require!((1..=config.max_claimers).contains(&max_claimers), PrayerError::InvalidMaxClaimers);
+        require!(
+            (1..=config.max_review_seconds).contains(&review_seconds),
+            PrayerError::InvalidReviewWindow
+        );
+        require!(options & !OPTION_ALL == 0, PrayerError::InvalidOptions);
+        require!(invited.len() <= MAX_INVITED, PrayerError::TooManyInvited);
+        require!(
+            requirements.min_agent_age_seconds >= 0,
+            PrayerError::InvalidRequirements
+        );
+        require!(
+            (0..=ttl_seconds).contains(&claim_timeout_seconds),
+            PrayerError::InvalidClaimTimeout
+        );
+        let claim_timeout_seconds = if claim_timeout_seconds == 0 {
+            config.claim_timeout_seconds.min(ttl_seconds)
+        } else {
+            claim_timeout_seconds
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        let chain = &mut ctx.accounts.prayer_chain;
+        let prayer_id = chain.total_prayers;
+
+        let prayer = &mut ctx.accounts.prayer;
+        prayer.id = prayer_id;
+        prayer.requester = ctx.accounts.requester.key();
         prayer.prayer_type = prayer_type;
         prayer.content_hash = content_hash;
         prayer.reward_lamports = reward_lamports;
+        prayer.reward_mint = ctx
+            .accounts
+            .reward_mint
+            .as_ref()
+            .map(|mint| mint.key())
+            .unwrap_or_default();
         prayer.status = PrayerStatus::Open;
         prayer.max_claimers = max_claimers;
         prayer.num_claimers = 0;
@@ -241,10 +932,60 @@ pub mod chorus_prayers {
         prayer.created_at = now;
         prayer.expires_at = now.checked_add(ttl_seconds).unwrap();
         prayer.fulfilled_at = 0;
+        prayer.review_seconds = review_seconds;
+        prayer.ruling_deadline = 0;
+        prayer.remainder_policy = remainder_policy;
+        prayer.weighted = false;
+        prayer.requester_weighted = false;
+        prayer.options = options;
+        prayer.num_answers = 0;
+        prayer.num_pending = 0;
+        prayer.invited = invited.clone();
+        prayer.requirements = requirements;
+        prayer.claim_stake = claim_stake;
+        prayer.claim_timeout_seconds = claim_timeout_seconds;
+        prayer.funded_lamports = 0;
+        prayer.num_funders = 0;
+        prayer.funder_refund = 0;
+        prayer.confirm_votes = 0;
+        prayer.requester_voted = false;
         prayer.bump = ctx.bumps.prayer;
 
-        // Escrow bounty
-        if reward_lamports > 0 {
+        // Escrow bounty. A token prayer always gets its escrow, even when it
+        // starts empty, so top-ups and refunds have somewhere to go.
+        if prayer.is_token_bounty() {
+            let accounts = &ctx.accounts;
+            let (Some(mint), Some(escrow), Some(from), Some(token_program)) = (
+                accounts.reward_mint.as_ref(),
+                accounts.escrow.as_ref(),
+                accounts.requester_token_account.as_ref(),
+                accounts.token_program.as_ref(),
+            ) else {
+                return err!(PrayerError::MissingTokenAccounts);
+            };
+
+            if reward_lamports > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: from.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: escrow.to_account_info(),
+                            authority: accounts.requester.to_account_info(),
+                        },
+                    ),
+                    reward_lamports,
+                    mint.decimals,
+                )?;
+
+                // Token-2022 transfer fees can withhold part of the deposit;
+                // the bounty is whatever actually landed in escrow.
+                let escrow = ctx.accounts.escrow.as_mut().unwrap();
+                escrow.reload()?;
+                ctx.accounts.prayer.reward_lamports = escrow.amount;
+            }
+        } else if reward_lamports > 0 {
             anchor_lang::system_program::transfer(
                 CpiContext::new(
                     ctx.accounts.system_program.to_account_info(),
@@ -257,24 +998,190 @@ pub mod chorus_prayers {
             )?;
         }
 
+        let chain = &mut ctx.accounts.prayer_chain;
         chain.total_prayers = chain.total_prayers.checked_add(1).unwrap();
         let agent = &mut ctx.accounts.requester_agent;
         agent.prayers_posted = agent.prayers_posted.checked_add(1).unwrap();
+        agent.open_prayers = agent.open_prayers.checked_add(1).unwrap();
 
         emit!(PrayerPosted {
             id: prayer_id,
             requester: ctx.accounts.requester.key(),
             prayer_type,
             content_hash,
-            reward_lamports,
+            reward_lamports: ctx.accounts.prayer.reward_lamports,
+            reward_mint: ctx.accounts.prayer.reward_mint,
             max_claimers,
             ttl_seconds,
+            review_seconds,
+            remainder_policy,
+            options,
+            invited,
+            requirements,
+            claim_stake,
+            claim_timeout_seconds,
+        });
+
+        Ok(())
+    }
+
+    /// Push an open prayer's expires_at back by extra_seconds, keeping it within
+    /// max_ttl_seconds of now. Claim deadlines stay as they are.
+    pub fn extend_prayer(ctx: Context<ExtendPrayer>, extra_seconds: i64) -> Result<()> {
+        let config = ctx.accounts.prayer_chain.config;
+        let prayer = &mut ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
+            PrayerError::NotOpen
+        );
+        require!(now < prayer.expires_at, PrayerError::Expired);
+        let expires_at = prayer.expires_at.saturating_add(extra_seconds);
+        require!(
+            extra_seconds > 0 && expires_at - now <= config.max_ttl_seconds,
+            PrayerError::InvalidTTL
+        );
+
+        prayer.expires_at = expires_at;
+
+        emit!(PrayerExtended {
+            id: prayer.id,
+            requester: prayer.requester,
+            expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Add amount to an open prayer's bounty, in lamports or the reward mint's
+    /// base units (pass the same token accounts as post_prayer).
+    pub fn top_up_bounty(ctx: Context<TopUpBounty>, amount: u64) -> Result<()> {
+        let prayer = &ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
+            PrayerError::NotOpen
+        );
+        require!(now < prayer.expires_at, PrayerError::Expired);
+        require!(amount > 0, PrayerError::InvalidAmount);
+
+        let added = if prayer.is_token_bounty() {
+            let accounts = &ctx.accounts;
+            let (mint, escrow, token_program) = token_escrow_accounts(
+                prayer,
+                &accounts.reward_mint,
+                &accounts.escrow,
+                &accounts.token_program,
+            )?;
+            let Some(from) = accounts.requester_token_account.as_ref() else {
+                return err!(PrayerError::MissingTokenAccounts);
+            };
+            let before = escrow.amount;
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: escrow.to_account_info(),
+                        authority: accounts.requester.to_account_info(),
+                    },
+                ),
+                amount,
+                mint.decimals,
+            )?;
+
+            // As in post_prayer, only what lands in escrow counts
+            let escrow = ctx.accounts.escrow.as_mut().unwrap();
+            escrow.reload()?;
+            escrow.amount.checked_sub(before).unwrap()
+        } else {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.requester.to_account_info(),
+                        to: ctx.accounts.prayer.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            amount
+        };
+
+        let prayer = &mut ctx.accounts.prayer;
+        prayer.reward_lamports = prayer.reward_lamports.checked_add(added).unwrap();
+
+        emit!(BountyToppedUp {
+            id: prayer.id,
+            requester: prayer.requester,
+            amount: added,
+            reward_lamports: prayer.reward_lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Add amount lamports to someone else's open SOL prayer. The funder's
+    /// running total is kept in a Contribution PDA: bounty refunded on cancel,
+    /// expiry or dispute goes back pro-rata through withdraw_contribution, and
+    /// it weighs the funder's vote_confirm. A wallet can't both fund and claim
+    /// the same prayer.
+    pub fn fund_prayer(ctx: Context<FundPrayer>, amount: u64) -> Result<()> {
+        let prayer = &ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
+            PrayerError::NotOpen
+        );
+        require!(now < prayer.expires_at, PrayerError::Expired);
+        require!(!prayer.is_token_bounty(), PrayerError::WrongMode);
+        require!(amount > 0, PrayerError::InvalidAmount);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.prayer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let prayer = &mut ctx.accounts.prayer;
+        let contribution = &mut ctx.accounts.contribution;
+        if contribution.amount == 0 {
+            contribution.prayer_id = prayer.id;
+            contribution.funder = ctx.accounts.funder.key();
+            contribution.voted = false;
+            contribution.bump = ctx.bumps.contribution;
+            prayer.num_funders = prayer.num_funders.checked_add(1).unwrap();
+        }
+        contribution.amount = contribution.amount.checked_add(amount).unwrap();
+        prayer.funded_lamports = prayer.funded_lamports.checked_add(amount).unwrap();
+        prayer.reward_lamports = prayer.reward_lamports.checked_add(amount).unwrap();
+
+        emit!(PrayerFunded {
+            id: prayer.id,
+            funder: contribution.funder,
+            amount,
+            contribution: contribution.amount,
+            reward_lamports: prayer.reward_lamports,
         });
 
         Ok(())
     }
 
-    /// Claim a prayer. Creates a Claim PDA. Multiple agents can claim until max_claimers.
+    /// Claim a prayer. Creates a Claim PDA. Multiple agents can claim until max_claimers;
+    /// a prayer posted with an invite list only accepts the wallets on it, and the
+    /// claimer's agent must meet the prayer's requirements.
+    /// On an approval-mode prayer the claim starts pending and takes no slot
+    /// until the requester approves it; at most MAX_PENDING_CLAIMS can wait.
     pub fn claim_prayer(ctx: Context<ClaimPrayer>) -> Result<()> {
         let prayer = &mut ctx.accounts.prayer;
         let now = Clock::get()?.unix_timestamp;
@@ -288,6 +1195,11 @@ pub mod chorus_prayers {
             prayer.requester != ctx.accounts.claimer.key(),
             PrayerError::CannotClaimOwn
         );
+        require!(
+            prayer.is_invited(&ctx.accounts.claimer.key()),
+            PrayerError::NotInvited
+        );
+        check_requirements(&prayer.requirements, &ctx.accounts.claimer_agent, now)?;
 
         // Initialize the Claim PDA
         let claim = &mut ctx.accounts.claim;
@@ -295,14 +1207,37 @@ pub mod chorus_prayers {
         claim.claimer = ctx.accounts.claimer.key();
         claim.content_delivered = false;
         claim.claimed_at = now;
+        claim.last_heartbeat = now;
+        claim.answered = false;
+        claim.weight_bps = 0;
+        claim.approved = !prayer.has_option(OPTION_APPROVAL);
+        claim.stake = prayer.claim_stake;
         claim.bump = ctx.bumps.claim;
 
-        // Increment claimer count
-        prayer.num_claimers = prayer.num_claimers.checked_add(1).unwrap();
+        if claim.stake > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.claimer.to_account_info(),
+                        to: claim.to_account_info(),
+                    },
+                ),
+                claim.stake,
+            )?;
+        }
+
+        let claimer_agent = &mut ctx.accounts.claimer_agent;
+        claimer_agent.open_claims = claimer_agent.open_claims.checked_add(1).unwrap();
 
-        // If all slots filled, move to Active
-        if prayer.num_claimers >= prayer.max_claimers {
-            prayer.status = PrayerStatus::Active;
+        if claim.approved {
+            take_slot(prayer);
+        } else {
+            require!(
+                prayer.num_pending < MAX_PENDING_CLAIMS,
+                PrayerError::TooManyPending
+            );
+            prayer.num_pending = prayer.num_pending.checked_add(1).unwrap();
         }
 
         emit!(PrayerClaimed {
@@ -310,12 +1245,97 @@ pub mod chorus_prayers {
             claimer: ctx.accounts.claimer.key(),
             num_claimers: prayer.num_claimers,
             max_claimers: prayer.max_claimers,
+            pending: !claim.approved,
+            stake: claim.stake,
+        });
+
+        Ok(())
+    }
+
+    /// Approve a pending claim on an approval-mode prayer. The claim takes a
+    /// slot, and its timeout restarts from approval.
+    pub fn approve_claim(ctx: Context<ApproveClaim>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!claim.approved, PrayerError::ClaimNotPending);
+        require!(
+            prayer.status == PrayerStatus::Open,
+            PrayerError::NotOpen
+        );
+        require!(now < prayer.expires_at, PrayerError::Expired);
+
+        claim.approved = true;
+        claim.claimed_at = now;
+        claim.last_heartbeat = now;
+        prayer.num_pending = prayer.num_pending.checked_sub(1).unwrap();
+        take_slot(prayer);
+
+        emit!(ClaimApproved {
+            prayer_id: prayer.id,
+            claimer: claim.claimer,
+            num_claimers: prayer.num_claimers,
+            max_claimers: prayer.max_claimers,
+        });
+
+        Ok(())
+    }
+
+    /// Reject a pending claim. Closes the Claim PDA and returns its rent to
+    /// the claimer.
+    pub fn reject_claim(ctx: Context<RejectClaim>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let claim = &ctx.accounts.claim;
+
+        require!(!claim.approved, PrayerError::ClaimNotPending);
+
+        prayer.num_pending = prayer.num_pending.checked_sub(1).unwrap();
+
+        let claimer_agent = &mut ctx.accounts.claimer_agent;
+        claimer_agent.open_claims = claimer_agent.open_claims.checked_sub(1).unwrap();
+
+        emit!(ClaimRejected {
+            prayer_id: prayer.id,
+            claimer: claim.claimer,
+            num_pending: prayer.num_pending,
+        });
+
+        // Claim PDA is closed by the `close = claimer_wallet` constraint
+        Ok(())
+    }
+
+    /// Keep an approved claim alive. Restarts the claim timeout from now, but
+    /// never pushes the deadline past the prayer's expires_at. The claimer must
+    /// heartbeat before the current deadline; a lapsed claim can only be removed.
+    pub fn heartbeat_claim(ctx: Context<HeartbeatClaim>) -> Result<()> {
+        let prayer = &ctx.accounts.prayer;
+        let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
+            PrayerError::NotClaimed
+        );
+        require!(now < prayer.expires_at, PrayerError::Expired);
+        require!(now <= claim.deadline(prayer), PrayerError::ClaimTimedOut);
+
+        let latest = prayer
+            .expires_at
+            .checked_sub(prayer.claim_timeout_seconds)
+            .unwrap();
+        claim.last_heartbeat = now.min(latest).max(claim.last_heartbeat);
+
+        emit!(ClaimHeartbeat {
+            prayer_id: prayer.id,
+            claimer: claim.claimer,
+            deadline: claim.deadline(prayer),
         });
 
         Ok(())
     }
 
-    /// Deliver encrypted content to a specific claimer.
+    /// Deliver encrypted content to a specific claimer before the prayer expires.
     /// Must be called once per claimer (each gets unique DH-encrypted content).
     pub fn deliver_content(
         ctx: Context<DeliverContent>,
@@ -323,11 +1343,13 @@ pub mod chorus_prayers {
     ) -> Result<()> {
         let prayer = &ctx.accounts.prayer;
         let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
 
         require!(
             prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
             PrayerError::NotClaimed
         );
+        require!(now < prayer.expires_at, PrayerError::Expired);
         require!(
             prayer.requester == ctx.accounts.requester.key(),
             PrayerError::NotRequester
@@ -346,7 +1368,8 @@ pub mod chorus_prayers {
         Ok(())
     }
 
-    /// Answer a prayer. The answerer must be a claimer (have a Claim PDA).
+    /// Answer a prayer before it expires. The answerer must be a claimer (have a
+    /// Claim PDA) and, under OPTION_REQUIRE_DELIVERY, have had content delivered.
     /// Encrypted answer is for the requester.
     pub fn answer_prayer(
         ctx: Context<AnswerPrayer>,
@@ -361,18 +1384,29 @@ pub mod chorus_prayers {
             PrayerError::NotClaimed
         );
         require!(now < prayer.expires_at, PrayerError::Expired);
+        require!(!prayer.has_option(OPTION_COMPETITION), PrayerError::WrongMode);
         // Claim PDA validation ensures answerer is a claimer (PDA derivation enforces it)
+        require!(
+            ctx.accounts.claim.content_delivered || !prayer.has_option(OPTION_REQUIRE_DELIVERY),
+            PrayerError::ContentNotDelivered
+        );
+
+        return_stake(&mut ctx.accounts.claim, &ctx.accounts.answerer)?;
 
+        let prayer = &mut ctx.accounts.prayer;
         prayer.status = PrayerStatus::Fulfilled;
         prayer.answerer = ctx.accounts.answerer.key();
         prayer.answer_hash = answer_hash;
         prayer.fulfilled_at = now;
 
+        let chain = &mut ctx.accounts.prayer_chain;
         let agent = &mut ctx.accounts.answerer_agent;
         agent.prayers_answered = agent.prayers_answered.checked_add(1).unwrap();
-        agent.reputation = agent.reputation.checked_add(10).unwrap();
+        agent.reputation = agent
+            .reputation
+            .checked_add(chain.config.answer_reputation)
+            .unwrap();
 
-        let chain = &mut ctx.accounts.prayer_chain;
         chain.total_answered = chain.total_answered.checked_add(1).unwrap();
 
         emit!(PrayerAnswered {
@@ -385,313 +1419,2297 @@ pub mod chorus_prayers {
         Ok(())
     }
 
-    /// Confirm a prayer. Bounty splits equally among ALL claimers.
-    /// Remaining accounts: pairs of [claimer_wallet, claimer_agent_pda] for each claimer.
-    pub fn confirm_prayer(ctx: Context<ConfirmPrayer>) -> Result<()> {
+    /// Submit a competing answer on a competition prayer. Creates an Answer PDA;
+    /// the prayer stays open for the other claimers until the requester ranks
+    /// the answers with select_winners.
+    pub fn submit_answer(
+        ctx: Context<SubmitAnswer>,
+        answer_hash: [u8; 32],
+        encrypted_answer: Vec<u8>,
+    ) -> Result<()> {
         let prayer = &mut ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
 
         require!(
-            prayer.status == PrayerStatus::Fulfilled,
-            PrayerError::NotFulfilled
+            prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
+            PrayerError::NotClaimed
         );
+        require!(now < prayer.expires_at, PrayerError::Expired);
+        require!(prayer.has_option(OPTION_COMPETITION), PrayerError::WrongMode);
         require!(
-            prayer.requester == ctx.accounts.requester.key(),
-            PrayerError::NotRequester
+            ctx.accounts.claim.content_delivered || !prayer.has_option(OPTION_REQUIRE_DELIVERY),
+            PrayerError::ContentNotDelivered
         );
 
-        prayer.status = PrayerStatus::Confirmed;
-
-        let num_claimers = prayer.num_claimers as u64;
-        let reward_per_claimer = if prayer.reward_lamports > 0 && num_claimers > 0 {
-            prayer.reward_lamports / num_claimers
-        } else {
-            0
-        };
-
-        // Distribute bounty equally via remaining accounts
-        // Each remaining account should be a claimer wallet (writable)
-        let prayer_info = prayer.to_account_info();
-        let remaining = &ctx.remaining_accounts;
-        let mut distributed: u64 = 0;
+        return_stake(&mut ctx.accounts.claim, &ctx.accounts.answerer)?;
+        ctx.accounts.claim.answered = true;
 
-        for account_info in remaining.iter() {
-            if distributed + reward_per_claimer > prayer.reward_lamports {
-                break;
-            }
-            if reward_per_claimer > 0 {
-                **prayer_info.try_borrow_mut_lamports()? = prayer_info
-                    .lamports()
-                    .checked_sub(reward_per_claimer)
-                    .unwrap();
-                **account_info.try_borrow_mut_lamports()? = account_info
-                    .lamports()
-                    .checked_add(reward_per_claimer)
-                    .unwrap();
-                distributed += reward_per_claimer;
-            }
-        }
+        let prayer = &mut ctx.accounts.prayer;
+        let answer = &mut ctx.accounts.answer;
+        answer.prayer_id = prayer.id;
+        answer.answerer = ctx.accounts.answerer.key();
+        answer.answer_hash = answer_hash;
+        answer.submitted_at = now;
+        answer.rank = 0;
+        answer.bump = ctx.bumps.answer;
 
-        // Give answerer's agent +5 bonus rep
-        let answerer_agent = &mut ctx.accounts.answerer_agent;
-        answerer_agent.prayers_confirmed = answerer_agent
-            .prayers_confirmed
-            .checked_add(1)
-            .unwrap();
-        answerer_agent.reputation = answerer_agent.reputation.checked_add(5).unwrap();
+        prayer.num_answers = prayer.num_answers.checked_add(1).unwrap();
 
-        emit!(PrayerConfirmed {
+        emit!(AnswerSubmitted {
             id: prayer.id,
-            requester: ctx.accounts.requester.key(),
-            answerer: prayer.answerer,
-            num_claimers: prayer.num_claimers,
-            reward_per_claimer,
-            reward_total: distributed,
+            answerer: answer.answerer,
+            answer_hash,
+            encrypted_answer,
+            num_answers: prayer.num_answers,
         });
 
         Ok(())
     }
 
-    /// Cancel a prayer. Only when NO claims exist (num_claimers == 0).
-    pub fn cancel_prayer(ctx: Context<CancelPrayer>) -> Result<()> {
+    /// Rank the answers on a competition prayer and fulfill it. weights_bps
+    /// gives each ranked answerer's share of the bounty, best first: at least
+    /// one, never increasing, summing to 10000. Unranked claimers get nothing,
+    /// including round-robin remainder units.
+    /// The winner becomes the prayer's answerer; confirm, finalize and disputes
+    /// then proceed as for any fulfilled prayer. Open until the prayer's
+    /// selection_deadline, after which settle_competition pays every answer.
+    /// Remaining accounts: [answer_pda (mut), claim_pda (mut)] per ranked answerer, in rank order.
+    pub fn select_winners<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SelectWinners<'info>>,
+        weights_bps: Vec<u16>,
+    ) -> Result<()> {
         let prayer = &mut ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
 
+        require!(prayer.has_option(OPTION_COMPETITION), PrayerError::WrongMode);
         require!(
-            prayer.status == PrayerStatus::Open,
-            PrayerError::CannotCancel
+            prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
+            PrayerError::NotClaimed
         );
         require!(
-            prayer.num_claimers == 0,
-            PrayerError::HasClaimers
+            now <= prayer.selection_deadline(),
+            PrayerError::SelectionWindowElapsed
         );
         require!(
-            prayer.requester == ctx.accounts.requester.key(),
-            PrayerError::NotRequester
+            !weights_bps.is_empty()
+                && weights_bps.len() <= prayer.num_answers as usize
+                && ctx.remaining_accounts.len() == weights_bps.len() * 2
+                && weights_bps.windows(2).all(|pair| pair[0] >= pair[1])
+                && weights_bps.iter().map(|w| *w as u64).sum::<u64>() == BPS_DENOMINATOR,
+            PrayerError::InvalidRanking
         );
 
-        prayer.status = PrayerStatus::Cancelled;
-
-        if prayer.reward_lamports > 0 {
-            let prayer_info = prayer.to_account_info();
-            let requester_info = ctx.accounts.requester.to_account_info();
-
-            **prayer_info.try_borrow_mut_lamports()? = prayer_info
-                .lamports()
-                .checked_sub(prayer.reward_lamports)
-                .unwrap();
-            **requester_info.try_borrow_mut_lamports()? = requester_info
-                .lamports()
-                .checked_add(prayer.reward_lamports)
-                .unwrap();
+        let mut winners: Vec<ClaimWeight> = Vec::with_capacity(weights_bps.len());
+        for (rank, (pair, weight_bps)) in ctx
+            .remaining_accounts
+            .chunks(2)
+            .zip(weights_bps)
+            .enumerate()
+        {
+            let (answer_info, claim_info) = (&pair[0], &pair[1]);
+            require!(
+                answer_info.is_writable && claim_info.is_writable,
+                PrayerError::InvalidClaim
+            );
+            let mut answer = load_answer(prayer, answer_info)?;
+            let mut claim = load_claim(prayer, claim_info)?;
+            require_keys_eq!(claim.claimer, answer.answerer, PrayerError::InvalidClaim);
+            require!(
+                !winners.iter().any(|w| w.claimer == answer.answerer),
+                PrayerError::InvalidRanking
+            );
+
+            if rank == 0 {
+                prayer.answerer = answer.answerer;
+                prayer.answer_hash = answer.answer_hash;
+            }
+            answer.rank = (rank + 1) as u8;
+            answer.try_serialize(&mut &mut answer_info.try_borrow_mut_data()?[..])?;
+            claim.weight_bps = weight_bps;
+            claim.try_serialize(&mut &mut claim_info.try_borrow_mut_data()?[..])?;
+            winners.push(ClaimWeight { claimer: answer.answerer, weight_bps });
         }
 
-        emit!(PrayerCancelled {
+        let agent = &mut ctx.accounts.winner_agent;
+        require_keys_eq!(agent.wallet, prayer.answerer, PrayerError::InvalidRanking);
+
+        prayer.status = PrayerStatus::Fulfilled;
+        prayer.fulfilled_at = now;
+        prayer.weighted = true;
+
+        let chain = &mut ctx.accounts.prayer_chain;
+        agent.prayers_answered = agent.prayers_answered.checked_add(1).unwrap();
+        agent.reputation = agent
+            .reputation
+            .checked_add(chain.config.answer_reputation)
+            .unwrap();
+        chain.total_answered = chain.total_answered.checked_add(1).unwrap();
+
+        emit!(WinnersSelected {
             id: prayer.id,
-            requester: ctx.accounts.requester.key(),
+            requester: prayer.requester,
+            winners,
         });
 
         Ok(())
     }
 
-    /// Remove a claim. Claimer voluntarily, or anyone after timeout.
-    /// Closes the Claim PDA and decrements num_claimers.
-    pub fn unclaim_prayer(ctx: Context<UnclaimPrayer>) -> Result<()> {
+    /// Pay a competition the requester never ranked. Permissionless once the
+    /// selection_deadline has passed: the bounty splits equally among every
+    /// submitted answer, and any rounding remainder stays in escrow for the
+    /// requester. No reputation is credited, as nobody accepted the answers.
+    /// Remaining accounts: [answer_pda, recipient (mut)] per submitted answer;
+    /// the recipient is the answerer's wallet, or its token account for SPL bounties.
+    pub fn settle_competition<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleCompetition<'info>>,
+    ) -> Result<()> {
         let prayer = &mut ctx.accounts.prayer;
-        let claim = &ctx.accounts.claim;
         let now = Clock::get()?.unix_timestamp;
 
+        require!(prayer.has_option(OPTION_COMPETITION), PrayerError::WrongMode);
         require!(
             prayer.status == PrayerStatus::Open || prayer.status == PrayerStatus::Active,
             PrayerError::NotClaimed
         );
-
-        let is_claimer = claim.claimer == ctx.accounts.caller.key();
-        let claim_expired = now > claim.claimed_at.checked_add(CLAIM_TIMEOUT_SECONDS).unwrap();
-
+        require!(prayer.num_answers > 0, PrayerError::NotFulfilled);
         require!(
-            is_claimer || claim_expired,
-            PrayerError::NotClaimer
+            now > prayer.selection_deadline(),
+            PrayerError::SelectionWindowOpen
+        );
+        require!(
+            ctx.remaining_accounts.len() == prayer.num_answers as usize * 2,
+            PrayerError::PayoutMismatch
         );
 
-        prayer.num_claimers = prayer.num_claimers.checked_sub(1).unwrap();
+        prayer.status = PrayerStatus::Confirmed;
+        prayer.fulfilled_at = now;
 
-        // If was Active, reopen since a slot freed up
-        if prayer.status == PrayerStatus::Active {
-            prayer.status = PrayerStatus::Open;
+        let reward_per_answer = prayer.reward_lamports / prayer.num_answers as u64;
+        let mut payouts: Vec<ClaimerPayout> = Vec::with_capacity(prayer.num_answers as usize);
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let (answer_info, recipient) = (&pair[0], &pair[1]);
+            let answer = load_answer(prayer, answer_info)?;
+            require!(
+                !payouts.iter().any(|paid| paid.claimer == answer.answerer),
+                PrayerError::PayoutMismatch
+            );
+            verify_recipient(prayer, &answer.answerer, recipient)?;
+            pay_bounty(
+                prayer,
+                &ctx.accounts.reward_mint,
+                &ctx.accounts.escrow,
+                &ctx.accounts.token_program,
+                recipient.clone(),
+                reward_per_answer,
+            )?;
+            payouts.push(ClaimerPayout {
+                claimer: answer.answerer,
+                amount: reward_per_answer,
+            });
         }
 
-        emit!(ClaimRemoved {
-            prayer_id: prayer.id,
-            claimer: claim.claimer,
+        emit!(CompetitionSettled {
+            id: prayer.id,
+            requester: prayer.requester,
+            caller: ctx.accounts.caller.key(),
+            reward_mint: prayer.reward_mint,
+            reward_per_answer,
+            reward_total: reward_per_answer * payouts.len() as u64,
+            payouts,
+        });
+
+        Ok(())
+    }
+
+    /// Weight the bounty split by contribution. The requester or the answerer
+    /// sets one weight per claimer, in basis points summing to 10000, while the
+    /// prayer awaits confirmation; a later call replaces the earlier weights,
+    /// except that once the requester has set them only the requester can.
+    /// Remaining accounts: every claim_pda (mut), in the same order as weights_bps.
+    pub fn set_claim_weights<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetClaimWeights<'info>>,
+        weights_bps: Vec<u16>,
+    ) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let setter = ctx.accounts.setter.key();
+
+        require!(
+            prayer.status == PrayerStatus::Fulfilled,
+            PrayerError::NotFulfilled
+        );
+        require!(
+            setter == prayer.requester || setter == prayer.answerer,
+            PrayerError::NotRequesterOrAnswerer
+        );
+        require!(
+            setter == prayer.requester || !prayer.requester_weighted,
+            PrayerError::WeightsLocked
+        );
+        // Competition payouts follow the requester's ranking
+        require!(!prayer.has_option(OPTION_COMPETITION), PrayerError::WrongMode);
+        require!(
+            weights_bps.len() == prayer.num_claimers as usize
+                && ctx.remaining_accounts.len() == weights_bps.len()
+                && weights_bps.iter().map(|w| *w as u64).sum::<u64>() == BPS_DENOMINATOR,
+            PrayerError::InvalidWeights
+        );
+
+        let mut weights: Vec<ClaimWeight> = Vec::with_capacity(weights_bps.len());
+        for (claim_info, weight_bps) in ctx.remaining_accounts.iter().zip(weights_bps) {
+            require!(claim_info.is_writable, PrayerError::InvalidClaim);
+            let mut claim = load_claim(prayer, claim_info)?;
+            require!(
+                !weights.iter().any(|w| w.claimer == claim.claimer),
+                PrayerError::InvalidWeights
+            );
+
+            claim.weight_bps = weight_bps;
+            claim.try_serialize(&mut &mut claim_info.try_borrow_mut_data()?[..])?;
+            weights.push(ClaimWeight { claimer: claim.claimer, weight_bps });
+        }
+
+        prayer.weighted = true;
+        prayer.requester_weighted = setter == prayer.requester;
+
+        emit!(ClaimWeightsSet {
+            id: prayer.id,
+            setter,
+            weights,
+        });
+
+        Ok(())
+    }
+
+    /// Confirm a prayer. Bounty splits equally among ALL claimers (or by weight
+    /// after set_claim_weights); any remainder follows the prayer's remainder_policy.
+    /// Remaining accounts: [claim_pda, recipient] pairs, one per active claimer.
+    /// The recipient is the claimer's wallet for SOL bounties, or a token account
+    /// owned by the claimer for the reward mint when the bounty is an SPL token.
+    pub fn confirm_prayer<'info>(ctx: Context<'_, '_, 'info, 'info, ConfirmPrayer<'info>>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+
+        require!(
+            prayer.status == PrayerStatus::Fulfilled,
+            PrayerError::NotFulfilled
+        );
+        require!(
+            prayer.requester == ctx.accounts.requester.key(),
+            PrayerError::NotRequester
+        );
+
+        prayer.status = PrayerStatus::Confirmed;
+
+        // Every active claimer must be paid exactly once, each through its Claim PDA
+        let split = pay_claimers(
+            prayer,
+            ctx.remaining_accounts,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.escrow,
+            &ctx.accounts.token_program,
+            prayer.reward_lamports,
+        )?;
+
+        let remainder = prayer.reward_lamports.checked_sub(split.distributed).unwrap();
+        split_refund(prayer, remainder);
+
+        // Give answerer's agent the confirmation bonus rep
+        credit_confirmation(
+            &mut ctx.accounts.answerer_agent,
+            &ctx.accounts.prayer_chain.config,
+        );
+
+        emit!(PrayerConfirmed {
+            id: prayer.id,
+            requester: ctx.accounts.requester.key(),
+            answerer: prayer.answerer,
+            num_claimers: prayer.num_claimers,
+            reward_mint: prayer.reward_mint,
+            reward_per_claimer: split.reward_per_claimer,
+            reward_total: split.distributed,
+            remainder_policy: prayer.remainder_policy,
+            payouts: split.payouts,
+            requester_remainder: remainder,
+        });
+
+        Ok(())
+    }
+
+    /// Finalize a fulfilled prayer the requester never reviewed. Permissionless
+    /// once review_seconds have passed since fulfilled_at, or as soon as voters
+    /// holding most of the bounty vote_confirm (funders alone only after the
+    /// funder_vote_deadline); pays out and credits reputation
+    /// exactly like confirm_prayer (same remaining accounts).
+    pub fn finalize_prayer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizePrayer<'info>>,
+    ) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            prayer.status == PrayerStatus::Fulfilled,
+            PrayerError::NotFulfilled
+        );
+        require!(
+            now > prayer.review_deadline() || prayer.funders_confirmed(now),
+            PrayerError::ReviewWindowOpen
+        );
+
+        prayer.status = PrayerStatus::Confirmed;
+
+        let split = pay_claimers(
+            prayer,
+            ctx.remaining_accounts,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.escrow,
+            &ctx.accounts.token_program,
+            prayer.reward_lamports,
+        )?;
+        let remainder = prayer.reward_lamports.checked_sub(split.distributed).unwrap();
+        split_refund(prayer, remainder);
+
+        credit_confirmation(
+            &mut ctx.accounts.answerer_agent,
+            &ctx.accounts.prayer_chain.config,
+        );
+
+        emit!(PrayerConfirmed {
+            id: prayer.id,
+            requester: prayer.requester,
+            answerer: prayer.answerer,
+            num_claimers: prayer.num_claimers,
+            reward_mint: prayer.reward_mint,
+            reward_per_claimer: split.reward_per_claimer,
+            reward_total: split.distributed,
+            remainder_policy: prayer.remainder_policy,
+            payouts: split.payouts,
+            requester_remainder: remainder,
+        });
+
+        emit!(PrayerFinalized {
+            id: prayer.id,
+            caller: ctx.accounts.caller.key(),
+            fulfilled_at: prayer.fulfilled_at,
+            review_seconds: prayer.review_seconds,
+        });
+
+        Ok(())
+    }
+
+    /// Vote to accept a fulfilled prayer's answer, weighted by the voter's
+    /// share of the bounty: a funder passes its Contribution, the requester
+    /// passes none and votes with whatever it put in itself. Once the votes
+    /// cover more than half the bounty, anyone can finalize_prayer without
+    /// waiting out the review window: right away if the requester voted, or
+    /// after the first half of the window if funders carried it alone.
+    pub fn vote_confirm(ctx: Context<VoteConfirm>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let voter = ctx.accounts.funder.key();
+
+        require!(
+            prayer.status == PrayerStatus::Fulfilled,
+            PrayerError::NotFulfilled
+        );
+
+        let votes = match ctx.accounts.contribution.as_mut() {
+            Some(contribution) => {
+                require!(!contribution.voted, PrayerError::AlreadyVoted);
+                contribution.voted = true;
+                contribution.amount
+            }
+            None => {
+                require!(voter == prayer.requester, PrayerError::NotRequester);
+                require!(!prayer.requester_voted, PrayerError::AlreadyVoted);
+                prayer.requester_voted = true;
+                prayer.reward_lamports.checked_sub(prayer.funded_lamports).unwrap()
+            }
+        };
+        prayer.confirm_votes = prayer.confirm_votes.checked_add(votes).unwrap();
+
+        emit!(ConfirmVoted {
+            prayer_id: prayer.id,
+            funder: voter,
+            confirm_votes: prayer.confirm_votes,
+            reward_lamports: prayer.reward_lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Reject a fulfilled answer and hand the prayer to the arbiters.
+    /// The bounty stays in escrow until an arbiter rules, or until the
+    /// config's dispute_timeout_seconds pass and anyone refunds it with
+    /// expire_dispute. Needs at least one arbiter to be set.
+    pub fn open_dispute(ctx: Context<OpenDispute>, reason_hash: [u8; 32]) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            prayer.status == PrayerStatus::Fulfilled,
+            PrayerError::NotFulfilled
+        );
+        require!(
+            now <= prayer.review_deadline(),
+            PrayerError::ReviewWindowElapsed
+        );
+
+        prayer.status = PrayerStatus::Disputed;
+        prayer.ruling_deadline = now
+            .checked_add(ctx.accounts.prayer_chain.config.dispute_timeout_seconds)
+            .unwrap();
+
+        emit!(DisputeOpened {
+            id: prayer.id,
+            requester: prayer.requester,
+            answerer: prayer.answerer,
+            reason_hash,
+            ruling_deadline: prayer.ruling_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Rule on a disputed prayer. claimer_bps of the bounty splits among the
+    /// claimers like confirm (same [claim_pda, recipient] remaining accounts);
+    /// the rest is refunded to the requester. A full award also grants the
+    /// answerer the confirmation reputation. Arbiters can still rule past the
+    /// ruling_deadline, until someone runs expire_dispute.
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        claimer_bps: u16,
+    ) -> Result<()> {
+        require!(
+            claimer_bps as u64 <= BPS_DENOMINATOR,
+            PrayerError::InvalidBasisPoints
+        );
+
+        let prayer = &mut ctx.accounts.prayer;
+        require!(
+            prayer.status == PrayerStatus::Disputed,
+            PrayerError::NotDisputed
+        );
+
+        let award = (prayer.reward_lamports as u128 * claimer_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+
+        let split = if claimer_bps > 0 {
+            prayer.status = PrayerStatus::Confirmed;
+            pay_claimers(
+                prayer,
+                ctx.remaining_accounts,
+                &ctx.accounts.reward_mint,
+                &ctx.accounts.escrow,
+                &ctx.accounts.token_program,
+                award,
+            )?
+        } else {
+            prayer.status = PrayerStatus::Cancelled;
+            Split::default()
+        };
+
+        // Everything not paid to claimers (including a requester remainder) goes back
+        let refunded = prayer.reward_lamports.checked_sub(split.distributed).unwrap();
+        let refund_to = requester_refund_account(
+            prayer,
+            ctx.accounts.requester.to_account_info(),
+            &ctx.accounts.requester_token_account,
+        )?;
+        let requester_refund = split_refund(prayer, refunded);
+        pay_bounty(
+            prayer,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.escrow,
+            &ctx.accounts.token_program,
+            refund_to,
+            requester_refund,
+        )?;
+
+        if claimer_bps as u64 == BPS_DENOMINATOR {
+            credit_confirmation(
+                &mut ctx.accounts.answerer_agent,
+                &ctx.accounts.prayer_chain.config,
+            );
+        }
+
+        emit!(DisputeResolved {
+            id: prayer.id,
+            requester: prayer.requester,
+            answerer: prayer.answerer,
+            arbiter: ctx.accounts.arbiter.key(),
+            claimer_bps,
+            num_claimers: prayer.num_claimers,
+            reward_mint: prayer.reward_mint,
+            reward_per_claimer: split.reward_per_claimer,
+            reward_total: split.distributed,
+            payouts: split.payouts,
+            refunded,
+        });
+
+        Ok(())
+    }
+
+    /// Refund a disputed prayer the arbiters never ruled on. Permissionless once
+    /// the ruling_deadline has passed; the whole bounty goes back to the
+    /// requester (and funders, pro-rata) as if an arbiter had awarded nothing.
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+
+        require!(
+            prayer.status == PrayerStatus::Disputed,
+            PrayerError::NotDisputed
+        );
+        require!(
+            Clock::get()?.unix_timestamp > prayer.ruling_deadline,
+            PrayerError::RulingWindowOpen
+        );
+
+        prayer.status = PrayerStatus::Cancelled;
+
+        let refunded = prayer.reward_lamports;
+        let refund_to = requester_refund_account(
+            prayer,
+            ctx.accounts.requester.to_account_info(),
+            &ctx.accounts.requester_token_account,
+        )?;
+        let requester_refund = split_refund(prayer, refunded);
+        pay_bounty(
+            prayer,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.escrow,
+            &ctx.accounts.token_program,
+            refund_to,
+            requester_refund,
+        )?;
+
+        emit!(DisputeExpired {
+            id: prayer.id,
+            requester: prayer.requester,
+            answerer: prayer.answerer,
+            caller: ctx.accounts.caller.key(),
+            refunded,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a prayer. Only when NO claims exist (num_claimers == 0) and no
+    /// competition answers are waiting to be paid; pending claims don't block
+    /// it and can be unclaimed by anyone afterwards.
+    pub fn cancel_prayer(ctx: Context<CancelPrayer>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+
+        require!(
+            prayer.status == PrayerStatus::Open,
+            PrayerError::CannotCancel
+        );
+        require!(
+            prayer.num_claimers == 0,
+            PrayerError::HasClaimers
+        );
+        require!(prayer.num_answers == 0, PrayerError::AnswersOutstanding);
+        require!(
+            prayer.requester == ctx.accounts.requester.key(),
+            PrayerError::NotRequester
+        );
+
+        prayer.status = PrayerStatus::Cancelled;
+
+        let refund_to = requester_refund_account(
+            prayer,
+            ctx.accounts.requester.to_account_info(),
+            &ctx.accounts.requester_token_account,
+        )?;
+        let reward = prayer.reward_lamports;
+        let refund = split_refund(prayer, reward);
+        pay_bounty(
+            prayer,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.escrow,
+            &ctx.accounts.token_program,
+            refund_to,
+            refund,
+        )?;
+
+        emit!(PrayerCancelled {
+            id: prayer.id,
+            requester: ctx.accounts.requester.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Remove a claim. Claimer voluntarily, or anyone after timeout or once
+    /// the prayer is confirmed, cancelled or expired. A claim whose competition
+    /// answer is in stays until the prayer resolves, so it can still be ranked.
+    /// Closes the Claim PDA and decrements num_claimers. The claim stake goes
+    /// back to the claimer, except when a third party removes a timed-out
+    /// claim on an unresolved prayer: then it is slashed into the bounty pool
+    /// (SOL prayers) or left on the prayer for the requester (token prayers).
+    pub fn unclaim_prayer(ctx: Context<UnclaimPrayer>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let claim = &ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
+
+        let is_resolved = matches!(
+            prayer.status,
+            PrayerStatus::Confirmed | PrayerStatus::Cancelled | PrayerStatus::Expired
+        );
+        require!(
+            is_resolved
+                || prayer.status == PrayerStatus::Open
+                || prayer.status == PrayerStatus::Active,
+            PrayerError::NotClaimed
+        );
+
+        let is_claimer = claim.claimer == ctx.accounts.caller.key();
+        let claim_expired = now > claim.deadline(prayer);
+
+        require!(
+            is_claimer || claim_expired || is_resolved,
+            PrayerError::NotClaimer
+        );
+        require!(
+            is_claimer || is_resolved || !claim.answered,
+            PrayerError::ClaimAnswered
+        );
+
+        let slashed = if claim.approved && !is_claimer && !is_resolved {
+            claim.stake
+        } else {
+            0
+        };
+        if slashed > 0 {
+            move_lamports(
+                &ctx.accounts.claim.to_account_info(),
+                &prayer.to_account_info(),
+                slashed,
+            )?;
+            if !prayer.is_token_bounty() {
+                prayer.reward_lamports = prayer.reward_lamports.checked_add(slashed).unwrap();
+            }
+        }
+
+        let claimer_agent = &mut ctx.accounts.claimer_agent;
+        claimer_agent.open_claims = claimer_agent.open_claims.checked_sub(1).unwrap();
+
+        if claim.approved {
+            prayer.num_claimers = prayer.num_claimers.checked_sub(1).unwrap();
+
+            // If was Active, reopen since a slot freed up
+            if prayer.status == PrayerStatus::Active {
+                prayer.status = PrayerStatus::Open;
+            }
+        } else {
+            prayer.num_pending = prayer.num_pending.checked_sub(1).unwrap();
+        }
+
+        emit!(ClaimRemoved {
+            prayer_id: prayer.id,
+            claimer: claim.claimer,
             num_claimers: prayer.num_claimers,
+            slashed,
+        });
+
+        // Claim PDA is closed by the `close = claimer_wallet` constraint
+        Ok(())
+    }
+
+    /// Close every Claim PDA left on a confirmed, cancelled or expired prayer
+    /// in one go, returning each claim's rent and stake to its claimer. Anyone
+    /// can call it. Remaining accounts: [claim_pda, claimer_agent,
+    /// claimer_wallet] triples, one per claim to close.
+    pub fn sweep_claims<'info>(ctx: Context<'_, '_, 'info, 'info, SweepClaims<'info>>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+
+        require!(
+            matches!(
+                prayer.status,
+                PrayerStatus::Confirmed | PrayerStatus::Cancelled | PrayerStatus::Expired
+            ),
+            PrayerError::CannotClose
+        );
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(3),
+            PrayerError::InvalidClaim
+        );
+
+        for triple in ctx.remaining_accounts.chunks(3) {
+            let (claim_info, agent_info, wallet) = (&triple[0], &triple[1], &triple[2]);
+            require!(
+                claim_info.is_writable && agent_info.is_writable && wallet.is_writable,
+                PrayerError::InvalidClaim
+            );
+            let claim = read_claim(prayer, claim_info)?;
+            require_keys_eq!(wallet.key(), claim.claimer, PrayerError::NotClaimer);
+
+            let mut claimer_agent = Account::<Agent>::try_from(agent_info)?;
+            require_keys_eq!(claimer_agent.wallet, claim.claimer, PrayerError::NotClaimer);
+            claimer_agent.open_claims = claimer_agent.open_claims.checked_sub(1).unwrap();
+            claimer_agent.exit(&crate::ID)?;
+
+            if claim.approved {
+                prayer.num_claimers = prayer.num_claimers.checked_sub(1).unwrap();
+            } else {
+                prayer.num_pending = prayer.num_pending.checked_sub(1).unwrap();
+            }
+
+            close_claim(claim_info, wallet)?;
+
+            emit!(ClaimRemoved {
+                prayer_id: prayer.id,
+                claimer: claim.claimer,
+                num_claimers: prayer.num_claimers,
+                slashed: 0,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Close a funder's Contribution once the prayer is confirmed, cancelled or
+    /// expired, paying them their pro-rata share of the refunded bounty plus the
+    /// PDA's rent. Anyone can call it; the lamports only go to the funder.
+    pub fn withdraw_contribution(ctx: Context<WithdrawContribution>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let contribution = &ctx.accounts.contribution;
+
+        require!(
+            matches!(
+                prayer.status,
+                PrayerStatus::Confirmed | PrayerStatus::Cancelled | PrayerStatus::Expired
+            ),
+            PrayerError::CannotClose
+        );
+
+        let refund = contribution.refund(prayer);
+        move_lamports(
+            &prayer.to_account_info(),
+            &ctx.accounts.funder_wallet.to_account_info(),
+            refund,
+        )?;
+        prayer.num_funders = prayer.num_funders.checked_sub(1).unwrap();
+
+        emit!(ContributionWithdrawn {
+            prayer_id: prayer.id,
+            funder: contribution.funder,
+            refund,
+            num_funders: prayer.num_funders,
         });
 
-        // Claim PDA is closed by the `close = claimer_wallet` constraint
-        Ok(())
-    }
+        Ok(())
+    }
+
+    /// Close an Answer PDA and return its rent to the answerer. The answerer can
+    /// withdraw while the prayer is open; anyone else only once the prayer is
+    /// resolved, so an expired competition's answers stay until they're paid.
+    pub fn close_answer(ctx: Context<CloseAnswer>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let answer = &ctx.accounts.answer;
+
+        let is_resolved = matches!(
+            prayer.status,
+            PrayerStatus::Confirmed | PrayerStatus::Cancelled | PrayerStatus::Expired
+        );
+        let is_open = matches!(prayer.status, PrayerStatus::Open | PrayerStatus::Active);
+        require!(is_resolved || is_open, PrayerError::AnswerLocked);
+
+        let is_answerer = answer.answerer == ctx.accounts.caller.key();
+        require!(is_answerer || is_resolved, PrayerError::NotClaimer);
+
+        prayer.num_answers = prayer.num_answers.checked_sub(1).unwrap();
+
+        emit!(AnswerRemoved {
+            prayer_id: prayer.id,
+            answerer: answer.answerer,
+            num_answers: prayer.num_answers,
+        });
+
+        // Answer PDA is closed by the `close = answerer_wallet` constraint
+        Ok(())
+    }
+
+    /// Close a resolved prayer and return rent to requester. Every claim must
+    /// be released first (unclaim_prayer or sweep_claims), every answer closed
+    /// (close_answer) and every contribution withdrawn (withdraw_contribution)
+    /// so no PDA outlives its prayer.
+    pub fn close_prayer(ctx: Context<ClosePrayer>) -> Result<()> {
+        let prayer = &ctx.accounts.prayer;
+
+        let is_terminal = matches!(
+            prayer.status,
+            PrayerStatus::Confirmed | PrayerStatus::Cancelled | PrayerStatus::Expired
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let is_expired = now > prayer.expires_at
+            && matches!(prayer.status, PrayerStatus::Open | PrayerStatus::Active);
+
+        require!(is_terminal || is_expired, PrayerError::CannotClose);
+        require!(
+            prayer.num_claimers == 0 && prayer.num_pending == 0,
+            PrayerError::ClaimsOutstanding
+        );
+        require!(prayer.num_answers == 0, PrayerError::AnswersOutstanding);
+        require!(prayer.num_funders == 0, PrayerError::ContributionsOutstanding);
+
+        if prayer.is_token_bounty() {
+            // Sweep whatever is left in escrow (expired bounty or split remainder)
+            // back to the requester, then close the escrow for its rent.
+            let (mint, escrow, token_program) = token_escrow_accounts(
+                prayer,
+                &ctx.accounts.reward_mint,
+                &ctx.accounts.escrow,
+                &ctx.accounts.token_program,
+            )?;
+            let refund_to = requester_refund_account(
+                prayer,
+                ctx.accounts.requester.to_account_info(),
+                &ctx.accounts.requester_token_account,
+            )?;
+            pay_bounty(
+                prayer,
+                &ctx.accounts.reward_mint,
+                &ctx.accounts.escrow,
+                &ctx.accounts.token_program,
+                refund_to,
+                escrow.amount,
+            )?;
+            close_escrow(
+                prayer,
+                mint,
+                escrow,
+                token_program,
+                ctx.accounts.requester.to_account_info(),
+            )?;
+        } else if is_expired {
+            pay_bounty(
+                prayer,
+                &None,
+                &None,
+                &None,
+                ctx.accounts.requester.to_account_info(),
+                prayer.reward_lamports,
+            )?;
+        }
+
+        let requester_agent = &mut ctx.accounts.requester_agent;
+        requester_agent.open_prayers = requester_agent.open_prayers.checked_sub(1).unwrap();
+
+        Ok(())
+    }
+
+    /// Expire an unanswered prayer past its expires_at. Anyone can call it.
+    /// Refunds the bounty to the requester (and funders, pro-rata) and marks the
+    /// prayer Expired, which lets anyone release its remaining claims and
+    /// contributions. Once none are left (now or on a later call) the prayer
+    /// is closed: the caller earns a keeper fee out of the rent and the
+    /// requester gets the rest. A competition holding submitted answers can't
+    /// be expired; settle_competition pays them instead.
+    pub fn expire_prayer(ctx: Context<ExpirePrayer>) -> Result<()> {
+        let prayer = &mut ctx.accounts.prayer;
+        let now = Clock::get()?.unix_timestamp;
+
+        let is_live = matches!(prayer.status, PrayerStatus::Open | PrayerStatus::Active);
+        require!(
+            is_live || prayer.status == PrayerStatus::Expired,
+            PrayerError::CannotExpire
+        );
+        require!(now > prayer.expires_at, PrayerError::NotExpired);
+        // Submitted competition answers are paid by select_winners or
+        // settle_competition, never refunded
+        require!(
+            !is_live || prayer.num_answers == 0,
+            PrayerError::AnswersOutstanding
+        );
+
+        let mut refund = 0;
+        if is_live {
+            prayer.status = PrayerStatus::Expired;
+            refund = prayer.reward_lamports;
+
+            let refund_to = requester_refund_account(
+                prayer,
+                ctx.accounts.requester.to_account_info(),
+                &ctx.accounts.requester_token_account,
+            )?;
+            let requester_refund = split_refund(prayer, refund);
+            pay_bounty(
+                prayer,
+                &ctx.accounts.reward_mint,
+                &ctx.accounts.escrow,
+                &ctx.accounts.token_program,
+                refund_to,
+                requester_refund,
+            )?;
+        }
+
+        let outstanding = prayer.num_claimers > 0
+            || prayer.num_pending > 0
+            || prayer.num_answers > 0
+            || prayer.num_funders > 0;
+        if outstanding {
+            // A repeat call only closes the prayer, so it waits for them to go
+            if !is_live {
+                require!(prayer.num_answers == 0, PrayerError::AnswersOutstanding);
+                require!(
+                    prayer.num_claimers == 0 && prayer.num_pending == 0,
+                    PrayerError::ClaimsOutstanding
+                );
+                return err!(PrayerError::ContributionsOutstanding);
+            }
+        } else {
+            if prayer.is_token_bounty() {
+                let (mint, escrow, token_program) = token_escrow_accounts(
+                    prayer,
+                    &ctx.accounts.reward_mint,
+                    &ctx.accounts.escrow,
+                    &ctx.accounts.token_program,
+                )?;
+                close_escrow(
+                    prayer,
+                    mint,
+                    escrow,
+                    token_program,
+                    ctx.accounts.requester.to_account_info(),
+                )?;
+            }
+
+            move_lamports(
+                &prayer.to_account_info(),
+                &ctx.accounts.keeper.to_account_info(),
+                KEEPER_FEE_LAMPORTS,
+            )?;
+            prayer.close(ctx.accounts.requester.to_account_info())?;
+
+            let requester_agent = &mut ctx.accounts.requester_agent;
+            requester_agent.open_prayers = requester_agent.open_prayers.checked_sub(1).unwrap();
+        }
+
+        emit!(PrayerExpired {
+            id: prayer.id,
+            requester: prayer.requester,
+            keeper: ctx.accounts.keeper.key(),
+            refund,
+            keeper_fee: if outstanding { 0 } else { KEEPER_FEE_LAMPORTS },
+        });
+
+        Ok(())
+    }
+}
+
+/// Whether an agent meets a prayer's claim requirements at `now`
+fn check_requirements(requirements: &ClaimRequirements, agent: &Agent, now: i64) -> Result<()> {
+    require!(
+        agent.reputation >= requirements.min_reputation,
+        PrayerError::ReputationTooLow
+    );
+    require!(
+        agent.prayers_confirmed >= requirements.min_confirmed,
+        PrayerError::TooFewConfirmed
+    );
+    require!(
+        now.saturating_sub(agent.registered_at) >= requirements.min_agent_age_seconds,
+        PrayerError::AgentTooNew
+    );
+    Ok(())
+}
+
+/// Count an approved claim toward max_claimers, moving the prayer to Active
+/// once every slot is filled
+fn take_slot(prayer: &mut Prayer) {
+    prayer.num_claimers = prayer.num_claimers.checked_add(1).unwrap();
+    if prayer.num_claimers >= prayer.max_claimers {
+        prayer.status = PrayerStatus::Active;
+    }
+}
+
+// ── Payouts ───────────────────────────────────────────────
+
+/// Reputation for an answer the requester (or the review timeout) accepted
+fn credit_confirmation(answerer_agent: &mut Agent, config: &ProtocolConfig) {
+    answerer_agent.prayers_confirmed = answerer_agent
+        .prayers_confirmed
+        .checked_add(1)
+        .unwrap();
+    answerer_agent.reputation = answerer_agent
+        .reputation
+        .checked_add(config.confirm_reputation)
+        .unwrap();
+}
+
+/// How pay_claimers divided a bounty
+#[derive(Default)]
+struct Split {
+    reward_per_claimer: u64, // Even share before the remainder (0 when weighted)
+    distributed: u64,        // Total paid out, remainder included
+    payouts: Vec<ClaimerPayout>,
+}
+
+/// Split `amount` across every active claimer, equally or by Claim weight_bps
+/// when the prayer is weighted, handing the rounding remainder out per the
+/// prayer's remainder_policy. Remaining accounts are [claim_pda, recipient]
+/// pairs and must cover each claimer exactly once.
+/// With RemainderPolicy::Requester the remainder stays in escrow.
+fn pay_claimers<'info>(
+    prayer: &Account<'info, Prayer>,
+    remaining: &'info [AccountInfo<'info>],
+    reward_mint: &Option<InterfaceAccount<'info, Mint>>,
+    escrow: &Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Option<Interface<'info, TokenInterface>>,
+    amount: u64,
+) -> Result<Split> {
+    require!(
+        remaining.len() == prayer.num_claimers as usize * 2,
+        PrayerError::PayoutMismatch
+    );
+
+    let reward_per_claimer = if prayer.num_claimers > 0 && !prayer.weighted {
+        amount / prayer.num_claimers as u64
+    } else {
+        0
+    };
+
+    // Verify every pair up front so the remainder can be worked out over the
+    // full claimer set: (claimer, share before remainder, ranked, recipient)
+    let mut recipients: Vec<(Pubkey, u64, bool, &'info AccountInfo<'info>)> =
+        Vec::with_capacity(prayer.num_claimers as usize);
+    for pair in remaining.chunks(2) {
+        let (claim_info, recipient) = (&pair[0], &pair[1]);
+        let claim = verify_payout_recipient(prayer, claim_info, recipient)?;
+        require!(
+            !recipients.iter().any(|(paid, _, _, _)| *paid == claim.claimer),
+            PrayerError::PayoutMismatch
+        );
+        let share = if prayer.weighted {
+            (amount as u128 * claim.weight_bps as u128 / BPS_DENOMINATOR as u128) as u64
+        } else {
+            reward_per_claimer
+        };
+        let ranked = !prayer.weighted || claim.weight_bps > 0;
+        recipients.push((claim.claimer, share, ranked, recipient));
+    }
+
+    let shares: u64 = recipients.iter().map(|(_, share, _, _)| share).sum();
+    let remainder = amount.checked_sub(shares).unwrap();
+
+    let mut split = Split {
+        reward_per_claimer,
+        distributed: 0,
+        payouts: Vec::with_capacity(recipients.len()),
+    };
+
+    for (claimer, share, ranked, recipient) in recipients.iter() {
+        let extra = match prayer.remainder_policy {
+            RemainderPolicy::Requester => 0,
+            RemainderPolicy::Answerer if *claimer == prayer.answerer => remainder,
+            RemainderPolicy::Answerer => 0,
+            RemainderPolicy::RoundRobin if !ranked => 0,
+            RemainderPolicy::RoundRobin => {
+                // Ordered by wallet address so the caller's account order can't pick winners
+                let rank = recipients
+                    .iter()
+                    .filter(|(other, _, other_ranked, _)| *other_ranked && other < claimer)
+                    .count();
+                u64::from((rank as u64) < remainder)
+            }
+        };
+        let share = share.checked_add(extra).unwrap();
+
+        pay_bounty(
+            prayer,
+            reward_mint,
+            escrow,
+            token_program,
+            (*recipient).clone(),
+            share,
+        )?;
+        split.distributed = split.distributed.checked_add(share).unwrap();
+        split.payouts.push(ClaimerPayout { claimer: *claimer, amount: share });
+    }
+
+    Ok(split)
+}
+
+/// Pay part of the bounty out of escrow: lamports from the prayer PDA for
+/// SOL bounties, or a token transfer from the escrow account for SPL bounties.
+fn pay_bounty<'info>(
+    prayer: &Account<'info, Prayer>,
+    reward_mint: &Option<InterfaceAccount<'info, Mint>>,
+    escrow: &Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Option<Interface<'info, TokenInterface>>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if prayer.is_token_bounty() {
+        let (mint, escrow, token_program) =
+            token_escrow_accounts(prayer, reward_mint, escrow, token_program)?;
+        return transfer_from_escrow(prayer, mint, escrow, token_program, to, amount);
+    }
+
+    move_lamports(&prayer.to_account_info(), &to, amount)
+}
+
+/// Move lamports out of a program-owned account by direct balance edits.
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from.lamports().checked_sub(amount).unwrap();
+    **to.try_borrow_mut_lamports()? = to.lamports().checked_add(amount).unwrap();
+    Ok(())
+}
+
+/// Hand a claim's stake back to its claimer once they have answered.
+fn return_stake<'info>(claim: &mut Account<'info, Claim>, claimer: &Signer<'info>) -> Result<()> {
+    let stake = claim.stake;
+    if stake > 0 {
+        move_lamports(&claim.to_account_info(), &claimer.to_account_info(), stake)?;
+        claim.stake = 0;
+    }
+    Ok(())
+}
+
+/// Set aside the funders' pro-rata part of a bounty refund on the prayer, for
+/// withdraw_contribution, and return what is left for the requester.
+fn split_refund(prayer: &mut Prayer, refund: u64) -> u64 {
+    if prayer.funded_lamports == 0 {
+        return refund;
+    }
+    let funders = (refund as u128 * prayer.funded_lamports as u128
+        / prayer.reward_lamports as u128) as u64;
+    prayer.funder_refund = prayer.funder_refund.checked_add(funders).unwrap();
+    refund.checked_sub(funders).unwrap()
+}
+
+/// Where refunds to the requester go: their wallet for SOL bounties,
+/// or their token account for SPL bounties.
+fn requester_refund_account<'info>(
+    prayer: &Prayer,
+    requester: AccountInfo<'info>,
+    requester_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<AccountInfo<'info>> {
+    if !prayer.is_token_bounty() {
+        return Ok(requester);
+    }
+    requester_token_account
+        .as_ref()
+        .map(|account| account.to_account_info())
+        .ok_or_else(|| error!(PrayerError::MissingTokenAccounts))
+}
+
+/// Check one confirm_prayer payout pair and return the Claim it pays.
+/// The Claim PDA must belong to this prayer, and the recipient must be the
+/// claimer's wallet (SOL) or a reward-mint token account the claimer owns.
+fn verify_payout_recipient<'info>(
+    prayer: &Prayer,
+    claim_info: &'info AccountInfo<'info>,
+    recipient: &'info AccountInfo<'info>,
+) -> Result<Claim> {
+    let claim = load_claim(prayer, claim_info)?;
+    verify_recipient(prayer, &claim.claimer, recipient)?;
+    Ok(claim)
+}
+
+/// Check a payout recipient is `wallet` itself (SOL) or a reward-mint token
+/// account `wallet` owns.
+fn verify_recipient<'info>(
+    prayer: &Prayer,
+    wallet: &Pubkey,
+    recipient: &'info AccountInfo<'info>,
+) -> Result<()> {
+    if prayer.is_token_bounty() {
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(recipient)?;
+        require_keys_eq!(token_account.owner, *wallet, PrayerError::RecipientMismatch);
+        require_keys_eq!(token_account.mint, prayer.reward_mint, PrayerError::MintMismatch);
+    } else {
+        require_keys_eq!(recipient.key(), *wallet, PrayerError::RecipientMismatch);
+    }
+    Ok(())
+}
+
+/// Read an Answer passed as a remaining account, checking it is this prayer's
+/// answer PDA for the answerer it names.
+fn load_answer(prayer: &Prayer, answer_info: &AccountInfo) -> Result<Answer> {
+    require_keys_eq!(*answer_info.owner, crate::ID, PrayerError::InvalidClaim);
+    let answer = Answer::try_deserialize(&mut &answer_info.try_borrow_data()?[..])?;
+    let expected = Pubkey::create_program_address(
+        &[
+            b"answer",
+            prayer.id.to_le_bytes().as_ref(),
+            answer.answerer.as_ref(),
+            &[answer.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| error!(PrayerError::InvalidClaim))?;
+    require_keys_eq!(expected, answer_info.key(), PrayerError::InvalidClaim);
+    require!(answer.prayer_id == prayer.id, PrayerError::InvalidClaim);
+
+    Ok(answer)
+}
+
+/// Read a Claim passed as a remaining account, checking it is this prayer's
+/// claim PDA for the claimer it names and has been approved.
+fn load_claim(prayer: &Prayer, claim_info: &AccountInfo) -> Result<Claim> {
+    let claim = read_claim(prayer, claim_info)?;
+    require!(claim.approved, PrayerError::ClaimPending);
+    Ok(claim)
+}
+
+/// Read a Claim passed as a remaining account, checking it is this prayer's
+/// claim PDA for the claimer it names. Pending claims are accepted.
+fn read_claim(prayer: &Prayer, claim_info: &AccountInfo) -> Result<Claim> {
+    require_keys_eq!(*claim_info.owner, crate::ID, PrayerError::InvalidClaim);
+    let claim = Claim::try_deserialize(&mut &claim_info.try_borrow_data()?[..])?;
+    let expected = Pubkey::create_program_address(
+        &[
+            b"claim",
+            prayer.id.to_le_bytes().as_ref(),
+            claim.claimer.as_ref(),
+            &[claim.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| error!(PrayerError::InvalidClaim))?;
+    require_keys_eq!(expected, claim_info.key(), PrayerError::InvalidClaim);
+    require!(claim.prayer_id == prayer.id, PrayerError::InvalidClaim);
+
+    Ok(claim)
+}
+
+/// Close a Claim PDA passed as a remaining account, sending all of its
+/// lamports (rent and any stake) to the claimer's wallet.
+fn close_claim(claim_info: &AccountInfo, wallet: &AccountInfo) -> Result<()> {
+    move_lamports(claim_info, wallet, claim_info.lamports())?;
+    claim_info.assign(&system_program::ID);
+    claim_info.resize(0)?;
+    Ok(())
+}
+
+// ── Token Escrow ──────────────────────────────────────────
+
+/// Unpack the optional token accounts a token-bounty prayer needs.
+fn token_escrow_accounts<'a, 'info>(
+    prayer: &Prayer,
+    reward_mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    escrow: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<(
+    &'a InterfaceAccount<'info, Mint>,
+    &'a InterfaceAccount<'info, TokenAccount>,
+    &'a Interface<'info, TokenInterface>,
+)> {
+    let (Some(mint), Some(escrow), Some(token_program)) =
+        (reward_mint.as_ref(), escrow.as_ref(), token_program.as_ref())
+    else {
+        return err!(PrayerError::MissingTokenAccounts);
+    };
+    require_keys_eq!(mint.key(), prayer.reward_mint, PrayerError::MintMismatch);
+    Ok((mint, escrow, token_program))
+}
+
+/// Move tokens out of the prayer's escrow, signed by the prayer PDA.
+fn transfer_from_escrow<'info>(
+    prayer: &Account<'info, Prayer>,
+    mint: &InterfaceAccount<'info, Mint>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let id_bytes = prayer.id.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"prayer", id_bytes.as_ref(), &[prayer.bump]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: escrow.to_account_info(),
+                mint: mint.to_account_info(),
+                to,
+                authority: prayer.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Close the (empty) escrow token account, returning its rent.
+/// Token-2022 won't close an account still holding withheld transfer fees,
+/// so any the deposit left behind are harvested to the mint first.
+fn close_escrow<'info>(
+    prayer: &Account<'info, Prayer>,
+    mint: &InterfaceAccount<'info, Mint>,
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    destination: AccountInfo<'info>,
+) -> Result<()> {
+    let id_bytes = prayer.id.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"prayer", id_bytes.as_ref(), &[prayer.bump]];
+
+    if withheld_fees(escrow)? > 0 {
+        let harvest = transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            token_program.key,
+            &mint.key(),
+            &[&escrow.key()],
+        )?;
+        anchor_lang::solana_program::program::invoke(
+            &harvest,
+            &[mint.to_account_info(), escrow.to_account_info()],
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination,
+            authority: prayer.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
+
+/// Transfer fees withheld on a Token-2022 account (0 for SPL Token accounts
+/// and for mints without a transfer fee)
+fn withheld_fees(account: &InterfaceAccount<TokenAccount>) -> Result<u64> {
+    let info = account.to_account_info();
+    if info.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<transfer_fee::TransferFeeAmount>()
+        .map_or(0, |fees| u64::from(fees.withheld_amount)))
+}
+
+// ── Contexts ──────────────────────────────────────────────
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PrayerChain::INIT_SPACE,
+        seeds = [b"prayer-chain"],
+        bump,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        has_one = authority @ PrayerError::NotAuthority,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = prayer_chain.pending_authority == new_authority.key()
+            @ PrayerError::NotPendingAuthority,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        has_one = authority @ PrayerError::NotAuthority,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        has_one = authority @ PrayerError::NotAuthority,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetArbiters<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        has_one = authority @ PrayerError::NotAuthority,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        has_one = authority @ PrayerError::NotAuthority,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, skills: String)]
+pub struct RegisterAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_REGISTER) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + Agent::space(name.len(), skills.len()),
+        seeds = [b"agent", wallet.key().as_ref()],
+        bump,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: Option<String>, skills: Option<String>)]
+pub struct UpdateAgent<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_REGISTER) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", wallet.key().as_ref()],
+        bump = agent.bump,
+        realloc = 8 + Agent::space(
+            name.as_ref().map_or(agent.name.len(), String::len),
+            skills.as_ref().map_or(agent.skills.len(), String::len),
+        ),
+        realloc::payer = wallet,
+        realloc::zero = false,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", wallet.key().as_ref()],
+        bump = agent.bump,
+        close = wallet,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostPrayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_POST) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", requester.key().as_ref()],
+        bump = requester_agent.bump,
+    )]
+    pub requester_agent: Account<'info, Agent>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + Prayer::INIT_SPACE,
+        seeds = [b"prayer", prayer_chain.total_prayers.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    /// Bounty mint (SPL Token or Token-2022). Omit for a native SOL bounty.
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token escrow owned by the prayer PDA
+    #[account(
+        init,
+        payer = requester,
+        seeds = [b"escrow", prayer_chain.total_prayers.to_le_bytes().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = prayer,
+        token::token_program = token_program,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ExtendPrayer<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_POST) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct TopUpBounty<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_POST) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct FundPrayer<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_POST) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        constraint = prayer.requester != funder.key() @ PrayerError::CannotFundOwn,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + Contribution::INIT_SPACE,
+        seeds = [b"contribution", prayer.id.to_le_bytes().as_ref(), funder.key().as_ref()],
+        bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    /// CHECK: The funder's Claim PDA; must not exist, as claimers can't fund
+    #[account(
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), funder.key().as_ref()],
+        bump,
+        constraint = funder_claim.data_is_empty() @ PrayerError::ClaimerCannotFund,
+    )]
+    pub funder_claim: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ClaimPrayer<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CLAIM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        init,
+        payer = claimer,
+        space = 8 + Claim::INIT_SPACE,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claimer.key().as_ref()],
+        bump,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    /// CHECK: The claimer's Contribution PDA; must not exist, as funders can't claim
+    #[account(
+        seeds = [b"contribution", prayer.id.to_le_bytes().as_ref(), claimer.key().as_ref()],
+        bump,
+        constraint = claimer_contribution.data_is_empty() @ PrayerError::FunderCannotClaim,
+    )]
+    pub claimer_contribution: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", claimer.key().as_ref()],
+        bump = claimer_agent.bump,
+    )]
+    pub claimer_agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ApproveClaim<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CLAIM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claim.claimer.as_ref()],
+        bump = claim.bump,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct RejectClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claim.claimer.as_ref()],
+        bump = claim.bump,
+        close = claimer_wallet,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", claim.claimer.as_ref()],
+        bump = claimer_agent.bump,
+    )]
+    pub claimer_agent: Account<'info, Agent>,
+
+    /// CHECK: Receives rent from closed Claim account
+    #[account(
+        mut,
+        constraint = claimer_wallet.key() == claim.claimer @ PrayerError::NotClaimer
+    )]
+    pub claimer_wallet: UncheckedAccount<'info>,
+
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct HeartbeatClaim<'info> {
+    #[account(
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claimer.key().as_ref()],
+        bump = claim.bump,
+        constraint = claim.approved @ PrayerError::ClaimPending,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    pub claimer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct DeliverContent<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_ANSWER) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claim.claimer.as_ref()],
+        bump = claim.bump,
+        constraint = claim.approved @ PrayerError::ClaimPending,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct AnswerPrayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_ANSWER) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    /// Claim PDA proves the answerer is a legitimate claimer; its stake is returned
+    #[account(
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), answerer.key().as_ref()],
+        bump = claim.bump,
+        constraint = claim.approved @ PrayerError::ClaimPending,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", answerer.key().as_ref()],
+        bump = answerer_agent.bump,
+    )]
+    pub answerer_agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub answerer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SubmitAnswer<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_ANSWER) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    /// Claim PDA proves the answerer is a legitimate claimer; its stake is returned
+    #[account(
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), answerer.key().as_ref()],
+        bump = claim.bump,
+        constraint = claim.approved @ PrayerError::ClaimPending,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        init,
+        payer = answerer,
+        space = 8 + Answer::INIT_SPACE,
+        seeds = [b"answer", prayer.id.to_le_bytes().as_ref(), answerer.key().as_ref()],
+        bump,
+    )]
+    pub answer: Account<'info, Answer>,
+
+    #[account(mut)]
+    pub answerer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SelectWinners<'info> {
+    #[account(
+        mut,
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CONFIRM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    /// Agent of the top-ranked answerer, credited with the answer
+    #[account(
+        mut,
+        seeds = [b"agent", winner_agent.wallet.as_ref()],
+        bump = winner_agent.bump,
+    )]
+    pub winner_agent: Account<'info, Agent>,
+
+    pub requester: Signer<'info>,
+
+    // Remaining accounts: [answer_pda (mut), claim_pda (mut)] per ranked answerer
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SettleCompetition<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CONFIRM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    pub caller: Signer<'info>,
+
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Remaining accounts: [answer_pda, answerer wallet or token account (mut)] per answer
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetClaimWeights<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CONFIRM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    pub setter: Signer<'info>,
+
+    // Remaining accounts: claim_pda (mut) per claimer, matching weights_bps
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ConfirmPrayer<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CONFIRM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
+
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+    )]
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", prayer.answerer.as_ref()],
+        bump = answerer_agent.bump,
+    )]
+    pub answerer_agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Remaining accounts: [claim_pda, claimer wallet or token account (mut)] per claimer
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct FinalizePrayer<'info> {
+    #[account(
+        seeds = [b"prayer-chain"],
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CONFIRM) @ PrayerError::Paused,
+    )]
+    pub prayer_chain: Account<'info, PrayerChain>,
 
-    /// Close a resolved prayer and return rent to requester.
-    pub fn close_prayer(ctx: Context<ClosePrayer>) -> Result<()> {
-        let prayer = &ctx.accounts.prayer;
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
 
-        let is_terminal = matches!(
-            prayer.status,
-            PrayerStatus::Confirmed | PrayerStatus::Cancelled
-        );
+    #[account(
+        mut,
+        seeds = [b"agent", prayer.answerer.as_ref()],
+        bump = answerer_agent.bump,
+    )]
+    pub answerer_agent: Account<'info, Agent>,
 
-        let now = Clock::get()?.unix_timestamp;
-        let is_expired = now > prayer.expires_at
-            && matches!(prayer.status, PrayerStatus::Open | PrayerStatus::Active);
+    pub caller: Signer<'info>,
 
-        require!(is_terminal || is_expired, PrayerError::CannotClose);
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
 
-        if is_expired && prayer.reward_lamports > 0 {
-            let prayer_info = ctx.accounts.prayer.to_account_info();
-            let requester_info = ctx.accounts.requester.to_account_info();
-
-            **prayer_info.try_borrow_mut_lamports()? = prayer_info
-                .lamports()
-                .checked_sub(prayer.reward_lamports)
-                .unwrap();
-            **requester_info.try_borrow_mut_lamports()? = requester_info
-                .lamports()
-                .checked_add(prayer.reward_lamports)
-                .unwrap();
-        }
+    #[account(
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
 
-        Ok(())
-    }
-}
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 
-// ── Contexts ──────────────────────────────────────────────
+    // Remaining accounts: [claim_pda, claimer wallet or token account (mut)] per claimer
+}
 
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+#[instruction()]
+pub struct VoteConfirm<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = 8 + PrayerChain::INIT_SPACE,
         seeds = [b"prayer-chain"],
-        bump,
+        bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_CONFIRM) @ PrayerError::Paused,
     )]
     pub prayer_chain: Account<'info, PrayerChain>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+    )]
+    pub prayer: Account<'info, Prayer>,
 
-    pub system_program: Program<'info, System>,
+    // None when the requester votes
+    #[account(
+        mut,
+        seeds = [b"contribution", prayer.id.to_le_bytes().as_ref(), funder.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Option<Account<'info, Contribution>>,
+
+    pub funder: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct RegisterAgent<'info> {
+#[instruction()]
+pub struct OpenDispute<'info> {
     #[account(
-        mut,
         seeds = [b"prayer-chain"],
         bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_DISPUTE) @ PrayerError::Paused,
+        constraint = !prayer_chain.arbiters.is_empty() @ PrayerError::NoArbiters,
     )]
     pub prayer_chain: Account<'info, PrayerChain>,
 
     #[account(
-        init,
-        payer = wallet,
-        space = 8 + Agent::INIT_SPACE,
-        seeds = [b"agent", wallet.key().as_ref()],
-        bump,
+        mut,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
     )]
-    pub agent: Account<'info, Agent>,
-
-    #[account(mut)]
-    pub wallet: Signer<'info>,
+    pub prayer: Account<'info, Prayer>,
 
-    pub system_program: Program<'info, System>,
+    pub requester: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct PostPrayer<'info> {
+#[instruction()]
+pub struct ResolveDispute<'info> {
     #[account(
-        mut,
         seeds = [b"prayer-chain"],
         bump = prayer_chain.bump,
+        constraint = !prayer_chain.is_paused(PAUSE_DISPUTE) @ PrayerError::Paused,
     )]
     pub prayer_chain: Account<'info, PrayerChain>,
 
     #[account(
         mut,
-        seeds = [b"agent", requester.key().as_ref()],
-        bump = requester_agent.bump,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
     )]
-    pub requester_agent: Account<'info, Agent>,
+    pub prayer: Account<'info, Prayer>,
 
     #[account(
-        init,
-        payer = requester,
-        space = 8 + Prayer::INIT_SPACE,
-        seeds = [b"prayer", prayer_chain.total_prayers.to_le_bytes().as_ref()],
-        bump,
+        mut,
+        seeds = [b"agent", prayer.answerer.as_ref()],
+        bump = answerer_agent.bump,
     )]
-    pub prayer: Account<'info, Prayer>,
+    pub answerer_agent: Account<'info, Agent>,
 
+    /// CHECK: Receives the refunded share; has_one ties it to the prayer
     #[account(mut)]
-    pub requester: Signer<'info>,
+    pub requester: UncheckedAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    #[account(
+        constraint = prayer_chain.arbiters.contains(&arbiter.key()) @ PrayerError::NotArbiter
+    )]
+    pub arbiter: Signer<'info>,
+
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Remaining accounts: [claim_pda, claimer wallet or token account (mut)] per claimer
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct ClaimPrayer<'info> {
+pub struct ExpireDispute<'info> {
     #[account(
         mut,
         seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
         bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
     )]
     pub prayer: Account<'info, Prayer>,
 
+    /// CHECK: Receives the refund; has_one ties it to the prayer
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
     #[account(
-        init,
-        payer = claimer,
-        space = 8 + Claim::INIT_SPACE,
-        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claimer.key().as_ref()],
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
         bump,
     )]
-    pub claim: Account<'info, Claim>,
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
-        seeds = [b"agent", claimer.key().as_ref()],
-        bump = claimer_agent.bump,
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
     )]
-    pub claimer_agent: Account<'info, Agent>,
-
-    #[account(mut)]
-    pub claimer: Signer<'info>,
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct DeliverContent<'info> {
+pub struct CancelPrayer<'info> {
     #[account(
+        mut,
         seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
         bump = prayer.bump,
         has_one = requester @ PrayerError::NotRequester,
     )]
     pub prayer: Account<'info, Prayer>,
 
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
     #[account(
         mut,
-        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claim.claimer.as_ref()],
-        bump = claim.bump,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub claim: Account<'info, Claim>,
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub requester: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct AnswerPrayer<'info> {
+pub struct UnclaimPrayer<'info> {
     #[account(
-        mut,
         seeds = [b"prayer-chain"],
         bump = prayer_chain.bump,
     )]
@@ -704,104 +3722,186 @@ pub struct AnswerPrayer<'info> {
     )]
     pub prayer: Account<'info, Prayer>,
 
-    /// Claim PDA proves the answerer is a legitimate claimer
     #[account(
-        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), answerer.key().as_ref()],
+        mut,
+        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claim.claimer.as_ref()],
         bump = claim.bump,
+        close = claimer_wallet,
     )]
     pub claim: Account<'info, Claim>,
 
     #[account(
         mut,
-        seeds = [b"agent", answerer.key().as_ref()],
-        bump = answerer_agent.bump,
+        seeds = [b"agent", claim.claimer.as_ref()],
+        bump = claimer_agent.bump,
     )]
-    pub answerer_agent: Account<'info, Agent>,
+    pub claimer_agent: Account<'info, Agent>,
 
-    pub answerer: Signer<'info>,
+    /// CHECK: Receives rent from closed Claim account
+    #[account(
+        mut,
+        constraint = claimer_wallet.key() == claim.claimer @ PrayerError::NotClaimer
+    )]
+    pub claimer_wallet: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct ConfirmPrayer<'info> {
+pub struct SweepClaims<'info> {
     #[account(
         mut,
         seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
         bump = prayer.bump,
-        has_one = requester @ PrayerError::NotRequester,
     )]
     pub prayer: Account<'info, Prayer>,
 
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct WithdrawContribution<'info> {
     #[account(
         mut,
-        seeds = [b"agent", prayer.answerer.as_ref()],
-        bump = answerer_agent.bump,
+        seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
+        bump = prayer.bump,
     )]
-    pub answerer_agent: Account<'info, Agent>,
+    pub prayer: Account<'info, Prayer>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", prayer.id.to_le_bytes().as_ref(), funder_wallet.key().as_ref()],
+        bump = contribution.bump,
+        close = funder_wallet,
+    )]
+    pub contribution: Account<'info, Contribution>,
 
+    /// CHECK: Receives the refund and rent; the contribution PDA seeds tie it to the funder
     #[account(mut)]
-    pub requester: Signer<'info>,
+    pub funder_wallet: UncheckedAccount<'info>,
 
-    // Remaining accounts: claimer wallets (mut) for bounty distribution
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct CancelPrayer<'info> {
+pub struct CloseAnswer<'info> {
     #[account(
         mut,
         seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
         bump = prayer.bump,
-        has_one = requester @ PrayerError::NotRequester,
     )]
     pub prayer: Account<'info, Prayer>,
 
-    #[account(mut)]
-    pub requester: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"answer", prayer.id.to_le_bytes().as_ref(), answer.answerer.as_ref()],
+        bump = answer.bump,
+        close = answerer_wallet,
+    )]
+    pub answer: Account<'info, Answer>,
+
+    /// CHECK: Receives rent from closed Answer account
+    #[account(
+        mut,
+        constraint = answerer_wallet.key() == answer.answerer @ PrayerError::NotClaimer
+    )]
+    pub answerer_wallet: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct UnclaimPrayer<'info> {
+pub struct ClosePrayer<'info> {
     #[account(
         mut,
         seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
         bump = prayer.bump,
+        has_one = requester @ PrayerError::NotRequester,
+        close = requester,
     )]
     pub prayer: Account<'info, Prayer>,
 
     #[account(
         mut,
-        seeds = [b"claim", prayer.id.to_le_bytes().as_ref(), claim.claimer.as_ref()],
-        bump = claim.bump,
-        close = claimer_wallet,
+        seeds = [b"agent", requester.key().as_ref()],
+        bump = requester_agent.bump,
     )]
-    pub claim: Account<'info, Claim>,
+    pub requester_agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    /// Writable so withheld Token-2022 fees can be harvested before the escrow closes
+    #[account(mut)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
 
-    /// CHECK: Receives rent from closed Claim account
     #[account(
         mut,
-        constraint = claimer_wallet.key() == claim.claimer @ PrayerError::NotClaimer
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub claimer_wallet: UncheckedAccount<'info>,
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
 #[instruction()]
-pub struct ClosePrayer<'info> {
+pub struct ExpirePrayer<'info> {
     #[account(
         mut,
         seeds = [b"prayer", prayer.id.to_le_bytes().as_ref()],
         bump = prayer.bump,
         has_one = requester @ PrayerError::NotRequester,
-        close = requester,
     )]
     pub prayer: Account<'info, Prayer>,
 
+    #[account(
+        mut,
+        seeds = [b"agent", requester.key().as_ref()],
+        bump = requester_agent.bump,
+    )]
+    pub requester_agent: Account<'info, Agent>,
+
+    /// CHECK: Receives the refund and rent; must be prayer.requester
     #[account(mut)]
-    pub requester: Signer<'info>,
+    pub requester: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Writable so withheld Token-2022 fees can be harvested before the escrow closes
+    #[account(mut)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", prayer.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = requester,
+        token::token_program = token_program,
+    )]
+    pub requester_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 // ── Errors ────────────────────────────────────────────────
@@ -812,7 +3912,7 @@ pub enum PrayerError {
     NameTooLong,
     #[msg("Skills exceeds 256 characters")]
     SkillsTooLong,
-    #[msg("TTL must be between 1 and 604800 seconds")]
+    #[msg("TTL must be positive and within the configured maximum")]
     InvalidTTL,
     #[msg("Prayer is not open for claims")]
     NotOpen,
@@ -838,6 +3938,112 @@ pub enum PrayerError {
     InvalidEncryptionKey,
     #[msg("Content has already been delivered to this claimer")]
     AlreadyDelivered,
-    #[msg("max_claimers must be 1-10")]
+    #[msg("max_claimers must be at least 1 and within the configured maximum")]
     InvalidMaxClaimers,
+    #[msg("Token bounty requires the mint, escrow, token account and token program")]
+    MissingTokenAccounts,
+    #[msg("Mint does not match the prayer's reward mint")]
+    MintMismatch,
+    #[msg("Payout must include every active claimer exactly once")]
+    PayoutMismatch,
+    #[msg("Claim account does not belong to this prayer")]
+    InvalidClaim,
+    #[msg("Payout recipient is not the claimer")]
+    RecipientMismatch,
+    #[msg("Only the protocol authority can perform this action")]
+    NotAuthority,
+    #[msg("At most 5 arbiters can be configured")]
+    TooManyArbiters,
+    #[msg("Signer is not a configured arbiter")]
+    NotArbiter,
+    #[msg("Prayer is not disputed")]
+    NotDisputed,
+    #[msg("Basis points must be between 0 and 10000")]
+    InvalidBasisPoints,
+    #[msg("Review window must be positive and within the configured maximum")]
+    InvalidReviewWindow,
+    #[msg("Review window has not elapsed yet")]
+    ReviewWindowOpen,
+    #[msg("Review window has elapsed")]
+    ReviewWindowElapsed,
+    #[msg("Config values must be positive")]
+    InvalidConfig,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("This instruction is paused")]
+    Paused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Agent still has live claims or unclosed prayers")]
+    AgentHasOpenWork,
+    #[msg("Release every claim before closing the prayer")]
+    ClaimsOutstanding,
+    #[msg("Only the requester or the answerer can set contribution weights")]
+    NotRequesterOrAnswerer,
+    #[msg("Weights must cover every claimer once and sum to 10000")]
+    InvalidWeights,
+    #[msg("Unknown prayer options")]
+    InvalidOptions,
+    #[msg("Not available in this prayer's mode")]
+    WrongMode,
+    #[msg("Ranking must list submitted answers once, best first, with weights summing to 10000")]
+    InvalidRanking,
+    #[msg("Answers stay open while the prayer is under review")]
+    AnswerLocked,
+    #[msg("Close every answer before closing the prayer")]
+    AnswersOutstanding,
+    #[msg("Claim is awaiting the requester's approval")]
+    ClaimPending,
+    #[msg("Claim is not pending approval")]
+    ClaimNotPending,
+    #[msg("At most 5 wallets can be invited")]
+    TooManyInvited,
+    #[msg("This prayer is restricted to invited agents")]
+    NotInvited,
+    #[msg("Claim requirements must not be negative")]
+    InvalidRequirements,
+    #[msg("Agent reputation is below this prayer's minimum")]
+    ReputationTooLow,
+    #[msg("Agent has fewer confirmed prayers than this prayer requires")]
+    TooFewConfirmed,
+    #[msg("Agent is too new to claim this prayer")]
+    AgentTooNew,
+    #[msg("Claim timeout must not be negative or exceed the TTL")]
+    InvalidClaimTimeout,
+    #[msg("Claim has timed out")]
+    ClaimTimedOut,
+    #[msg("Only open or expired prayers can be expired")]
+    CannotExpire,
+    #[msg("Prayer has not expired yet")]
+    NotExpired,
+    #[msg("Content must be delivered to this claimer before it can answer")]
+    ContentNotDelivered,
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Requesters add to their own bounty with top_up_bounty")]
+    CannotFundOwn,
+    #[msg("This contribution has already voted")]
+    AlreadyVoted,
+    #[msg("Withdraw every contribution before closing the prayer")]
+    ContributionsOutstanding,
+    #[msg("The requester set these weights; only the requester can change them")]
+    WeightsLocked,
+    #[msg("Claimers can't fund the prayer they claimed")]
+    ClaimerCannotFund,
+    #[msg("Funders can't claim the prayer they funded")]
+    FunderCannotClaim,
+    #[msg("No arbiters are set to rule on disputes")]
+    NoArbiters,
+    #[msg("The arbiters can still rule on this dispute")]
+    RulingWindowOpen,
+    #[msg("The requester can still select winners")]
+    SelectionWindowOpen,
+    #[msg("The selection window has passed; settle the competition instead")]
+    SelectionWindowElapsed,
+    #[msg("Unpause everything before renouncing authority")]
+    PausedRenounce,
+    #[msg("Claim has a submitted answer and stays until the prayer resolves")]
+    ClaimAnswered,
+    #[msg("Too many claims are waiting for approval")]
+    TooManyPending,
 }
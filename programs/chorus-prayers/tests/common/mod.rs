@@ -0,0 +1,465 @@
+//! In-process SVM for the program's integration tests.
+//!
+//! Tests run the compiled program (target/deploy/chorus_prayers.so, built by
+//! `anchor build`) in litesvm next to the bundled SPL Token, Token-2022 and
+//! Associated Token programs, so every instruction goes through the real
+//! loader: accounts are created by CPI to the system program, bounties move
+//! through the token programs, and the runtime enforces its own rules
+//! (signers, ownership, rent, lamport conservation) on each transaction.
+//!
+//! Fees are paid by a separate harness payer, so a wallet's balance only moves
+//! by what the instructions themselves do with it.
+
+#![allow(dead_code)]
+
+use std::collections::{BTreeSet, HashMap};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{system_instruction, system_program};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee, ExtensionType},
+};
+use chorus_prayers::{
+    instruction, Agent, Answer, Claim, Contribution, Prayer, PrayerChain, PrayerError, PrayerType,
+    RemainderPolicy,
+};
+use chorus_prayers_client::instructions as ix;
+use chorus_prayers_client::pda::{
+    agent_pda, answer_pda, associated_token_address, claim_pda, contribution_pda,
+    prayer_chain_pda, prayer_pda, ASSOCIATED_TOKEN_PROGRAM_ID,
+};
+use chorus_prayers_client::TokenBounty;
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_instruction::error::InstructionError;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+/// Starting balance for test wallets
+pub const WALLET_LAMPORTS: u64 = 10_000_000_000;
+
+/// Clock at the start of every test
+pub const START: i64 = 1_700_000_000;
+
+/// TTL and review window used by `post`
+pub const TTL: i64 = 86_400;
+pub const REVIEW: i64 = 86_400;
+
+/// Decimals of every mint the harness creates
+pub const DECIMALS: u8 = 6;
+
+const PROGRAM_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/chorus_prayers.so"
+);
+
+pub struct Svm {
+    svm: LiteSVM,
+    /// Pays every transaction fee and funds fresh wallets
+    payer: Keypair,
+    keypairs: HashMap<Pubkey, Keypair>,
+    /// Every account a fixture created or an instruction referenced
+    known: BTreeSet<Pubkey>,
+    /// unix_timestamp returned by Clock::get
+    pub now: i64,
+    /// Initial PrayerChain authority (funded wallet)
+    pub authority: Pubkey,
+}
+
+/// The error an instruction fails with for a PrayerError
+pub fn prayer_err(err: PrayerError) -> ProgramError {
+    ProgramError::Custom(err.into())
+}
+
+pub fn rent(len: usize) -> u64 {
+    Rent::default().minimum_balance(len)
+}
+
+/// post_prayer arguments for a Knowledge prayer with the harness TTL and
+/// review window and no options
+pub fn post_args(reward_lamports: u64, max_claimers: u8) -> instruction::PostPrayer {
+    instruction::PostPrayer {
+        prayer_type: PrayerType::Knowledge,
+        content_hash: [1u8; 32],
+        reward_lamports,
+        ttl_seconds: TTL,
+        max_claimers,
+        review_seconds: REVIEW,
+        remainder_policy: RemainderPolicy::Requester,
+        options: 0,
+        invited: Vec::new(),
+        requirements: Default::default(),
+        claim_stake: 0,
+        claim_timeout_seconds: 0,
+    }
+}
+
+impl Svm {
+    /// Fresh ledger holding the system, token and chorus programs, plus a
+    /// PrayerChain initialized with the default config
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(chorus_prayers::ID, PROGRAM_SO)
+            .unwrap_or_else(|err| panic!("{PROGRAM_SO}: {err} (run `anchor build` first)"));
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000 * WALLET_LAMPORTS)
+            .unwrap();
+
+        let mut svm = Self {
+            svm,
+            payer,
+            keypairs: HashMap::new(),
+            known: BTreeSet::new(),
+            now: START,
+            authority: Pubkey::default(),
+        };
+        svm.authority = svm.wallet();
+        let authority = svm.authority;
+        svm.process(&ix::initialize(&authority)).unwrap();
+        svm
+    }
+
+    // ── Ledger ────────────────────────────────────────────
+
+    /// The account at `key`, or None if it was never created or has been closed
+    pub fn account(&self, key: &Pubkey) -> Option<Account> {
+        self.svm
+            .get_account(key)
+            .filter(|account| account.lamports > 0)
+    }
+
+    /// Overwrite an account outside any instruction, for states the
+    /// instructions can't reach on their own
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.known.insert(key);
+        self.svm.set_account(key, account).unwrap();
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).map_or(0, |account| account.lamports)
+    }
+
+    /// Transfer lamports to `key` from the harness payer
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let payer = self.payer.pubkey();
+        self.process(&system_instruction::transfer(&payer, key, lamports))
+            .unwrap();
+    }
+
+    /// Sum of every balance the tests can see (the harness payer excluded)
+    pub fn total_lamports(&self) -> u64 {
+        self.known.iter().map(|key| self.lamports(key)).sum()
+    }
+
+    /// Every live account a fixture created or an instruction referenced
+    pub fn accounts(&self) -> impl Iterator<Item = (Pubkey, Account)> + '_ {
+        self.known
+            .iter()
+            .filter_map(|key| Some((*key, self.account(key)?)))
+    }
+
+    /// A fresh system-owned wallet holding WALLET_LAMPORTS
+    pub fn wallet(&mut self) -> Pubkey {
+        let keypair = Keypair::new();
+        let key = keypair.pubkey();
+        self.keypairs.insert(key, keypair);
+        self.airdrop(&key, WALLET_LAMPORTS);
+        key
+    }
+
+    pub fn load<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.account(key).expect("account does not exist");
+        T::try_deserialize(&mut account.data.as_slice()).expect("account does not deserialize")
+    }
+
+    /// Rewrite an existing program account in place, keeping its size and lamports
+    pub fn store<T: AccountSerialize>(&mut self, key: &Pubkey, value: &T) {
+        let mut account = self.account(key).expect("account does not exist");
+        let len = account.data.len();
+        account.data.clear();
+        value.try_serialize(&mut account.data).unwrap();
+        assert!(
+            account.data.len() <= len,
+            "account grew past its allocation"
+        );
+        account.data.resize(len, 0);
+        self.set_account(*key, account);
+    }
+
+    pub fn modify<T: AccountSerialize + AccountDeserialize>(
+        &mut self,
+        key: &Pubkey,
+        f: impl FnOnce(&mut T),
+    ) {
+        let mut value = self.load::<T>(key);
+        f(&mut value);
+        self.store(key, &value);
+    }
+
+    // ── Execution ─────────────────────────────────────────
+
+    /// Run one instruction in its own transaction, signed by every signer it
+    /// names. On failure nothing but the harness payer's balance changes.
+    pub fn process(&mut self, ix: &Instruction) -> std::result::Result<(), ProgramError> {
+        self.process_all(std::slice::from_ref(ix))
+    }
+
+    /// Run several instructions atomically in one transaction
+    pub fn process_all(&mut self, ixs: &[Instruction]) -> std::result::Result<(), ProgramError> {
+        let mut clock: Clock = self.svm.get_sysvar();
+        if clock.unix_timestamp != self.now {
+            clock.unix_timestamp = self.now;
+            self.svm.set_sysvar(&clock);
+        }
+
+        let mut signers: Vec<&Keypair> = vec![&self.payer];
+        for meta in ixs.iter().flat_map(|ix| &ix.accounts) {
+            if meta.pubkey != self.payer.pubkey() {
+                self.known.insert(meta.pubkey);
+            }
+            if !meta.is_signer || signers.iter().any(|s| s.pubkey() == meta.pubkey) {
+                continue;
+            }
+            let keypair = self
+                .keypairs
+                .get(&meta.pubkey)
+                .unwrap_or_else(|| panic!("no keypair for signer {}", meta.pubkey));
+            signers.push(keypair);
+        }
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.payer.pubkey()),
+            &signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        // A fresh blockhash lets an identical transaction run again
+        self.svm.expire_blockhash();
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(failed) => match failed.err {
+                TransactionError::InstructionError(_, err) => Err(program_error(err)),
+                err => panic!("transaction failed outside any instruction: {err}"),
+            },
+        }
+    }
+}
+
+fn program_error(err: InstructionError) -> ProgramError {
+    ProgramError::try_from(err.clone())
+        .unwrap_or_else(|_| panic!("instruction failed with a runtime error: {err}"))
+}
+
+// ── Fixtures ──────────────────────────────────────────────
+
+impl Svm {
+    pub fn chain(&self) -> PrayerChain {
+        self.load(&prayer_chain_pda().0)
+    }
+
+    pub fn agent(&self, wallet: &Pubkey) -> Agent {
+        self.load(&agent_pda(wallet).0)
+    }
+
+    pub fn prayer(&self, prayer_id: u64) -> Prayer {
+        self.load(&prayer_pda(prayer_id).0)
+    }
+
+    pub fn claim(&self, prayer_id: u64, claimer: &Pubkey) -> Claim {
+        self.load(&claim_pda(prayer_id, claimer).0)
+    }
+
+    pub fn answer(&self, prayer_id: u64, answerer: &Pubkey) -> Answer {
+        self.load(&answer_pda(prayer_id, answerer).0)
+    }
+
+    pub fn contribution(&self, prayer_id: u64, funder: &Pubkey) -> Contribution {
+        self.load(&contribution_pda(prayer_id, funder).0)
+    }
+
+    pub fn register(&mut self, wallet: &Pubkey) {
+        self.process(&ix::register_agent(
+            wallet,
+            "agent".to_string(),
+            "testing".to_string(),
+            [7u8; 32],
+        ))
+        .unwrap();
+    }
+
+    /// A registered wallet
+    pub fn agent_wallet(&mut self) -> Pubkey {
+        let wallet = self.wallet();
+        self.register(&wallet);
+        wallet
+    }
+
+    /// Post a SOL prayer with `post_args`, returning its id
+    pub fn post(&mut self, requester: &Pubkey, reward_lamports: u64, max_claimers: u8) -> u64 {
+        self.post_with(requester, post_args(reward_lamports, max_claimers), None)
+    }
+
+    /// Post a prayer with the given arguments, returning its id
+    pub fn post_with(
+        &mut self,
+        requester: &Pubkey,
+        args: instruction::PostPrayer,
+        token: Option<&TokenBounty>,
+    ) -> u64 {
+        let prayer_id = self.chain().total_prayers;
+        self.process(&ix::post_prayer(requester, prayer_id, args, token))
+            .unwrap();
+        prayer_id
+    }
+
+    /// Post a single-claimer token prayer escrowing `amount` of a fresh SPL
+    /// Token mint
+    pub fn post_token(&mut self, requester: &Pubkey, amount: u64) -> (u64, TokenBounty) {
+        let token = self.token_bounty(requester, &spl_token::ID, amount);
+        let prayer_id = self.post_with(requester, post_args(amount, 1), Some(&token));
+        (prayer_id, token)
+    }
+
+    /// A fresh mint owned by `token_program`, with `amount` minted to the
+    /// requester's associated account
+    pub fn token_bounty(
+        &mut self,
+        requester: &Pubkey,
+        token_program: &Pubkey,
+        amount: u64,
+    ) -> TokenBounty {
+        let mint = self.mint(token_program);
+        let token = TokenBounty::new(mint, *token_program, requester);
+        self.token_account(requester, &mint, token_program);
+        self.mint_to(&mint, &token.requester_token_account, amount);
+        token
+    }
+
+    pub fn claim_prayer(&mut self, prayer_id: u64, claimer: &Pubkey) {
+        self.process(&ix::claim_prayer(claimer, prayer_id)).unwrap();
+    }
+
+    pub fn fund(&mut self, prayer_id: u64, funder: &Pubkey, amount: u64) {
+        self.process(&ix::fund_prayer(funder, prayer_id, amount))
+            .unwrap();
+    }
+
+    pub fn submit_answer(&mut self, prayer_id: u64, answerer: &Pubkey) {
+        self.process(&ix::submit_answer(
+            answerer,
+            prayer_id,
+            [answerer.to_bytes()[0]; 32],
+            vec![1, 2, 3],
+        ))
+        .unwrap();
+    }
+
+    // ── Tokens ────────────────────────────────────────────
+
+    /// A mint owned by `token_program` with DECIMALS decimals, the harness
+    /// payer as mint authority
+    pub fn mint(&mut self, token_program: &Pubkey) -> Pubkey {
+        let keypair = Keypair::new();
+        let mint = keypair.pubkey();
+        let payer = self.payer.pubkey();
+        self.keypairs.insert(mint, keypair);
+        let len = spl_token::state::Mint::LEN;
+        // InitializeMint2 { decimals, mint_authority, freeze_authority: None },
+        // which both token programs share
+        let mut data = vec![20, DECIMALS];
+        data.extend_from_slice(payer.as_ref());
+        data.push(0);
+        self.process_all(&[
+            system_instruction::create_account(&payer, &mint, rent(len), len as u64, token_program),
+            Instruction {
+                program_id: *token_program,
+                accounts: vec![AccountMeta::new(mint, false)],
+                data,
+            },
+        ])
+        .unwrap();
+        mint
+    }
+
+    /// A Token-2022 mint charging `fee_bps` on every transfer, withheld on
+    /// the receiving account, with DECIMALS decimals
+    pub fn fee_mint(&mut self, fee_bps: u16) -> Pubkey {
+        let keypair = Keypair::new();
+        let mint = keypair.pubkey();
+        let payer = self.payer.pubkey();
+        self.keypairs.insert(mint, keypair);
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        let token_program = spl_token_2022::ID;
+        self.process_all(&[
+            system_instruction::create_account(&payer, &mint, rent(len), len as u64, &token_program),
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_program,
+                &mint,
+                Some(&payer),
+                Some(&payer),
+                fee_bps,
+                u64::MAX,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint2(&token_program, &mint, &payer, None, DECIMALS)
+                .unwrap(),
+        ])
+        .unwrap();
+        mint
+    }
+
+    /// Create `owner`'s associated token account for `mint`
+    pub fn token_account(&mut self, owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+        let payer = self.payer.pubkey();
+        let address = associated_token_address(owner, mint, token_program);
+        self.process(&Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(address, false),
+                AccountMeta::new_readonly(*owner, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+            data: vec![1], // CreateIdempotent
+        })
+        .unwrap();
+        address
+    }
+
+    pub fn mint_to(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+        let token_program = self.account(mint).expect("mint does not exist").owner;
+        let payer = self.payer.pubkey();
+        // MintTo { amount }
+        let mut data = vec![7];
+        data.extend_from_slice(&amount.to_le_bytes());
+        self.process(&Instruction {
+            program_id: token_program,
+            accounts: vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*account, false),
+                AccountMeta::new_readonly(payer, true),
+            ],
+            data,
+        })
+        .unwrap();
+    }
+
+    /// Token balance of an SPL Token or Token-2022 account (0 once closed)
+    pub fn token_balance(&self, account: &Pubkey) -> u64 {
+        self.account(account).map_or(0, |account| {
+            u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+        })
+    }
+}
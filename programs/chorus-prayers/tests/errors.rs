@@ -0,0 +1,1875 @@
+//! Every PrayerError path, each driven through the instructions that can
+//! raise it.
+#![cfg(sbf_program_built)]
+
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, ProgramError, Pubkey};
+use chorus_prayers::{
+    instruction, ClaimRequirements, PrayerError, PrayerStatus, ProtocolConfig, OPTION_ALL,
+    OPTION_APPROVAL, OPTION_COMPETITION, OPTION_REQUIRE_DELIVERY, PAUSE_ALL, PAUSE_ANSWER, PAUSE_CLAIM, PAUSE_CONFIRM, PAUSE_DISPUTE,
+    PAUSE_POST, PAUSE_REGISTER,
+};
+use chorus_prayers_client::instructions as ix;
+use chorus_prayers_client::pda::{agent_pda, claim_pda, prayer_pda};
+use chorus_prayers_client::TokenBounty;
+use common::{post_args, prayer_err, Svm, REVIEW, TTL};
+
+/// A prayer answered by the first of `claimers` fresh agents
+fn fulfilled(svm: &mut Svm, reward: u64, claimers: usize) -> (Pubkey, u64, Vec<Pubkey>) {
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, reward, claimers as u8);
+    let claimers: Vec<Pubkey> = (0..claimers).map(|_| svm.agent_wallet()).collect();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+    }
+    svm.process(&ix::answer_prayer(
+        &claimers[0],
+        prayer_id,
+        [9u8; 32],
+        vec![1, 2, 3],
+    ))
+    .unwrap();
+    (requester, prayer_id, claimers)
+}
+
+/// A prayer `funder` chipped `amount` into before the first of `claimers`
+/// fresh agents answered it
+fn crowdfunded(svm: &mut Svm, amount: u64, claimers: usize) -> (Pubkey, u64, Vec<Pubkey>, Pubkey) {
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, claimers as u8);
+    let funder = svm.wallet();
+    svm.fund(prayer_id, &funder, amount);
+    let claimers: Vec<Pubkey> = (0..claimers).map(|_| svm.agent_wallet()).collect();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+    }
+    svm.process(&ix::answer_prayer(
+        &claimers[0],
+        prayer_id,
+        [9u8; 32],
+        vec![1, 2, 3],
+    ))
+    .unwrap();
+    (requester, prayer_id, claimers, funder)
+}
+
+/// post_prayer for the chain's next prayer id
+fn try_post(
+    svm: &mut Svm,
+    requester: &Pubkey,
+    args: instruction::PostPrayer,
+) -> Result<(), ProgramError> {
+    let prayer_id = svm.chain().total_prayers;
+    svm.process(&ix::post_prayer(requester, prayer_id, args, None))
+}
+
+fn payouts(claimers: &[Pubkey]) -> Vec<(Pubkey, Pubkey)> {
+    claimers
+        .iter()
+        .map(|claimer| (*claimer, *claimer))
+        .collect()
+}
+
+/// A competition prayer where each of `claimers` fresh agents submitted an answer
+fn competition(svm: &mut Svm, reward: u64, claimers: usize) -> (Pubkey, u64, Vec<Pubkey>) {
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION,
+            ..post_args(reward, claimers as u8)
+        },
+        None,
+    );
+    let claimers: Vec<Pubkey> = (0..claimers).map(|_| svm.agent_wallet()).collect();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+        svm.submit_answer(prayer_id, claimer);
+    }
+    (requester, prayer_id, claimers)
+}
+
+/// An approval-mode prayer with one pending applicant
+fn application(svm: &mut Svm, max_claimers: u8) -> (Pubkey, u64, Pubkey) {
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_APPROVAL,
+            ..post_args(1_000, max_claimers)
+        },
+        None,
+    );
+    let applicant = svm.agent_wallet();
+    svm.claim_prayer(prayer_id, &applicant);
+    (requester, prayer_id, applicant)
+}
+
+/// A fresh wallet made the chain's only arbiter, so disputes can be opened
+fn arbiter(svm: &mut Svm) -> Pubkey {
+    let arbiter = svm.wallet();
+    let authority = svm.authority;
+    svm.process(&ix::set_arbiters(&authority, vec![arbiter]))
+        .unwrap();
+    arbiter
+}
+
+fn disputed(svm: &mut Svm) -> (Pubkey, u64, Vec<Pubkey>, Pubkey) {
+    let (requester, prayer_id, claimers) = fulfilled(svm, 1_000, 1);
+    let arbiter = arbiter(svm);
+    svm.process(&ix::open_dispute(&requester, prayer_id, [4u8; 32]))
+        .unwrap();
+    (requester, prayer_id, claimers, arbiter)
+}
+
+// ── Agents ────────────────────────────────────────────────
+
+#[test]
+fn name_too_long() {
+    let mut svm = Svm::new();
+    let wallet = svm.agent_wallet();
+
+    let res = svm.process(&ix::update_agent(&wallet, Some("n".repeat(33)), None, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::NameTooLong)));
+}
+
+#[test]
+fn skills_too_long() {
+    let mut svm = Svm::new();
+    let wallet = svm.agent_wallet();
+
+    let res = svm.process(&ix::update_agent(
+        &wallet,
+        None,
+        Some("s".repeat(257)),
+        None,
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::SkillsTooLong)));
+}
+
+#[test]
+fn invalid_encryption_key() {
+    let mut svm = Svm::new();
+    let wallet = svm.agent_wallet();
+
+    let res = svm.process(&ix::update_agent(&wallet, None, None, Some([0u8; 32])));
+    assert_eq!(res, Err(prayer_err(PrayerError::InvalidEncryptionKey)));
+}
+
+#[test]
+fn agent_has_open_work() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 0, 1);
+    let claimer = svm.agent_wallet();
+    svm.claim_prayer(prayer_id, &claimer);
+
+    let res = svm.process(&ix::close_agent(&requester));
+    assert_eq!(res, Err(prayer_err(PrayerError::AgentHasOpenWork)));
+    let res = svm.process(&ix::close_agent(&claimer));
+    assert_eq!(res, Err(prayer_err(PrayerError::AgentHasOpenWork)));
+}
+
+// ── Authority ─────────────────────────────────────────────
+
+#[test]
+fn not_authority() {
+    let mut svm = Svm::new();
+    let stranger = svm.wallet();
+    let expected = Err(prayer_err(PrayerError::NotAuthority));
+
+    assert_eq!(
+        svm.process(&ix::propose_authority(&stranger, stranger)),
+        expected
+    );
+    assert_eq!(svm.process(&ix::renounce_authority(&stranger)), expected);
+    assert_eq!(
+        svm.process(&ix::update_config(&stranger, ProtocolConfig::default())),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::set_arbiters(&stranger, vec![stranger])),
+        expected
+    );
+    assert_eq!(svm.process(&ix::set_pause(&stranger, PAUSE_ALL)), expected);
+
+    // A renounced chain has no authority at all
+    let authority = svm.authority;
+    svm.process(&ix::renounce_authority(&authority)).unwrap();
+    assert_eq!(svm.process(&ix::set_pause(&authority, PAUSE_ALL)), expected);
+}
+
+#[test]
+fn paused_renounce() {
+    let mut svm = Svm::new();
+    let authority = svm.authority;
+
+    // Renouncing would leave the paused families frozen for good
+    svm.process(&ix::set_pause(&authority, PAUSE_CLAIM)).unwrap();
+    let res = svm.process(&ix::renounce_authority(&authority));
+    assert_eq!(res, Err(prayer_err(PrayerError::PausedRenounce)));
+
+    svm.process(&ix::set_pause(&authority, 0)).unwrap();
+    svm.process(&ix::renounce_authority(&authority)).unwrap();
+}
+
+#[test]
+fn not_pending_authority() {
+    let mut svm = Svm::new();
+    let authority = svm.authority;
+    let successor = svm.wallet();
+    let stranger = svm.wallet();
+    let expected = Err(prayer_err(PrayerError::NotPendingAuthority));
+
+    // Nothing proposed yet
+    assert_eq!(svm.process(&ix::accept_authority(&successor)), expected);
+
+    svm.process(&ix::propose_authority(&authority, successor))
+        .unwrap();
+    assert_eq!(svm.process(&ix::accept_authority(&stranger)), expected);
+
+    svm.process(&ix::accept_authority(&successor)).unwrap();
+    assert_eq!(svm.chain().authority, successor);
+}
+
+#[test]
+fn invalid_config() {
+    let mut svm = Svm::new();
+    let authority = svm.authority;
+    let expected = Err(prayer_err(PrayerError::InvalidConfig));
+
+    for config in [
+        ProtocolConfig {
+            claim_timeout_seconds: 0,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            max_claimers: 0,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            max_ttl_seconds: 0,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            max_review_seconds: -1,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            dispute_timeout_seconds: 0,
+            ..Default::default()
+        },
+        // Large enough to overflow the deadlines computed from them
+        ProtocolConfig {
+            max_ttl_seconds: i64::MAX,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            max_review_seconds: i64::MAX,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            claim_timeout_seconds: 31_536_001,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            max_claimers: 21,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            answer_reputation: 1_001,
+            ..Default::default()
+        },
+        ProtocolConfig {
+            confirm_reputation: u64::MAX,
+            ..Default::default()
+        },
+    ] {
+        assert_eq!(
+            svm.process(&ix::update_config(&authority, config)),
+            expected
+        );
+    }
+}
+
+#[test]
+fn too_many_arbiters() {
+    let mut svm = Svm::new();
+    let authority = svm.authority;
+    let arbiters: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+
+    let res = svm.process(&ix::set_arbiters(&authority, arbiters.clone()));
+    assert_eq!(res, Err(prayer_err(PrayerError::TooManyArbiters)));
+    svm.process(&ix::set_arbiters(&authority, arbiters[..5].to_vec()))
+        .unwrap();
+}
+
+#[test]
+fn invalid_pause_flags() {
+    let mut svm = Svm::new();
+    let authority = svm.authority;
+
+    let res = svm.process(&ix::set_pause(&authority, PAUSE_ALL + 1));
+    assert_eq!(res, Err(prayer_err(PrayerError::InvalidPauseFlags)));
+}
+
+#[test]
+fn paused() {
+    let mut svm = Svm::new();
+    let authority = svm.authority;
+    let (requester, prayer_id, claimers, funder) = crowdfunded(&mut svm, 500, 1);
+    let open_id = svm.post(&requester, 0, 2);
+    let (applied_to, apply_id, applicant) = application(&mut svm, 1);
+    let (swept_from, sweep_id, swept) = application(&mut svm, 1);
+    let claimer = claimers[0];
+    svm.fund(open_id, &funder, 500);
+    let contest_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION,
+            ..post_args(1_000, 2)
+        },
+        None,
+    );
+    let contestant = svm.agent_wallet();
+    svm.claim_prayer(contest_id, &contestant);
+    let newcomer = svm.wallet();
+    let agent = svm.agent_wallet();
+    let expected = Err(prayer_err(PrayerError::Paused));
+    let next_id = svm.chain().total_prayers;
+
+    let cases = [
+        (
+            PAUSE_REGISTER,
+            ix::register_agent(&newcomer, "n".to_string(), "s".to_string(), [7u8; 32]),
+        ),
+        (
+            PAUSE_REGISTER,
+            ix::update_agent(&claimer, None, None, Some([2u8; 32])),
+        ),
+        (
+            PAUSE_POST,
+            ix::post_prayer(&requester, next_id, post_args(1_000, 1), None),
+        ),
+        (PAUSE_POST, ix::fund_prayer(&agent, open_id, 500)),
+        (PAUSE_CLAIM, ix::claim_prayer(&agent, open_id)),
+        (
+            PAUSE_ANSWER,
+            ix::submit_answer(&contestant, contest_id, [0u8; 32], vec![]),
+        ),
+        (PAUSE_POST, ix::extend_prayer(&requester, open_id, 60)),
+        (PAUSE_POST, ix::top_up_bounty(&requester, open_id, 1, None)),
+        (
+            PAUSE_CLAIM,
+            ix::approve_claim(&applied_to, apply_id, &applicant),
+        ),
+        (
+            PAUSE_ANSWER,
+            ix::deliver_content(&requester, prayer_id, &claimer, vec![1]),
+        ),
+        (
+            PAUSE_ANSWER,
+            ix::answer_prayer(&claimer, prayer_id, [0u8; 32], vec![]),
+        ),
+        (
+            PAUSE_CONFIRM,
+            ix::set_claim_weights(&requester, prayer_id, &[(claimer, 10_000)]),
+        ),
+        (
+            PAUSE_CONFIRM,
+            ix::confirm_prayer(&requester, prayer_id, &claimer, None, &payouts(&claimers)),
+        ),
+        (
+            PAUSE_CONFIRM,
+            ix::finalize_prayer(&claimer, prayer_id, &claimer, None, &payouts(&claimers)),
+        ),
+        (PAUSE_CONFIRM, ix::vote_confirm(&funder, prayer_id)),
+        (
+            PAUSE_DISPUTE,
+            ix::open_dispute(&requester, prayer_id, [0u8; 32]),
+        ),
+        (
+            PAUSE_DISPUTE,
+            ix::resolve_dispute(&authority, prayer_id, &requester, &claimer, 0, None, &[]),
+        ),
+    ];
+
+    for (family, instruction) in &cases {
+        svm.process(&ix::set_pause(&authority, *family)).unwrap();
+        assert_eq!(svm.process(instruction), expected);
+    }
+
+    // Refund paths stay available with everything paused
+    svm.process(&ix::set_pause(&authority, PAUSE_ALL)).unwrap();
+    svm.process(&ix::cancel_prayer(&requester, open_id, None))
+        .unwrap();
+    svm.process(&ix::withdraw_contribution(&funder, open_id, &funder))
+        .unwrap();
+    svm.process(&ix::close_prayer(&requester, open_id, None))
+        .unwrap();
+    svm.process(&ix::reject_claim(&applied_to, apply_id, &applicant))
+        .unwrap();
+    svm.process(&ix::cancel_prayer(&swept_from, sweep_id, None))
+        .unwrap();
+    svm.process(&ix::sweep_claims(&claimer, sweep_id, &[swept]))
+        .unwrap();
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&claimer, &applied_to, apply_id, None))
+        .unwrap();
+}
+
+// ── Prayers ───────────────────────────────────────────────
+
+#[test]
+fn not_claimed() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 2);
+    let expected = Err(prayer_err(PrayerError::NotClaimed));
+
+    assert_eq!(
+        svm.process(&ix::deliver_content(
+            &requester,
+            prayer_id,
+            &claimers[1],
+            vec![1]
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::answer_prayer(
+            &claimers[1],
+            prayer_id,
+            [0u8; 32],
+            vec![]
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::unclaim_prayer(&claimers[1], prayer_id, &claimers[1])),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::heartbeat_claim(&claimers[1], prayer_id)),
+        expected
+    );
+}
+
+#[test]
+fn not_fulfilled() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    arbiter(&mut svm);
+    let expected = Err(prayer_err(PrayerError::NotFulfilled));
+
+    // answerer_agent is derived from prayer.answerer, so point it at the claimer
+    svm.modify(
+        &prayer_pda(prayer_id).0,
+        |p: &mut chorus_prayers::Prayer| p.answerer = claimer,
+    );
+
+    let payouts = payouts(&[claimer]);
+    assert_eq!(
+        svm.process(&ix::confirm_prayer(
+            &requester, prayer_id, &claimer, None, &payouts
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::finalize_prayer(
+            &claimer, prayer_id, &claimer, None, &payouts
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32])),
+        expected
+    );
+    let funder = svm.wallet();
+    svm.fund(prayer_id, &funder, 500);
+    assert_eq!(
+        svm.process(&ix::vote_confirm(&funder, prayer_id)),
+        expected
+    );
+}
+
+#[test]
+fn expired() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+
+    let (applied_to, apply_id, applicant) = application(&mut svm, 1);
+
+    svm.now += TTL;
+    let res = svm.process(&ix::answer_prayer(&claimer, prayer_id, [0u8; 32], vec![]));
+    assert_eq!(res, Err(prayer_err(PrayerError::Expired)));
+    let res = svm.process(&ix::deliver_content(&requester, prayer_id, &claimer, vec![1]));
+    assert_eq!(res, Err(prayer_err(PrayerError::Expired)));
+    let res = svm.process(&ix::approve_claim(&applied_to, apply_id, &applicant));
+    assert_eq!(res, Err(prayer_err(PrayerError::Expired)));
+    let res = svm.process(&ix::heartbeat_claim(&claimer, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::Expired)));
+    let res = svm.process(&ix::extend_prayer(&requester, prayer_id, 60));
+    assert_eq!(res, Err(prayer_err(PrayerError::Expired)));
+    let res = svm.process(&ix::top_up_bounty(&requester, prayer_id, 1, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::Expired)));
+}
+
+#[test]
+fn not_claimer() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let stranger = svm.wallet();
+    let prayer_id = svm.post(&requester, 1_000, 2);
+    svm.claim_prayer(prayer_id, &claimer);
+    let expected = Err(prayer_err(PrayerError::NotClaimer));
+
+    // Someone else's live claim
+    assert_eq!(
+        svm.process(&ix::unclaim_prayer(&stranger, prayer_id, &claimer)),
+        expected
+    );
+
+    // Rent must go back to the claimer's own wallet
+    let mut unclaim = ix::unclaim_prayer(&claimer, prayer_id, &claimer);
+    unclaim.accounts[4].pubkey = stranger;
+    assert_eq!(svm.process(&unclaim), expected);
+
+    // Anyone can release a claim once it times out
+    svm.now += svm.chain().config.claim_timeout_seconds + 1;
+    svm.process(&ix::unclaim_prayer(&stranger, prayer_id, &claimer))
+        .unwrap();
+
+    // A sweep must refund each claim to its own claimer and agent
+    let (requester, prayer_id, applicant) = application(&mut svm, 1);
+    svm.process(&ix::cancel_prayer(&requester, prayer_id, None))
+        .unwrap();
+    let mut sweep = ix::sweep_claims(&stranger, prayer_id, &[applicant]);
+    sweep.accounts[4].pubkey = stranger;
+    assert_eq!(svm.process(&sweep), expected);
+    let mut sweep = ix::sweep_claims(&stranger, prayer_id, &[applicant]);
+    sweep.accounts[3].pubkey = agent_pda(&claimer).0;
+    assert_eq!(svm.process(&sweep), expected);
+
+    // Only the answerer can withdraw an answer before the prayer resolves,
+    // even once it has expired
+    let (_, prayer_id, claimers) = competition(&mut svm, 1_000, 1);
+    svm.now += TTL + 1;
+    assert_eq!(
+        svm.process(&ix::close_answer(&stranger, prayer_id, &claimers[0])),
+        expected
+    );
+}
+
+#[test]
+fn claim_timed_out() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let stranger = svm.wallet();
+    let timeout = TTL / 2;
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            claim_timeout_seconds: timeout,
+            ..post_args(1_000, 1)
+        },
+        None,
+    );
+    svm.claim_prayer(prayer_id, &claimer);
+
+    // A heartbeat before the deadline restarts the timeout
+    svm.now += timeout;
+    svm.process(&ix::heartbeat_claim(&claimer, prayer_id))
+        .unwrap();
+    assert_eq!(svm.claim(prayer_id, &claimer).last_heartbeat, svm.now);
+    svm.now += 1;
+    let res = svm.process(&ix::unclaim_prayer(&stranger, prayer_id, &claimer));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotClaimer)));
+
+    // ... but never past expires_at
+    let expires_at = svm.prayer(prayer_id).expires_at;
+    svm.now = expires_at - 1;
+    svm.process(&ix::heartbeat_claim(&claimer, prayer_id))
+        .unwrap();
+    let prayer = svm.prayer(prayer_id);
+    assert_eq!(svm.claim(prayer_id, &claimer).deadline(&prayer), expires_at);
+
+    // A lapsed claim can't be revived
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    svm.now += svm.prayer(prayer_id).claim_timeout_seconds + 1;
+    let res = svm.process(&ix::heartbeat_claim(&claimer, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::ClaimTimedOut)));
+}
+
+#[test]
+fn not_requester() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 1);
+    let open_id = svm.post(&requester, 1_000, 1);
+    let (_, apply_id, applicant) = application(&mut svm, 1);
+    let stranger = svm.agent_wallet();
+    let arbiter = arbiter(&mut svm);
+    let expected = Err(prayer_err(PrayerError::NotRequester));
+
+    assert_eq!(
+        svm.process(&ix::approve_claim(&stranger, apply_id, &applicant)),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::reject_claim(&stranger, apply_id, &applicant)),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::deliver_content(
+            &stranger,
+            prayer_id,
+            &claimers[0],
+            vec![1]
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::confirm_prayer(
+            &stranger,
+            prayer_id,
+            &claimers[0],
+            None,
+            &payouts(&claimers)
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::open_dispute(&stranger, prayer_id, [0u8; 32])),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::cancel_prayer(&stranger, open_id, None)),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::extend_prayer(&stranger, open_id, 60)),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::top_up_bounty(&stranger, open_id, 1, None)),
+        expected
+    );
+    // Without a Contribution, only the requester has a vote
+    assert_eq!(
+        svm.process(&ix::vote_confirm_requester(&stranger, prayer_id)),
+        expected
+    );
+
+    svm.process(&ix::cancel_prayer(&requester, open_id, None))
+        .unwrap();
+    assert_eq!(
+        svm.process(&ix::close_prayer(&stranger, open_id, None)),
+        expected
+    );
+
+    // resolve_dispute and expire_dispute refund the prayer's requester, not
+    // an arbitrary account
+    svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]))
+        .unwrap();
+    assert_eq!(
+        svm.process(&ix::expire_dispute(&stranger, &stranger, prayer_id, None)),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::resolve_dispute(
+            &arbiter,
+            prayer_id,
+            &stranger,
+            &claimers[0],
+            0,
+            None,
+            &[]
+        )),
+        expected
+    );
+
+    // expire_prayer refunds the prayer's requester, not the keeper's pick
+    let expire_id = svm.post(&requester, 1_000, 1);
+    svm.now += TTL + 1;
+    assert_eq!(
+        svm.process(&ix::expire_prayer(&stranger, &stranger, expire_id, None)),
+        expected
+    );
+}
+
+#[test]
+fn has_claimers() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 2);
+    svm.claim_prayer(prayer_id, &claimer);
+
+    let res = svm.process(&ix::cancel_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::HasClaimers)));
+}
+
+#[test]
+fn cannot_cancel() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, _) = fulfilled(&mut svm, 1_000, 1);
+
+    let res = svm.process(&ix::cancel_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::CannotCancel)));
+}
+
+#[test]
+fn cannot_close() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 1);
+    let answerer = claimers[0];
+    let open_id = svm.post(&requester, 1_000, 1);
+    let expected = Err(prayer_err(PrayerError::CannotClose));
+
+    assert_eq!(
+        svm.process(&ix::close_prayer(&requester, open_id, None)),
+        expected
+    );
+    // Claims on an unresolved prayer can't be swept, nor contributions withdrawn
+    assert_eq!(
+        svm.process(&ix::sweep_claims(&requester, prayer_id, &[answerer])),
+        expected
+    );
+    let funder = svm.wallet();
+    svm.fund(open_id, &funder, 500);
+    assert_eq!(
+        svm.process(&ix::withdraw_contribution(&funder, open_id, &funder)),
+        expected
+    );
+    // Fulfilled prayers can't be closed even after the TTL
+    svm.now += TTL + 1;
+    assert_eq!(
+        svm.process(&ix::close_prayer(&requester, prayer_id, None)),
+        expected
+    );
+}
+
+#[test]
+fn claims_outstanding() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 1);
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    let res = svm.process(&ix::close_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::ClaimsOutstanding)));
+
+    // Pending claims count too
+    let (requester, prayer_id, _) = application(&mut svm, 1);
+    svm.process(&ix::cancel_prayer(&requester, prayer_id, None))
+        .unwrap();
+    let res = svm.process(&ix::close_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::ClaimsOutstanding)));
+
+    // An expired prayer waits for its claims before expire_prayer can close it
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&claimer, &requester, prayer_id, None))
+        .unwrap();
+    let res = svm.process(&ix::expire_prayer(&claimer, &requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::ClaimsOutstanding)));
+}
+
+#[test]
+fn contributions_outstanding() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let funder = svm.wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.fund(prayer_id, &funder, 500);
+    svm.process(&ix::cancel_prayer(&requester, prayer_id, None))
+        .unwrap();
+
+    let res = svm.process(&ix::close_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::ContributionsOutstanding)));
+
+    // An expired prayer waits for its funders before expire_prayer can close it
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.fund(prayer_id, &funder, 500);
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&funder, &requester, prayer_id, None))
+        .unwrap();
+    let res = svm.process(&ix::expire_prayer(&funder, &requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::ContributionsOutstanding)));
+}
+
+#[test]
+fn cannot_expire() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, _) = fulfilled(&mut svm, 1_000, 1);
+    let keeper = svm.wallet();
+
+    // An answered prayer is settled by confirm or finalize, never by expiry
+    svm.now += TTL + 1;
+    let res = svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::CannotExpire)));
+}
+
+#[test]
+fn not_expired() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let keeper = svm.wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+
+    svm.now += TTL;
+    let res = svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotExpired)));
+}
+
+#[test]
+fn invalid_ttl() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 0, 1);
+    let max_ttl = svm.chain().config.max_ttl_seconds;
+    let expected = Err(prayer_err(PrayerError::InvalidTTL));
+
+    for ttl_seconds in [0, -1, max_ttl + 1] {
+        let args = instruction::PostPrayer {
+            ttl_seconds,
+            ..post_args(0, 1)
+        };
+        assert_eq!(try_post(&mut svm, &requester, args), expected);
+    }
+
+    assert_eq!(svm.process(&ix::extend_prayer(&requester, prayer_id, 0)), expected);
+    assert_eq!(svm.process(&ix::extend_prayer(&requester, prayer_id, -1)), expected);
+
+    // Up to max_ttl_seconds from now, and not a second more
+    svm.process(&ix::extend_prayer(&requester, prayer_id, max_ttl - TTL))
+        .unwrap();
+    assert_eq!(svm.prayer(prayer_id).expires_at, svm.now + max_ttl);
+    assert_eq!(svm.process(&ix::extend_prayer(&requester, prayer_id, 1)), expected);
+    assert_eq!(
+        svm.process(&ix::extend_prayer(&requester, prayer_id, i64::MAX)),
+        expected
+    );
+}
+
+#[test]
+fn invalid_max_claimers() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let max_claimers = svm.chain().config.max_claimers;
+
+    for max_claimers in [0, max_claimers + 1] {
+        let res = try_post(&mut svm, &requester, post_args(0, max_claimers));
+        assert_eq!(res, Err(prayer_err(PrayerError::InvalidMaxClaimers)));
+    }
+    try_post(&mut svm, &requester, post_args(0, max_claimers)).unwrap();
+}
+
+#[test]
+fn invalid_review_window() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let max_review = svm.chain().config.max_review_seconds;
+
+    for review_seconds in [0, -1, max_review + 1] {
+        let args = instruction::PostPrayer {
+            review_seconds,
+            ..post_args(0, 1)
+        };
+        let res = try_post(&mut svm, &requester, args);
+        assert_eq!(res, Err(prayer_err(PrayerError::InvalidReviewWindow)));
+    }
+}
+
+#[test]
+fn invalid_options() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+
+    let args = instruction::PostPrayer {
+        options: OPTION_ALL + 1,
+        ..post_args(0, 1)
+    };
+    let res = try_post(&mut svm, &requester, args);
+    assert_eq!(res, Err(prayer_err(PrayerError::InvalidOptions)));
+}
+
+#[test]
+fn too_many_invited() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let invited: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+
+    let args = instruction::PostPrayer {
+        invited: invited.clone(),
+        ..post_args(0, 1)
+    };
+    let res = try_post(&mut svm, &requester, args);
+    assert_eq!(res, Err(prayer_err(PrayerError::TooManyInvited)));
+
+    let args = instruction::PostPrayer {
+        invited: invited[..5].to_vec(),
+        ..post_args(0, 1)
+    };
+    try_post(&mut svm, &requester, args).unwrap();
+}
+
+#[test]
+fn invalid_requirements() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+
+    let args = instruction::PostPrayer {
+        requirements: ClaimRequirements {
+            min_agent_age_seconds: -1,
+            ..Default::default()
+        },
+        ..post_args(0, 1)
+    };
+    let res = try_post(&mut svm, &requester, args);
+    assert_eq!(res, Err(prayer_err(PrayerError::InvalidRequirements)));
+}
+
+#[test]
+fn invalid_claim_timeout() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+
+    // Zero takes the chain default; anything else must fit in the TTL
+    for claim_timeout_seconds in [-1, TTL + 1] {
+        let args = instruction::PostPrayer {
+            claim_timeout_seconds,
+            ..post_args(0, 1)
+        };
+        let res = try_post(&mut svm, &requester, args);
+        assert_eq!(res, Err(prayer_err(PrayerError::InvalidClaimTimeout)));
+    }
+
+    // The default is capped at the TTL too
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            ttl_seconds: 600,
+            ..post_args(0, 1)
+        },
+        None,
+    );
+    assert_eq!(svm.prayer(prayer_id).claim_timeout_seconds, 600);
+}
+
+#[test]
+fn cannot_claim_own() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+
+    let res = svm.process(&ix::claim_prayer(&requester, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::CannotClaimOwn)));
+}
+
+#[test]
+fn not_invited() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let guest = svm.agent_wallet();
+    let stranger = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            invited: vec![guest],
+            ..post_args(1_000, 2)
+        },
+        None,
+    );
+
+    let res = svm.process(&ix::claim_prayer(&stranger, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotInvited)));
+    svm.claim_prayer(prayer_id, &guest);
+}
+
+/// A prayer open to agents meeting `requirements`
+fn gated(svm: &mut Svm, requirements: ClaimRequirements) -> u64 {
+    let requester = svm.agent_wallet();
+    svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            requirements,
+            ..post_args(1_000, 1)
+        },
+        None,
+    )
+}
+
+#[test]
+fn reputation_too_low() {
+    let mut svm = Svm::new();
+    let (_, _, claimers) = fulfilled(&mut svm, 0, 1);
+    let veteran = claimers[0];
+    let newcomer = svm.agent_wallet();
+    let reputation = svm.agent(&veteran).reputation;
+    let prayer_id = gated(
+        &mut svm,
+        ClaimRequirements {
+            min_reputation: reputation,
+            ..Default::default()
+        },
+    );
+
+    let res = svm.process(&ix::claim_prayer(&newcomer, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::ReputationTooLow)));
+    svm.claim_prayer(prayer_id, &veteran);
+}
+
+#[test]
+fn too_few_confirmed() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 0, 1);
+    let answerer = claimers[0];
+    let gate = ClaimRequirements {
+        min_confirmed: 1,
+        ..Default::default()
+    };
+    let gated_id = gated(&mut svm, gate);
+
+    // Answering alone doesn't count; the requester has to confirm
+    let res = svm.process(&ix::claim_prayer(&answerer, gated_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::TooFewConfirmed)));
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &answerer,
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+    svm.claim_prayer(gated_id, &answerer);
+}
+
+#[test]
+fn agent_too_new() {
+    let mut svm = Svm::new();
+    let agent = svm.agent_wallet();
+    let prayer_id = gated(
+        &mut svm,
+        ClaimRequirements {
+            min_agent_age_seconds: 60,
+            ..Default::default()
+        },
+    );
+
+    svm.now += 59;
+    let res = svm.process(&ix::claim_prayer(&agent, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::AgentTooNew)));
+    svm.now += 1;
+    svm.claim_prayer(prayer_id, &agent);
+}
+
+#[test]
+fn cannot_fund_own() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+
+    let res = svm.process(&ix::fund_prayer(&requester, prayer_id, 500));
+    assert_eq!(res, Err(prayer_err(PrayerError::CannotFundOwn)));
+}
+
+#[test]
+fn claimer_cannot_fund() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 2);
+    svm.claim_prayer(prayer_id, &claimer);
+
+    let res = svm.process(&ix::fund_prayer(&claimer, prayer_id, 500));
+    assert_eq!(res, Err(prayer_err(PrayerError::ClaimerCannotFund)));
+
+    // Released claims don't count
+    svm.process(&ix::unclaim_prayer(&claimer, prayer_id, &claimer))
+        .unwrap();
+    svm.fund(prayer_id, &claimer, 500);
+}
+
+#[test]
+fn funder_cannot_claim() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let funder = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 2);
+    svm.fund(prayer_id, &funder, 500);
+
+    let res = svm.process(&ix::claim_prayer(&funder, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::FunderCannotClaim)));
+}
+
+#[test]
+fn invalid_amount() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+
+    let res = svm.process(&ix::top_up_bounty(&requester, prayer_id, 0, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::InvalidAmount)));
+}
+
+#[test]
+fn already_delivered() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 0, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+
+    svm.process(&ix::deliver_content(
+        &requester,
+        prayer_id,
+        &claimer,
+        vec![1],
+    ))
+    .unwrap();
+    let res = svm.process(&ix::deliver_content(
+        &requester,
+        prayer_id,
+        &claimer,
+        vec![1],
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::AlreadyDelivered)));
+}
+
+#[test]
+fn content_not_delivered() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_REQUIRE_DELIVERY,
+            ..post_args(0, 1)
+        },
+        None,
+    );
+    svm.claim_prayer(prayer_id, &claimer);
+
+    let res = svm.process(&ix::answer_prayer(&claimer, prayer_id, [9u8; 32], vec![]));
+    assert_eq!(res, Err(prayer_err(PrayerError::ContentNotDelivered)));
+
+    svm.process(&ix::deliver_content(
+        &requester,
+        prayer_id,
+        &claimer,
+        vec![1],
+    ))
+    .unwrap();
+    svm.process(&ix::answer_prayer(&claimer, prayer_id, [9u8; 32], vec![]))
+        .unwrap();
+
+    // Competition entries as well
+    let contest_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION | OPTION_REQUIRE_DELIVERY,
+            ..post_args(0, 1)
+        },
+        None,
+    );
+    svm.claim_prayer(contest_id, &claimer);
+    let res = svm.process(&ix::submit_answer(&claimer, contest_id, [9u8; 32], vec![]));
+    assert_eq!(res, Err(prayer_err(PrayerError::ContentNotDelivered)));
+    svm.process(&ix::deliver_content(
+        &requester,
+        contest_id,
+        &claimer,
+        vec![1],
+    ))
+    .unwrap();
+    svm.submit_answer(contest_id, &claimer);
+}
+
+#[test]
+fn review_window_open() {
+    let mut svm = Svm::new();
+    let (_, prayer_id, claimers, funder) = crowdfunded(&mut svm, 1_000, 1);
+
+    svm.now += REVIEW;
+    let res = svm.process(&ix::finalize_prayer(
+        &claimers[0],
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::ReviewWindowOpen)));
+
+    // Half the bounty isn't a majority
+    svm.process(&ix::vote_confirm(&funder, prayer_id)).unwrap();
+    let res = svm.process(&ix::finalize_prayer(
+        &claimers[0],
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::ReviewWindowOpen)));
+
+    // Funders holding most of the bounty still leave the requester the first
+    // half of the window to dispute
+    let (_, prayer_id, claimers, funder) = crowdfunded(&mut svm, 2_000, 1);
+    svm.process(&ix::vote_confirm(&funder, prayer_id)).unwrap();
+    svm.now = svm.prayer(prayer_id).funder_vote_deadline();
+    let res = svm.process(&ix::finalize_prayer(
+        &claimers[0],
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::ReviewWindowOpen)));
+}
+
+#[test]
+fn review_window_elapsed() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, _) = fulfilled(&mut svm, 1_000, 1);
+    arbiter(&mut svm);
+
+    svm.now += REVIEW + 1;
+    let res = svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]));
+    assert_eq!(res, Err(prayer_err(PrayerError::ReviewWindowElapsed)));
+}
+
+#[test]
+fn already_voted() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, _, funder) = crowdfunded(&mut svm, 100, 1);
+
+    svm.process(&ix::vote_confirm(&funder, prayer_id)).unwrap();
+    let res = svm.process(&ix::vote_confirm(&funder, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::AlreadyVoted)));
+
+    svm.process(&ix::vote_confirm_requester(&requester, prayer_id))
+        .unwrap();
+    let res = svm.process(&ix::vote_confirm_requester(&requester, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::AlreadyVoted)));
+}
+
+// ── Payouts ───────────────────────────────────────────────
+
+#[test]
+fn payout_mismatch() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 2);
+    let expected = Err(prayer_err(PrayerError::PayoutMismatch));
+
+    // Missing a claimer
+    assert_eq!(
+        svm.process(&ix::confirm_prayer(
+            &requester,
+            prayer_id,
+            &claimers[0],
+            None,
+            &payouts(&claimers[..1])
+        )),
+        expected
+    );
+    // Paying one claimer twice
+    let twice = payouts(&[claimers[0], claimers[0]]);
+    assert_eq!(
+        svm.process(&ix::confirm_prayer(
+            &requester,
+            prayer_id,
+            &claimers[0],
+            None,
+            &twice
+        )),
+        expected
+    );
+}
+
+#[test]
+fn invalid_claim() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 1);
+    let other_id = svm.post(&requester, 0, 1);
+    svm.claim_prayer(other_id, &claimers[0]);
+    let expected = Err(prayer_err(PrayerError::InvalidClaim));
+
+    // A claim on another prayer
+    let mut confirm = ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    );
+    confirm.accounts[7].pubkey = claim_pda(other_id, &claimers[0]).0;
+    assert_eq!(svm.process(&confirm), expected);
+
+    // An account the program doesn't own
+    confirm.accounts[7].pubkey = claimers[0];
+    assert_eq!(svm.process(&confirm), expected);
+
+    // sweep_claims needs whole [claim, agent, wallet] triples
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+    let mut sweep = ix::sweep_claims(&requester, prayer_id, &claimers);
+    sweep.accounts.pop();
+    assert_eq!(svm.process(&sweep), expected);
+    assert_eq!(
+        svm.process(&ix::sweep_claims(&requester, prayer_id, &[])),
+        expected
+    );
+    let mut sweep = ix::sweep_claims(&requester, prayer_id, &claimers);
+    sweep.accounts[2].pubkey = claim_pda(other_id, &claimers[0]).0;
+    assert_eq!(svm.process(&sweep), expected);
+}
+
+#[test]
+fn recipient_mismatch() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 1);
+
+    let res = svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &[(claimers[0], requester)],
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::RecipientMismatch)));
+}
+
+#[test]
+fn missing_token_accounts() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let (prayer_id, token) = svm.post_token(&requester, 1_000);
+
+    let res = svm.process(&ix::cancel_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::MissingTokenAccounts)));
+    let res = svm.process(&ix::top_up_bounty(&requester, prayer_id, 1, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::MissingTokenAccounts)));
+
+    // A mint with nothing to fund the escrow from, even for an empty bounty
+    let next_id = svm.chain().total_prayers;
+    for reward in [1_000, 0] {
+        let mut post = ix::post_prayer(&requester, next_id, post_args(reward, 1), Some(&token));
+        post.accounts[6] = AccountMeta::new_readonly(chorus_prayers::ID, false);
+        assert_eq!(
+            svm.process(&post),
+            Err(prayer_err(PrayerError::MissingTokenAccounts))
+        );
+    }
+}
+
+#[test]
+fn mint_mismatch() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let (prayer_id, token) = svm.post_token(&requester, 1_000);
+
+    // A consistent set of token accounts, but for a different mint
+    let other = svm.mint(&token.token_program);
+    let wrong = TokenBounty::new(other, token.token_program, &requester);
+    svm.token_account(&requester, &other, &token.token_program);
+
+    let res = svm.process(&ix::cancel_prayer(&requester, prayer_id, Some(&wrong)));
+    assert_eq!(res, Err(prayer_err(PrayerError::MintMismatch)));
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Open);
+}
+
+#[test]
+fn not_requester_or_answerer() {
+    let mut svm = Svm::new();
+    let (_, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 2);
+
+    // A co-claimer who didn't submit the answer
+    let res = svm.process(&ix::set_claim_weights(
+        &claimers[1],
+        prayer_id,
+        &[(claimers[0], 5_000), (claimers[1], 5_000)],
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotRequesterOrAnswerer)));
+}
+
+#[test]
+fn invalid_weights() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 2);
+    let expected = Err(prayer_err(PrayerError::InvalidWeights));
+
+    let cases = [
+        // Doesn't sum to 10_000
+        vec![(claimers[0], 5_000), (claimers[1], 4_999)],
+        // Missing a claimer
+        vec![(claimers[0], 10_000)],
+        // One claimer twice
+        vec![(claimers[0], 5_000), (claimers[0], 5_000)],
+    ];
+    for weights in &cases {
+        assert_eq!(
+            svm.process(&ix::set_claim_weights(&requester, prayer_id, weights)),
+            expected
+        );
+    }
+    assert!(!svm.prayer(prayer_id).weighted);
+}
+
+#[test]
+fn weights_locked() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 2);
+    let even = [(claimers[0], 5_000), (claimers[1], 5_000)];
+    let skewed = [(claimers[0], 9_000), (claimers[1], 1_000)];
+
+    // The answerer's weights are a proposal the requester can replace...
+    svm.process(&ix::set_claim_weights(&claimers[0], prayer_id, &skewed))
+        .unwrap();
+    svm.process(&ix::set_claim_weights(&requester, prayer_id, &even))
+        .unwrap();
+
+    // ... but the requester's can't be replaced by the answerer
+    let res = svm.process(&ix::set_claim_weights(&claimers[0], prayer_id, &skewed));
+    assert_eq!(res, Err(prayer_err(PrayerError::WeightsLocked)));
+    assert_eq!(svm.claim(prayer_id, &claimers[0]).weight_bps, 5_000);
+
+    // The requester can still revise them
+    svm.process(&ix::set_claim_weights(&requester, prayer_id, &skewed))
+        .unwrap();
+    assert_eq!(svm.claim(prayer_id, &claimers[0]).weight_bps, 9_000);
+}
+
+// ── Competitions ──────────────────────────────────────────
+
+#[test]
+fn wrong_mode() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 2);
+    let expected = Err(prayer_err(PrayerError::WrongMode));
+
+    // Competition prayers are fulfilled by ranking, not by a single answer
+    assert_eq!(
+        svm.process(&ix::answer_prayer(
+            &claimers[0],
+            prayer_id,
+            [0u8; 32],
+            vec![]
+        )),
+        expected
+    );
+
+    // ... and their payout weights come from the ranking
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[0], 10_000)],
+    ))
+    .unwrap();
+    assert_eq!(
+        svm.process(&ix::set_claim_weights(
+            &requester,
+            prayer_id,
+            &[(claimers[0], 5_000), (claimers[1], 5_000)],
+        )),
+        expected
+    );
+
+    // Only competition prayers can be ranked
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    let claimers = [svm.agent_wallet()];
+    svm.claim_prayer(prayer_id, &claimers[0]);
+    assert_eq!(
+        svm.process(&ix::select_winners(
+            &requester,
+            prayer_id,
+            &[(claimers[0], 10_000)],
+        )),
+        expected
+    );
+}
+
+#[test]
+fn invalid_ranking() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION,
+            ..post_args(1_000, 3)
+        },
+        None,
+    );
+    let claimers = [svm.agent_wallet(), svm.agent_wallet()];
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+        svm.submit_answer(prayer_id, claimer);
+    }
+    // Claimed without answering
+    let outsider = svm.agent_wallet();
+    svm.claim_prayer(prayer_id, &outsider);
+    let expected = Err(prayer_err(PrayerError::InvalidRanking));
+
+    let cases = [
+        // Weights must not increase down the ranking
+        vec![(claimers[0], 4_000), (claimers[1], 6_000)],
+        // Must sum to 10_000
+        vec![(claimers[0], 6_000), (claimers[1], 3_000)],
+        // One answer twice
+        vec![(claimers[0], 5_000), (claimers[0], 5_000)],
+        // More winners than answers
+        vec![
+            (claimers[0], 4_000),
+            (claimers[1], 3_000),
+            (outsider, 3_000),
+        ],
+    ];
+    for ranking in &cases {
+        assert_eq!(
+            svm.process(&ix::select_winners(&requester, prayer_id, ranking)),
+            expected
+        );
+    }
+
+    // The winner's agent must match the top answer
+    let mut select = ix::select_winners(&requester, prayer_id, &[(claimers[0], 10_000)]);
+    select.accounts[2].pubkey = agent_pda(&claimers[1]).0;
+    assert_eq!(svm.process(&select), expected);
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Active);
+}
+
+#[test]
+fn answer_locked() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 2);
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[1], 10_000)],
+    ))
+    .unwrap();
+
+    // Answers are the record the requester reviews; they stay until resolution
+    let res = svm.process(&ix::close_answer(&claimers[0], prayer_id, &claimers[0]));
+    assert_eq!(res, Err(prayer_err(PrayerError::AnswerLocked)));
+}
+
+#[test]
+fn answers_outstanding() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 1);
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[0], 10_000)],
+    ))
+    .unwrap();
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+    svm.process(&ix::unclaim_prayer(&requester, prayer_id, &claimers[0]))
+        .unwrap();
+
+    let res = svm.process(&ix::close_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::AnswersOutstanding)));
+
+    // An expired competition's answers are paid, never refunded: neither
+    // expire_prayer nor close_prayer can take the bounty back while any remain
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 1);
+    svm.now += TTL + 1;
+    let res = svm.process(&ix::expire_prayer(&requester, &requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::AnswersOutstanding)));
+    svm.process(&ix::unclaim_prayer(&claimers[0], prayer_id, &claimers[0]))
+        .unwrap();
+    let res = svm.process(&ix::close_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::AnswersOutstanding)));
+
+    // With its last claim gone the competition is open again, but cancelling
+    // would still refund a bounty its answers are owed
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Open);
+    let res = svm.process(&ix::cancel_prayer(&requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::AnswersOutstanding)));
+}
+
+#[test]
+fn selection_window_open() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 2);
+    let payouts: Vec<(Pubkey, Pubkey)> = claimers.iter().map(|c| (*c, *c)).collect();
+    let deadline = svm.prayer(prayer_id).selection_deadline();
+    assert_eq!(deadline, svm.now + TTL + REVIEW);
+
+    svm.now = deadline;
+    let res = svm.process(&ix::settle_competition(&requester, prayer_id, None, &payouts));
+    assert_eq!(res, Err(prayer_err(PrayerError::SelectionWindowOpen)));
+
+    // The requester can still rank right up to the deadline...
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[0], 10_000)],
+    ))
+    .unwrap();
+}
+
+#[test]
+fn selection_window_elapsed() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 2);
+
+    // ... but not after it; the answers get settled instead
+    svm.now = svm.prayer(prayer_id).selection_deadline() + 1;
+    let res = svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[0], 10_000)],
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::SelectionWindowElapsed)));
+}
+
+// ── Applications ──────────────────────────────────────────
+
+#[test]
+fn claim_pending() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, applicant) = application(&mut svm, 2);
+    let approved = svm.agent_wallet();
+    svm.claim_prayer(prayer_id, &approved);
+    svm.process(&ix::approve_claim(&requester, prayer_id, &approved))
+        .unwrap();
+    let expected = Err(prayer_err(PrayerError::ClaimPending));
+
+    // A pending applicant gets no content and can't answer...
+    assert_eq!(
+        svm.process(&ix::deliver_content(
+            &requester,
+            prayer_id,
+            &applicant,
+            vec![1]
+        )),
+        expected
+    );
+    assert_eq!(
+        svm.process(&ix::answer_prayer(
+            &applicant,
+            prayer_id,
+            [0u8; 32],
+            vec![]
+        )),
+        expected
+    );
+
+    // ... or keep a claim alive it doesn't hold yet
+    assert_eq!(
+        svm.process(&ix::heartbeat_claim(&applicant, prayer_id)),
+        expected
+    );
+
+    // ... or take the approved claimer's place in the payout
+    svm.process(&ix::answer_prayer(&approved, prayer_id, [9u8; 32], vec![]))
+        .unwrap();
+    assert_eq!(
+        svm.process(&ix::confirm_prayer(
+            &requester,
+            prayer_id,
+            &approved,
+            None,
+            &[(applicant, applicant)],
+        )),
+        expected
+    );
+}
+
+#[test]
+fn claim_not_pending() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, applicant) = application(&mut svm, 2);
+    svm.process(&ix::approve_claim(&requester, prayer_id, &applicant))
+        .unwrap();
+    let expected = Err(prayer_err(PrayerError::ClaimNotPending));
+
+    assert_eq!(
+        svm.process(&ix::approve_claim(&requester, prayer_id, &applicant)),
+        expected
+    );
+    // Approved claims are released with unclaim_prayer, not rejected
+    assert_eq!(
+        svm.process(&ix::reject_claim(&requester, prayer_id, &applicant)),
+        expected
+    );
+}
+
+#[test]
+fn not_open() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, applicant) = application(&mut svm, 1);
+    let second = svm.agent_wallet();
+    svm.claim_prayer(prayer_id, &second);
+    svm.process(&ix::approve_claim(&requester, prayer_id, &applicant))
+        .unwrap();
+
+    // Every slot is taken, so the other applicant can't be approved
+    let res = svm.process(&ix::approve_claim(&requester, prayer_id, &second));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotOpen)));
+    assert_eq!(svm.prayer(prayer_id).num_pending, 1);
+
+    // An answered prayer can no longer be extended or repriced
+    let (answered_to, answered_id, _) = fulfilled(&mut svm, 1_000, 1);
+    let res = svm.process(&ix::extend_prayer(&answered_to, answered_id, 60));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotOpen)));
+    let res = svm.process(&ix::top_up_bounty(&answered_to, answered_id, 1, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotOpen)));
+}
+
+// ── Disputes ──────────────────────────────────────────────
+
+#[test]
+fn not_arbiter() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers, _) = disputed(&mut svm);
+    let stranger = svm.wallet();
+
+    let res = svm.process(&ix::resolve_dispute(
+        &stranger,
+        prayer_id,
+        &requester,
+        &claimers[0],
+        0,
+        None,
+        &[],
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::NotArbiter)));
+}
+
+#[test]
+fn not_disputed() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 1);
+    let arbiter = arbiter(&mut svm);
+    let expected = Err(prayer_err(PrayerError::NotDisputed));
+
+    let res = svm.process(&ix::resolve_dispute(
+        &arbiter,
+        prayer_id,
+        &requester,
+        &claimers[0],
+        0,
+        None,
+        &[],
+    ));
+    assert_eq!(res, expected);
+    let res = svm.process(&ix::expire_dispute(&arbiter, &requester, prayer_id, None));
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn no_arbiters() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, _) = fulfilled(&mut svm, 1_000, 1);
+
+    // Nobody could rule on it
+    let res = svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]));
+    assert_eq!(res, Err(prayer_err(PrayerError::NoArbiters)));
+}
+
+#[test]
+fn ruling_window_open() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, _, arbiter) = disputed(&mut svm);
+    let timeout = svm.chain().config.dispute_timeout_seconds;
+    assert_eq!(svm.prayer(prayer_id).ruling_deadline, svm.now + timeout);
+
+    svm.now += timeout;
+    let res = svm.process(&ix::expire_dispute(&arbiter, &requester, prayer_id, None));
+    assert_eq!(res, Err(prayer_err(PrayerError::RulingWindowOpen)));
+    svm.now += 1;
+    svm.process(&ix::expire_dispute(&arbiter, &requester, prayer_id, None))
+        .unwrap();
+}
+
+#[test]
+fn invalid_basis_points() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers, arbiter) = disputed(&mut svm);
+
+    let res = svm.process(&ix::resolve_dispute(
+        &arbiter,
+        prayer_id,
+        &requester,
+        &claimers[0],
+        10_001,
+        None,
+        &payouts(&claimers),
+    ));
+    assert_eq!(res, Err(prayer_err(PrayerError::InvalidBasisPoints)));
+}
+
+#[test]
+fn claim_answered() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = competition(&mut svm, 1_000, 2);
+    assert!(svm.claim(prayer_id, &claimers[0]).answered);
+    svm.now += svm.prayer(prayer_id).claim_timeout_seconds + 1;
+
+    // A timed-out answerer's claim can't be removed from under its answer
+    let res = svm.process(&ix::unclaim_prayer(&requester, prayer_id, &claimers[0]));
+    assert_eq!(res, Err(prayer_err(PrayerError::ClaimAnswered)));
+
+    // so it can still be ranked
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[0], 10_000)],
+    ))
+    .unwrap();
+}
+
+#[test]
+fn too_many_pending() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, first) = application(&mut svm, 1);
+    while svm.prayer(prayer_id).num_pending < 32 {
+        let applicant = svm.agent_wallet();
+        svm.claim_prayer(prayer_id, &applicant);
+    }
+
+    let applicant = svm.agent_wallet();
+    let res = svm.process(&ix::claim_prayer(&applicant, prayer_id));
+    assert_eq!(res, Err(prayer_err(PrayerError::TooManyPending)));
+
+    // Turning one applicant away makes room again
+    svm.process(&ix::reject_claim(&requester, prayer_id, &first))
+        .unwrap();
+    svm.claim_prayer(prayer_id, &applicant);
+}
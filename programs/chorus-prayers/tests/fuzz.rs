@@ -0,0 +1,954 @@
+//! Property test over random sequences of prayer lifecycle instructions.
+//!
+//! After every step, whether the instruction succeeded or not:
+//! - escrowed lamports (prayer balances above rent) plus lamports paid out to
+//!   wallets (bounties and refunds) equal the bounties deposited; claim stakes
+//!   count as locked by the claimer until slashed into escrow
+//! - each prayer's `num_claimers` / `num_pending` match its live approved and
+//!   pending Claim PDAs, and no Claim outlives its prayer
+//! - each prayer's `num_funders` matches its live Contribution PDAs, and no
+//!   Contribution outlives its prayer
+//! - each agent's `open_claims` / `open_prayers` match the live accounts
+//! - a token prayer's escrow holds its whole bounty until the prayer resolves,
+//!   and no escrow outlives its prayer
+//!
+//! Every step sends the real instruction, and the program alone decides
+//! whether it goes through; deposits are only recorded for the ones that do.
+#![cfg(sbf_program_built)]
+
+mod common;
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{token, token_2022};
+use chorus_prayers::{
+    instruction, ClaimRequirements, Claim, Contribution, PrayerStatus, RemainderPolicy, OPTION_APPROVAL,
+    OPTION_COMPETITION, OPTION_REQUIRE_DELIVERY,
+};
+use chorus_prayers_client::instructions::{self as ix, TokenBounty};
+use chorus_prayers_client::pda::{answer_pda, claim_pda, escrow_pda, prayer_pda};
+use chorus_prayers_client::ChorusAccount;
+use common::{post_args, rent, Svm, WALLET_LAMPORTS};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+const AGENTS: usize = 4;
+
+const POLICIES: [RemainderPolicy; 3] = [
+    RemainderPolicy::Requester,
+    RemainderPolicy::Answerer,
+    RemainderPolicy::RoundRobin,
+];
+
+const TOKEN_PROGRAMS: [Pubkey; 2] = [token::ID, token_2022::ID];
+
+#[derive(Clone, Debug)]
+enum Step {
+    Post {
+        requester: usize,
+        reward: u64,
+        max_claimers: u8,
+        policy: usize, // Index into POLICIES
+        approval: bool,
+        require_delivery: bool,
+        competition: bool,
+        invited: Vec<usize>, // Empty = open to every agent
+        min_reputation: u64,
+        claim_stake: u64,
+        token: Option<usize>, // Index into TOKEN_PROGRAMS; None = SOL bounty
+    },
+    TopUp {
+        prayer: usize,
+        amount: u64,
+    },
+    Claim {
+        prayer: usize,
+        claimer: usize,
+    },
+    Approve {
+        prayer: usize,
+        claimer: usize,
+    },
+    Reject {
+        prayer: usize,
+        claimer: usize,
+    },
+    Unclaim {
+        prayer: usize,
+        claimer: usize,
+        caller: usize,
+    },
+    Heartbeat {
+        prayer: usize,
+        claimer: usize,
+    },
+    Deliver {
+        prayer: usize,
+        claimer: usize,
+    },
+    Answer {
+        prayer: usize,
+        claimer: usize,
+    },
+    Submit {
+        prayer: usize,
+        claimer: usize,
+    },
+    Select {
+        prayer: usize,
+        first_bps: u16, // The first answer's weight; the second gets the rest
+    },
+    Settle {
+        prayer: usize,
+    },
+    CloseAnswer {
+        prayer: usize,
+        answerer: usize,
+        caller: usize,
+    },
+    Weigh {
+        prayer: usize,
+        first_bps: u16, // The first claimer's weight; the second gets the rest
+    },
+    Confirm {
+        prayer: usize,
+    },
+    Finalize {
+        prayer: usize,
+    },
+    Dispute {
+        prayer: usize,
+    },
+    Resolve {
+        prayer: usize,
+        claimer_bps: u16,
+    },
+    ExpireDispute {
+        prayer: usize,
+    },
+    Extend {
+        prayer: usize,
+        seconds: i64,
+    },
+    Fund {
+        prayer: usize,
+        funder: usize,
+        amount: u64,
+    },
+    Vote {
+        prayer: usize,
+        funder: usize,
+    },
+    RequesterVote {
+        prayer: usize,
+    },
+    Withdraw {
+        prayer: usize,
+        funder: usize,
+    },
+    Cancel {
+        prayer: usize,
+    },
+    Close {
+        prayer: usize,
+    },
+    Expire {
+        prayer: usize,
+    },
+    Sweep {
+        prayer: usize,
+    },
+    Warp {
+        seconds: i64,
+    },
+}
+
+/// Prayer arguments count back from the most recently posted prayer. Claimer
+/// and answerer arguments pick among the prayer's live claims and answers when
+/// it has any, so most answers and unclaims are ones the program could accept.
+fn step() -> impl Strategy<Value = Step> {
+    let agent = || 0..AGENTS;
+    let prayer = || 0..3usize;
+    prop_oneof![
+        3 => (
+            agent(),
+            0..1_000_000u64,
+            1..=3u8,
+            0..POLICIES.len(),
+            prop::bool::weighted(0.25),
+            prop::bool::weighted(0.25),
+            prop::bool::weighted(0.25),
+            prop_oneof![3 => Just(Vec::new()), 1 => prop::collection::vec(agent(), 1..=2)],
+            prop_oneof![3 => Just(0u64), 1 => Just(10u64)],
+            prop_oneof![2 => Just(0u64), 1 => 1..10_000u64],
+            prop_oneof![3 => Just(None), 1 => (0..TOKEN_PROGRAMS.len()).prop_map(Some)],
+        )
+            .prop_map(
+                |(
+                    requester,
+                    reward,
+                    max_claimers,
+                    policy,
+                    approval,
+                    require_delivery,
+                    competition,
+                    invited,
+                    min_reputation,
+                    claim_stake,
+                    token,
+                )| {
+                    Step::Post {
+                        requester,
+                        reward,
+                        max_claimers,
+                        policy,
+                        approval,
+                        require_delivery,
+                        competition,
+                        invited,
+                        min_reputation,
+                        claim_stake,
+                        token,
+                    }
+                },
+            ),
+        1 => (prayer(), 1..1_000_000u64).prop_map(|(prayer, amount)| Step::TopUp { prayer, amount }),
+        4 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Claim { prayer, claimer }),
+        2 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Approve { prayer, claimer }),
+        1 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Reject { prayer, claimer }),
+        2 => (prayer(), agent(), agent()).prop_map(|(prayer, claimer, caller)| {
+            Step::Unclaim { prayer, claimer, caller }
+        }),
+        1 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Heartbeat { prayer, claimer }),
+        1 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Deliver { prayer, claimer }),
+        3 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Answer { prayer, claimer }),
+        3 => (prayer(), agent()).prop_map(|(prayer, claimer)| Step::Submit { prayer, claimer }),
+        1 => (prayer(), 0..=10_000u16)
+            .prop_map(|(prayer, first_bps)| Step::Select { prayer, first_bps }),
+        1 => prayer().prop_map(|prayer| Step::Settle { prayer }),
+        1 => (prayer(), agent(), agent()).prop_map(|(prayer, answerer, caller)| {
+            Step::CloseAnswer { prayer, answerer, caller }
+        }),
+        1 => (prayer(), 0..=10_000u16)
+            .prop_map(|(prayer, first_bps)| Step::Weigh { prayer, first_bps }),
+        3 => prayer().prop_map(|prayer| Step::Confirm { prayer }),
+        1 => prayer().prop_map(|prayer| Step::Finalize { prayer }),
+        1 => prayer().prop_map(|prayer| Step::Dispute { prayer }),
+        1 => (prayer(), prop_oneof![Just(0u16), Just(10_000u16), 0..=10_000u16])
+            .prop_map(|(prayer, claimer_bps)| Step::Resolve { prayer, claimer_bps }),
+        1 => prayer().prop_map(|prayer| Step::ExpireDispute { prayer }),
+        1 => (prayer(), 1..100_000i64).prop_map(|(prayer, seconds)| Step::Extend { prayer, seconds }),
+        2 => (prayer(), agent(), 1..1_000_000u64)
+            .prop_map(|(prayer, funder, amount)| Step::Fund { prayer, funder, amount }),
+        1 => (prayer(), agent()).prop_map(|(prayer, funder)| Step::Vote { prayer, funder }),
+        1 => prayer().prop_map(|prayer| Step::RequesterVote { prayer }),
+        1 => (prayer(), agent()).prop_map(|(prayer, funder)| Step::Withdraw { prayer, funder }),
+        1 => prayer().prop_map(|prayer| Step::Cancel { prayer }),
+        2 => prayer().prop_map(|prayer| Step::Close { prayer }),
+        1 => prayer().prop_map(|prayer| Step::Expire { prayer }),
+        1 => prayer().prop_map(|prayer| Step::Sweep { prayer }),
+        // Past the claim timeout, the TTL and review window, or the ruling deadline
+        2 => prop_oneof![0..5_000i64, 0..200_000i64, 0..1_000_000i64]
+            .prop_map(|seconds| Step::Warp { seconds }),
+    ]
+}
+
+struct World {
+    svm: Svm,
+    agents: Vec<Pubkey>,
+    keeper: Pubkey,
+    arbiter: Pubkey,
+    prayers: Vec<u64>,
+    /// Token bounties by prayer id
+    tokens: HashMap<u64, TokenBounty>,
+    /// SOL bounties moved into escrow, by requester or funder
+    deposits: HashMap<Pubkey, u64>,
+}
+
+impl World {
+    fn new() -> Self {
+        let mut svm = Svm::new();
+        let agents = (0..AGENTS).map(|_| svm.agent_wallet()).collect();
+        let keeper = svm.wallet();
+        let arbiter = svm.wallet();
+        let authority = svm.authority;
+        svm.process(&ix::set_arbiters(&authority, vec![arbiter]))
+            .unwrap();
+        Self {
+            svm,
+            agents,
+            keeper,
+            arbiter,
+            prayers: Vec::new(),
+            tokens: HashMap::new(),
+            deposits: HashMap::new(),
+        }
+    }
+
+    fn prayer_id(&self, index: usize) -> Option<u64> {
+        if self.prayers.is_empty() {
+            return None;
+        }
+        let len = self.prayers.len();
+        Some(self.prayers[len - 1 - index % len])
+    }
+
+    /// Claimers holding a live Claim PDA on the prayer
+    fn claimers(&self, prayer_id: u64) -> Vec<Pubkey> {
+        self.agents
+            .iter()
+            .filter(|agent| self.svm.account(&claim_pda(prayer_id, agent).0).is_some())
+            .copied()
+            .collect()
+    }
+
+    /// The `index`th live claimer, or the `index`th agent if there are none
+    fn pick_claimer(&self, prayer_id: u64, index: usize) -> Pubkey {
+        let claimers = self.claimers(prayer_id);
+        if claimers.is_empty() {
+            self.agents[index]
+        } else {
+            claimers[index % claimers.len()]
+        }
+    }
+
+    /// Answerers holding a live Answer PDA on the prayer
+    fn answerers(&self, prayer_id: u64) -> Vec<Pubkey> {
+        self.agents
+            .iter()
+            .filter(|agent| self.svm.account(&answer_pda(prayer_id, agent).0).is_some())
+            .copied()
+            .collect()
+    }
+
+    /// The `index`th live answerer, or the `index`th agent if there are none
+    fn pick_answerer(&self, prayer_id: u64, index: usize) -> Pubkey {
+        let answerers = self.answerers(prayer_id);
+        if answerers.is_empty() {
+            self.agents[index]
+        } else {
+            answerers[index % answerers.len()]
+        }
+    }
+
+    fn token(&self, prayer_id: u64) -> Option<TokenBounty> {
+        self.tokens.get(&prayer_id).copied()
+    }
+
+    /// Where `wallet` is paid: the wallet itself for SOL bounties, or its
+    /// token account (created on first use) for token bounties
+    fn recipient(&mut self, prayer_id: u64, wallet: Pubkey) -> Pubkey {
+        match self.token(prayer_id) {
+            Some(token) => self
+                .svm
+                .token_account(&wallet, &token.mint, &token.token_program),
+            None => wallet,
+        }
+    }
+
+    /// Payout pairs for every approved claimer
+    fn payouts(&mut self, prayer_id: u64) -> Vec<(Pubkey, Pubkey)> {
+        let claimers: Vec<Pubkey> = self
+            .claimers(prayer_id)
+            .into_iter()
+            .filter(|claimer| self.svm.claim(prayer_id, claimer).approved)
+            .collect();
+        claimers
+            .into_iter()
+            .map(|claimer| (claimer, self.recipient(prayer_id, claimer)))
+            .collect()
+    }
+
+    fn answerer(&self, prayer_id: u64) -> Option<Pubkey> {
+        self.svm.account(&prayer_pda(prayer_id).0)?;
+        Some(self.svm.prayer(prayer_id).answerer)
+    }
+
+    fn run(&mut self, step: &Step) {
+        // Failed instructions are expected; the ledger is left untouched
+        match *step {
+            Step::Post {
+                requester,
+                reward,
+                max_claimers,
+                policy,
+                approval,
+                require_delivery,
+                competition,
+                ref invited,
+                min_reputation,
+                claim_stake,
+                token,
+            } => {
+                let requester = self.agents[requester];
+                let mut options = 0;
+                if approval {
+                    options |= OPTION_APPROVAL;
+                }
+                if require_delivery {
+                    options |= OPTION_REQUIRE_DELIVERY;
+                }
+                if competition {
+                    options |= OPTION_COMPETITION;
+                }
+                let args = instruction::PostPrayer {
+                    remainder_policy: POLICIES[policy],
+                    options,
+                    invited: invited.iter().map(|agent| self.agents[*agent]).collect(),
+                    requirements: ClaimRequirements {
+                        min_reputation,
+                        ..Default::default()
+                    },
+                    claim_stake,
+                    ..post_args(reward, max_claimers)
+                };
+                // Each token prayer gets a fresh mint holding just its bounty
+                let token = token.map(|index| {
+                    self.svm
+                        .token_bounty(&requester, &TOKEN_PROGRAMS[index], reward)
+                });
+                let prayer_id = self.svm.chain().total_prayers;
+                if self
+                    .svm
+                    .process(&ix::post_prayer(&requester, prayer_id, args, token.as_ref()))
+                    .is_err()
+                {
+                    return;
+                }
+                match token {
+                    Some(token) => {
+                        self.tokens.insert(prayer_id, token);
+                    }
+                    None => *self.deposits.entry(requester).or_default() += reward,
+                }
+                self.prayers.push(prayer_id);
+            }
+            Step::TopUp { prayer, amount } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let token = self.token(prayer_id);
+                if let Some(token) = &token {
+                    self.svm
+                        .mint_to(&token.mint, &token.requester_token_account, amount);
+                }
+                let res = self.svm.process(&ix::top_up_bounty(
+                    &requester,
+                    prayer_id,
+                    amount,
+                    token.as_ref(),
+                ));
+                if res.is_ok() && token.is_none() {
+                    *self.deposits.entry(requester).or_default() += amount;
+                }
+            }
+            Step::Claim { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let _ = self
+                    .svm
+                    .process(&ix::claim_prayer(&self.agents[claimer], prayer_id));
+            }
+            Step::Approve { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ = self
+                    .svm
+                    .process(&ix::approve_claim(&requester, prayer_id, &claimer));
+            }
+            Step::Reject { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ = self
+                    .svm
+                    .process(&ix::reject_claim(&requester, prayer_id, &claimer));
+            }
+            Step::Unclaim {
+                prayer,
+                claimer,
+                caller,
+            } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ = self.svm.process(&ix::unclaim_prayer(
+                    &self.agents[caller],
+                    prayer_id,
+                    &claimer,
+                ));
+            }
+            Step::Heartbeat { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ = self
+                    .svm
+                    .process(&ix::heartbeat_claim(&claimer, prayer_id));
+            }
+            Step::Deliver { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ = self.svm.process(&ix::deliver_content(
+                    &requester,
+                    prayer_id,
+                    &claimer,
+                    vec![1],
+                ));
+            }
+            Step::Answer { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ =
+                    self.svm
+                        .process(&ix::answer_prayer(&claimer, prayer_id, [3u8; 32], vec![]));
+            }
+            Step::Submit { prayer, claimer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let claimer = self.pick_claimer(prayer_id, claimer);
+                let _ =
+                    self.svm
+                        .process(&ix::submit_answer(&claimer, prayer_id, [4u8; 32], vec![]));
+            }
+            Step::Select { prayer, first_bps } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                // The first two answers are ranked, best first; any others go unranked
+                let best = first_bps.max(10_000 - first_bps);
+                let ranking: Vec<(Pubkey, u16)> = self
+                    .answerers(prayer_id)
+                    .into_iter()
+                    .take(2)
+                    .enumerate()
+                    .map(|(i, answerer)| (answerer, if i == 0 { best } else { 10_000 - best }))
+                    .collect();
+                let ranking = match ranking.len() {
+                    1 => vec![(ranking[0].0, 10_000)],
+                    _ => ranking,
+                };
+                let _ = self
+                    .svm
+                    .process(&ix::select_winners(&requester, prayer_id, &ranking));
+            }
+            Step::Settle { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let answerers = self.answerers(prayer_id);
+                let payouts: Vec<(Pubkey, Pubkey)> = answerers
+                    .into_iter()
+                    .map(|answerer| (answerer, self.recipient(prayer_id, answerer)))
+                    .collect();
+                let token = self.token(prayer_id);
+                let _ = self.svm.process(&ix::settle_competition(
+                    &self.keeper,
+                    prayer_id,
+                    token.as_ref(),
+                    &payouts,
+                ));
+            }
+            Step::CloseAnswer {
+                prayer,
+                answerer,
+                caller,
+            } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let answerer = self.pick_answerer(prayer_id, answerer);
+                let _ = self.svm.process(&ix::close_answer(
+                    &self.agents[caller],
+                    prayer_id,
+                    &answerer,
+                ));
+            }
+            Step::Weigh { prayer, first_bps } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                // The first two claimers share the bounty, any others get nothing
+                let weights: Vec<(Pubkey, u16)> = self
+                    .claimers(prayer_id)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, claimer)| match i {
+                        0 => (claimer, first_bps),
+                        1 => (claimer, 10_000 - first_bps),
+                        _ => (claimer, 0),
+                    })
+                    .collect();
+                let _ = self
+                    .svm
+                    .process(&ix::set_claim_weights(&requester, prayer_id, &weights));
+            }
+            Step::Confirm { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let (Some(requester), Some(answerer)) =
+                    (self.requester(prayer_id), self.answerer(prayer_id))
+                else {
+                    return;
+                };
+                let payouts = self.payouts(prayer_id);
+                let token = self.token(prayer_id);
+                let _ = self.svm.process(&ix::confirm_prayer(
+                    &requester,
+                    prayer_id,
+                    &answerer,
+                    token.as_ref(),
+                    &payouts,
+                ));
+            }
+            Step::Finalize { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(answerer) = self.answerer(prayer_id) else {
+                    return;
+                };
+                let payouts = self.payouts(prayer_id);
+                let token = self.token(prayer_id);
+                let _ = self.svm.process(&ix::finalize_prayer(
+                    &self.keeper,
+                    prayer_id,
+                    &answerer,
+                    token.as_ref(),
+                    &payouts,
+                ));
+            }
+            Step::Dispute { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let _ = self
+                    .svm
+                    .process(&ix::open_dispute(&requester, prayer_id, [5u8; 32]));
+            }
+            Step::Resolve {
+                prayer,
+                claimer_bps,
+            } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let (Some(requester), Some(answerer)) =
+                    (self.requester(prayer_id), self.answerer(prayer_id))
+                else {
+                    return;
+                };
+                let payouts = self.payouts(prayer_id);
+                let token = self.token(prayer_id);
+                let _ = self.svm.process(&ix::resolve_dispute(
+                    &self.arbiter,
+                    prayer_id,
+                    &requester,
+                    &answerer,
+                    claimer_bps,
+                    token.as_ref(),
+                    &payouts,
+                ));
+            }
+            Step::ExpireDispute { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let token = self.token(prayer_id);
+                let _ = self.svm.process(&ix::expire_dispute(
+                    &self.keeper,
+                    &requester,
+                    prayer_id,
+                    token.as_ref(),
+                ));
+            }
+            Step::Extend { prayer, seconds } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let _ = self
+                    .svm
+                    .process(&ix::extend_prayer(&requester, prayer_id, seconds));
+            }
+            Step::Fund {
+                prayer,
+                funder,
+                amount,
+            } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let funder = self.agents[funder];
+                if self
+                    .svm
+                    .process(&ix::fund_prayer(&funder, prayer_id, amount))
+                    .is_ok()
+                {
+                    *self.deposits.entry(funder).or_default() += amount;
+                }
+            }
+            Step::Vote { prayer, funder } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let _ = self
+                    .svm
+                    .process(&ix::vote_confirm(&self.agents[funder], prayer_id));
+            }
+            Step::RequesterVote { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let _ = self
+                    .svm
+                    .process(&ix::vote_confirm_requester(&requester, prayer_id));
+            }
+            Step::Withdraw { prayer, funder } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let _ = self.svm.process(&ix::withdraw_contribution(
+                    &self.keeper,
+                    prayer_id,
+                    &self.agents[funder],
+                ));
+            }
+            Step::Cancel { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let token = self.token(prayer_id);
+                let _ = self
+                    .svm
+                    .process(&ix::cancel_prayer(&requester, prayer_id, token.as_ref()));
+            }
+            Step::Close { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let token = self.token(prayer_id);
+                let _ = self
+                    .svm
+                    .process(&ix::close_prayer(&requester, prayer_id, token.as_ref()));
+            }
+            Step::Expire { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let Some(requester) = self.requester(prayer_id) else {
+                    return;
+                };
+                let token = self.token(prayer_id);
+                let _ = self.svm.process(&ix::expire_prayer(
+                    &self.keeper,
+                    &requester,
+                    prayer_id,
+                    token.as_ref(),
+                ));
+            }
+            Step::Sweep { prayer } => {
+                let Some(prayer_id) = self.prayer_id(prayer) else {
+                    return;
+                };
+                let claimers = self.claimers(prayer_id);
+                let _ = self
+                    .svm
+                    .process(&ix::sweep_claims(&self.keeper, prayer_id, &claimers));
+            }
+            Step::Warp { seconds } => self.svm.now += seconds,
+        }
+    }
+
+    fn requester(&self, prayer_id: u64) -> Option<Pubkey> {
+        self.svm.account(&prayer_pda(prayer_id).0)?;
+        Some(self.svm.prayer(prayer_id).requester)
+    }
+
+    fn check_invariants(&self) -> Result<(), TestCaseError> {
+        let mut escrowed: u128 = 0;
+        // Rent (and claim stakes) each wallet has locked up in open accounts
+        let mut locked: HashMap<Pubkey, u64> = HashMap::new();
+        let mut prayers = Vec::new();
+        let mut claims = Vec::new();
+        let mut contributions: Vec<Contribution> = Vec::new();
+        let mut agents = Vec::new();
+
+        for (_, account) in self.svm.accounts() {
+            if account.owner != chorus_prayers::ID {
+                continue;
+            }
+            let Ok(decoded) = ChorusAccount::decode(&account.data) else {
+                continue;
+            };
+            match decoded {
+                ChorusAccount::Prayer(prayer) => {
+                    let prayer_rent = rent(account.data.len());
+                    escrowed += (account.lamports - prayer_rent) as u128;
+                    *locked.entry(prayer.requester).or_default() += prayer_rent;
+                    prayers.push(prayer);
+                }
+                ChorusAccount::Claim(claim) => {
+                    *locked.entry(claim.claimer).or_default() += account.lamports;
+                    claims.push(claim);
+                }
+                ChorusAccount::Contribution(contribution) => {
+                    *locked.entry(contribution.funder).or_default() += account.lamports;
+                    contributions.push(contribution);
+                }
+                ChorusAccount::Agent(agent) => {
+                    *locked.entry(agent.wallet).or_default() += account.lamports;
+                    agents.push(agent);
+                }
+                ChorusAccount::PrayerChain(chain) => {
+                    *locked.entry(chain.authority).or_default() += account.lamports;
+                }
+                ChorusAccount::Answer(answer) => {
+                    *locked.entry(answer.answerer).or_default() += account.lamports;
+                }
+            }
+        }
+
+        // Token escrows are owned by the token program, but their rent is the
+        // requester's until close_prayer returns it
+        for prayer_id in self.tokens.keys() {
+            let escrow = escrow_pda(*prayer_id).0;
+            let prayer = prayers.iter().find(|prayer| prayer.id == *prayer_id);
+            if let Some(account) = self.svm.account(&escrow) {
+                let Some(prayer) = prayer else {
+                    return Err(TestCaseError::fail(format!("escrow on closed prayer {prayer_id}")));
+                };
+                *locked.entry(prayer.requester).or_default() += account.lamports;
+            }
+            if let Some(prayer) = prayer {
+                if matches!(
+                    prayer.status,
+                    PrayerStatus::Open | PrayerStatus::Active | PrayerStatus::Fulfilled | PrayerStatus::Disputed
+                ) {
+                    prop_assert_eq!(
+                        self.svm.token_balance(&escrow),
+                        prayer.reward_lamports,
+                        "prayer {}",
+                        prayer_id
+                    );
+                }
+            }
+        }
+
+        // Whatever a wallet holds beyond what it started with, less the rent
+        // and bounties it put in, was paid out of escrow
+        let wallets = self
+            .agents
+            .iter()
+            .chain([&self.keeper, &self.arbiter, &self.svm.authority]);
+        let mut paid_out: i128 = 0;
+        for wallet in wallets {
+            paid_out += self.svm.lamports(wallet) as i128
+                + locked.get(wallet).copied().unwrap_or(0) as i128
+                + self.deposits.get(wallet).copied().unwrap_or(0) as i128
+                - WALLET_LAMPORTS as i128;
+        }
+        let deposits: u64 = self.deposits.values().sum();
+        prop_assert_eq!(escrowed as i128 + paid_out, deposits as i128);
+
+        for prayer in &prayers {
+            let (approved, pending): (Vec<&Claim>, Vec<&Claim>) = claims
+                .iter()
+                .filter(|claim| claim.prayer_id == prayer.id)
+                .partition(|claim| claim.approved);
+            prop_assert_eq!(prayer.num_claimers as usize, approved.len(), "prayer {}", prayer.id);
+            prop_assert_eq!(prayer.num_pending as usize, pending.len(), "prayer {}", prayer.id);
+            let funders = contributions
+                .iter()
+                .filter(|contribution| contribution.prayer_id == prayer.id)
+                .count();
+            prop_assert_eq!(prayer.num_funders as usize, funders, "prayer {}", prayer.id);
+        }
+        for claim in &claims {
+            prop_assert!(
+                prayers.iter().any(|prayer| prayer.id == claim.prayer_id),
+                "claim on closed prayer {}",
+                claim.prayer_id
+            );
+        }
+        for contribution in &contributions {
+            prop_assert!(
+                prayers.iter().any(|prayer| prayer.id == contribution.prayer_id),
+                "contribution on closed prayer {}",
+                contribution.prayer_id
+            );
+        }
+        for agent in &agents {
+            let open_claims = claims
+                .iter()
+                .filter(|claim| claim.claimer == agent.wallet)
+                .count();
+            let open_prayers = prayers
+                .iter()
+                .filter(|prayer| prayer.requester == agent.wallet)
+                .count();
+            prop_assert_eq!(agent.open_claims as usize, open_claims);
+            prop_assert_eq!(agent.open_prayers as usize, open_prayers);
+        }
+        Ok(())
+    }
+}
+
+proptest! {
+    #[test]
+    fn lifecycle_conserves_lamports(steps in prop::collection::vec(step(), 1..80)) {
+        let mut world = World::new();
+        for step in &steps {
+            world.run(step);
+            world.check_invariants()?;
+        }
+    }
+}
@@ -0,0 +1,1057 @@
+//! Lamport accounting for the instructions that move SOL bounties:
+//! confirm_prayer (and its finalize / resolve_dispute variants), cancel_prayer,
+//! close_prayer, expire_prayer and settle_competition, plus claim stakes through answer_prayer,
+//! unclaim_prayer and sweep_claims, and funders' shares through
+//! withdraw_contribution. The runtime already enforces conservation per
+//! transaction; these check where the lamports end up.
+#![cfg(sbf_program_built)]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use chorus_prayers::{
+    instruction, Answer, Claim, Contribution, Prayer, PrayerStatus, RemainderPolicy, KEEPER_FEE_LAMPORTS, OPTION_APPROVAL,
+    OPTION_COMPETITION,
+};
+use chorus_prayers_client::instructions as ix;
+use chorus_prayers_client::pda::{agent_pda, answer_pda, claim_pda, contribution_pda, prayer_pda};
+use common::{post_args, rent, Svm, REVIEW, TTL};
+
+fn prayer_rent() -> u64 {
+    rent(8 + Prayer::INIT_SPACE)
+}
+
+fn claim_rent() -> u64 {
+    rent(8 + Claim::INIT_SPACE)
+}
+
+fn contribution_rent() -> u64 {
+    rent(8 + Contribution::INIT_SPACE)
+}
+
+/// A SOL prayer with `claimers` claims, answered by the first claimer
+fn fulfilled(svm: &mut Svm, reward: u64, claimers: usize) -> (Pubkey, u64, Vec<Pubkey>) {
+    fulfilled_with(svm, post_args(reward, claimers as u8))
+}
+
+/// A prayer posted with `args`, claimed to capacity and answered by the
+/// first claimer
+fn fulfilled_with(svm: &mut Svm, args: instruction::PostPrayer) -> (Pubkey, u64, Vec<Pubkey>) {
+    let requester = svm.agent_wallet();
+    let claimers = args.max_claimers as usize;
+    let prayer_id = svm.post_with(&requester, args, None);
+    let claimers: Vec<Pubkey> = (0..claimers).map(|_| svm.agent_wallet()).collect();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+    }
+    svm.process(&ix::answer_prayer(
+        &claimers[0],
+        prayer_id,
+        [9u8; 32],
+        vec![],
+    ))
+    .unwrap();
+    (requester, prayer_id, claimers)
+}
+
+fn payouts(claimers: &[Pubkey]) -> Vec<(Pubkey, Pubkey)> {
+    claimers
+        .iter()
+        .map(|claimer| (*claimer, *claimer))
+        .collect()
+}
+
+/// post_args with a remainder policy
+fn with_policy(reward: u64, max_claimers: u8, policy: RemainderPolicy) -> instruction::PostPrayer {
+    instruction::PostPrayer {
+        remainder_policy: policy,
+        ..post_args(reward, max_claimers)
+    }
+}
+
+fn balances(svm: &Svm, keys: &[Pubkey]) -> Vec<u64> {
+    keys.iter().map(|key| svm.lamports(key)).collect()
+}
+
+#[test]
+fn confirm_splits_bounty_and_keeps_dust_until_close() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 3);
+    let prayer = prayer_pda(prayer_id).0;
+    assert_eq!(svm.lamports(&prayer), prayer_rent() + 1_000);
+
+    let requester_before = svm.lamports(&requester);
+    let claimers_before = balances(&svm, &claimers);
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    // 1_000 / 3 = 333 each; the 1 lamport remainder stays in the prayer PDA
+    for (before, after) in claimers_before.iter().zip(balances(&svm, &claimers)) {
+        assert_eq!(after, before + 333);
+    }
+    assert_eq!(svm.lamports(&requester), requester_before);
+    assert_eq!(svm.lamports(&prayer), prayer_rent() + 1);
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Confirmed);
+
+    let answerer = svm.agent(&claimers[0]);
+    assert_eq!(answerer.prayers_confirmed, 1);
+    assert_eq!(answerer.reputation, 15);
+
+    // Anyone can release the claims of a confirmed prayer; rent goes to each claimer
+    for claimer in &claimers {
+        let before = svm.lamports(claimer);
+        svm.process(&ix::unclaim_prayer(&requester, prayer_id, claimer))
+            .unwrap();
+        assert_eq!(svm.lamports(claimer), before + claim_rent());
+        assert!(svm.account(&claim_pda(prayer_id, claimer).0).is_none());
+    }
+
+    // Closing returns the rent plus the split remainder
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(
+        svm.lamports(&requester),
+        requester_before + prayer_rent() + 1
+    );
+    assert!(svm.account(&prayer).is_none());
+    assert_eq!(svm.agent(&requester).open_prayers, 0);
+}
+
+#[test]
+fn confirm_with_even_split_leaves_only_rent() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 900, 3);
+
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn answerer_policy_pays_remainder_to_answerer() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled_with(&mut svm, with_policy(1_001, 3, RemainderPolicy::Answerer));
+    let before = balances(&svm, &claimers);
+
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    // 1_001 / 3 = 333 each; the answerer also takes the 2 lamport remainder
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], before[0] + 335);
+    assert_eq!(after[1], before[1] + 333);
+    assert_eq!(after[2], before[2] + 333);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn round_robin_policy_follows_wallet_order() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled_with(&mut svm, with_policy(1_002, 4, RemainderPolicy::RoundRobin));
+    let before = balances(&svm, &claimers);
+
+    // Payout order doesn't matter: the two lowest wallets get the extra lamport
+    let mut reversed = payouts(&claimers);
+    reversed.reverse();
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &reversed,
+    ))
+    .unwrap();
+
+    let mut sorted = claimers.clone();
+    sorted.sort();
+    for (i, claimer) in claimers.iter().enumerate() {
+        let extra = if sorted[..2].contains(claimer) { 1 } else { 0 };
+        assert_eq!(svm.lamports(claimer), before[i] + 250 + extra);
+    }
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn weighted_split_pays_by_contribution() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 3);
+    let before = balances(&svm, &claimers);
+
+    // The answerer proposes weights; the requester replaces them before confirming
+    svm.process(&ix::set_claim_weights(
+        &claimers[0],
+        prayer_id,
+        &[
+            (claimers[0], 8_000),
+            (claimers[1], 1_000),
+            (claimers[2], 1_000),
+        ],
+    ))
+    .unwrap();
+    svm.process(&ix::set_claim_weights(
+        &requester,
+        prayer_id,
+        &[
+            (claimers[2], 1_000),
+            (claimers[1], 2_000),
+            (claimers[0], 7_000),
+        ],
+    ))
+    .unwrap();
+    assert_eq!(svm.claim(prayer_id, &claimers[0]).weight_bps, 7_000);
+    assert!(svm.prayer(prayer_id).weighted);
+
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], before[0] + 700);
+    assert_eq!(after[1], before[1] + 200);
+    assert_eq!(after[2], before[2] + 100);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn weighted_split_rounding_follows_remainder_policy() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled_with(&mut svm, with_policy(1_001, 3, RemainderPolicy::Answerer));
+    let before = balances(&svm, &claimers);
+
+    svm.process(&ix::set_claim_weights(
+        &requester,
+        prayer_id,
+        &[
+            (claimers[0], 3_334),
+            (claimers[1], 3_333),
+            (claimers[2], 3_333),
+        ],
+    ))
+    .unwrap();
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    // Each weighted share rounds down to 333; the 2 lamports left go to the answerer
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], before[0] + 335);
+    assert_eq!(after[1], before[1] + 333);
+    assert_eq!(after[2], before[2] + 333);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn competition_pays_ranked_winners() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION,
+            ..post_args(1_000, 3)
+        },
+        None,
+    );
+    let claimers: Vec<Pubkey> = (0..3).map(|_| svm.agent_wallet()).collect();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+        svm.submit_answer(prayer_id, claimer);
+    }
+    let before = balances(&svm, &claimers);
+
+    // The third answer wins, the first is runner-up, the second goes unranked
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[2], 7_000), (claimers[0], 3_000)],
+    ))
+    .unwrap();
+    let prayer = svm.prayer(prayer_id);
+    assert!(prayer.status == PrayerStatus::Fulfilled);
+    assert_eq!(prayer.answerer, claimers[2]);
+    assert_eq!(svm.answer(prayer_id, &claimers[2]).rank, 1);
+    assert_eq!(svm.answer(prayer_id, &claimers[0]).rank, 2);
+    assert_eq!(svm.answer(prayer_id, &claimers[1]).rank, 0);
+    assert_eq!(svm.agent(&claimers[2]).prayers_answered, 1);
+
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[2],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], before[0] + 300);
+    assert_eq!(after[1], before[1]);
+    assert_eq!(after[2], before[2] + 700);
+
+    // Anyone can return answer rent once the prayer is resolved
+    let answer_rent = rent(8 + Answer::INIT_SPACE);
+    for claimer in &claimers {
+        let before = svm.lamports(claimer);
+        svm.process(&ix::close_answer(&requester, prayer_id, claimer))
+            .unwrap();
+        assert_eq!(svm.lamports(claimer), before + answer_rent);
+        assert!(svm.account(&answer_pda(prayer_id, claimer).0).is_none());
+    }
+    assert_eq!(svm.prayer(prayer_id).num_answers, 0);
+}
+
+#[test]
+fn round_robin_skips_unranked_competitors() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION,
+            ..with_policy(1_001, 3, RemainderPolicy::RoundRobin)
+        },
+        None,
+    );
+    let mut claimers: Vec<Pubkey> = (0..3).map(|_| svm.agent_wallet()).collect();
+    claimers.sort();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+        svm.submit_answer(prayer_id, claimer);
+    }
+    let before = balances(&svm, &claimers);
+
+    // The lowest wallet goes unranked, so the spare lamport skips it
+    svm.process(&ix::select_winners(
+        &requester,
+        prayer_id,
+        &[(claimers[2], 5_000), (claimers[1], 5_000)],
+    ))
+    .unwrap();
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[2],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], before[0]);
+    assert_eq!(after[1], before[1] + 501);
+    assert_eq!(after[2], before[2] + 500);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn settle_competition_splits_bounty_across_answers() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let keeper = svm.wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_COMPETITION,
+            ..post_args(1_001, 3)
+        },
+        None,
+    );
+    let claimers: Vec<Pubkey> = (0..3).map(|_| svm.agent_wallet()).collect();
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+    }
+    // The third claimer never answers and gets nothing
+    for claimer in &claimers[..2] {
+        svm.submit_answer(prayer_id, claimer);
+    }
+    let before = balances(&svm, &claimers);
+
+    // The requester never ranks; once the selection window closes anyone can
+    // pay the answers in equal shares
+    svm.now = svm.prayer(prayer_id).selection_deadline() + 1;
+    svm.process(&ix::settle_competition(
+        &keeper,
+        prayer_id,
+        None,
+        &payouts(&claimers[..2]),
+    ))
+    .unwrap();
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], before[0] + 500);
+    assert_eq!(after[1], before[1] + 500);
+    assert_eq!(after[2], before[2]);
+    let prayer = svm.prayer(prayer_id);
+    assert!(prayer.status == PrayerStatus::Confirmed);
+    assert_eq!(prayer.fulfilled_at, svm.now);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent() + 1);
+
+    // Settled counts as resolved: answers and claims are released and the
+    // dust goes back with the rent
+    for claimer in &claimers[..2] {
+        svm.process(&ix::close_answer(&keeper, prayer_id, claimer))
+            .unwrap();
+    }
+    for claimer in &claimers {
+        svm.process(&ix::unclaim_prayer(&requester, prayer_id, claimer))
+            .unwrap();
+    }
+    let before = svm.lamports(&requester);
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(svm.lamports(&requester), before + prayer_rent() + 1);
+}
+
+#[test]
+fn approval_mode_pays_only_approved_claims() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options: OPTION_APPROVAL,
+            ..post_args(1_000, 1)
+        },
+        None,
+    );
+    let applicants: Vec<Pubkey> = (0..3).map(|_| svm.agent_wallet()).collect();
+    for applicant in &applicants {
+        svm.claim_prayer(prayer_id, applicant);
+    }
+
+    // Pending applicants take no slot, so more can apply than max_claimers
+    let prayer = svm.prayer(prayer_id);
+    assert!(prayer.status == PrayerStatus::Open);
+    assert_eq!((prayer.num_claimers, prayer.num_pending), (0, 3));
+
+    // A rejected applicant gets its claim rent back
+    let before = svm.lamports(&applicants[1]);
+    svm.process(&ix::reject_claim(&requester, prayer_id, &applicants[1]))
+        .unwrap();
+    assert_eq!(svm.lamports(&applicants[1]), before + claim_rent());
+    assert!(svm.account(&claim_pda(prayer_id, &applicants[1]).0).is_none());
+    assert_eq!(svm.agent(&applicants[1]).open_claims, 0);
+
+    svm.now += 60;
+    svm.process(&ix::approve_claim(&requester, prayer_id, &applicants[0]))
+        .unwrap();
+    let prayer = svm.prayer(prayer_id);
+    assert!(prayer.status == PrayerStatus::Active);
+    assert_eq!((prayer.num_claimers, prayer.num_pending), (1, 1));
+    assert_eq!(svm.claim(prayer_id, &applicants[0]).claimed_at, svm.now);
+
+    svm.process(&ix::answer_prayer(
+        &applicants[0],
+        prayer_id,
+        [9u8; 32],
+        vec![],
+    ))
+    .unwrap();
+    let before = balances(&svm, &applicants);
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &applicants[0],
+        None,
+        &payouts(&applicants[..1]),
+    ))
+    .unwrap();
+    let after = balances(&svm, &applicants);
+    assert_eq!(after[0], before[0] + 1_000);
+    assert_eq!(after[2], before[2]);
+
+    // The leftover applicant is released like any claim once resolved
+    for applicant in [applicants[0], applicants[2]] {
+        svm.process(&ix::unclaim_prayer(&requester, prayer_id, &applicant))
+            .unwrap();
+    }
+    assert_eq!(svm.lamports(&applicants[2]), before[2] + claim_rent());
+    let prayer = svm.prayer(prayer_id);
+    assert_eq!((prayer.num_claimers, prayer.num_pending), (0, 0));
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+}
+
+/// A SOL prayer whose claimers each deposit `stake`
+fn staked(svm: &mut Svm, reward: u64, stake: u64, options: u8) -> (Pubkey, u64) {
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post_with(
+        &requester,
+        instruction::PostPrayer {
+            options,
+            claim_stake: stake,
+            ..post_args(reward, 2)
+        },
+        None,
+    );
+    (requester, prayer_id)
+}
+
+#[test]
+fn claim_stake_returned_on_answer_and_unclaim() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id) = staked(&mut svm, 1_000, 500, 0);
+    let claimers: Vec<Pubkey> = (0..2).map(|_| svm.agent_wallet()).collect();
+    let before = balances(&svm, &claimers);
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+    }
+    let claim = claim_pda(prayer_id, &claimers[0]).0;
+    assert_eq!(svm.lamports(&claim), claim_rent() + 500);
+    assert_eq!(svm.claim(prayer_id, &claimers[0]).stake, 500);
+
+    // Walking away voluntarily costs nothing
+    svm.process(&ix::unclaim_prayer(&claimers[1], prayer_id, &claimers[1]))
+        .unwrap();
+    assert_eq!(svm.lamports(&claimers[1]), before[1]);
+
+    // Answering hands the stake back; the claim keeps only its rent
+    svm.process(&ix::answer_prayer(
+        &claimers[0],
+        prayer_id,
+        [9u8; 32],
+        vec![],
+    ))
+    .unwrap();
+    assert_eq!(svm.lamports(&claimers[0]), before[0] - claim_rent());
+    assert_eq!(svm.lamports(&claim), claim_rent());
+    assert_eq!(svm.claim(prayer_id, &claimers[0]).stake, 0);
+
+    // Once resolved, a third-party unclaim is a release, not a slash
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers[..1]),
+    ))
+    .unwrap();
+    svm.process(&ix::unclaim_prayer(&requester, prayer_id, &claimers[0]))
+        .unwrap();
+    assert_eq!(svm.lamports(&claimers[0]), before[0] + 1_000);
+}
+
+#[test]
+fn timed_out_claim_stake_slashed_into_bounty() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id) = staked(&mut svm, 1_000, 500, 0);
+    let claimer = svm.agent_wallet();
+    svm.claim_prayer(prayer_id, &claimer);
+    let before = svm.lamports(&claimer);
+    let keeper = svm.wallet();
+
+    svm.now += svm.chain().config.claim_timeout_seconds + 1;
+    svm.process(&ix::unclaim_prayer(&keeper, prayer_id, &claimer))
+        .unwrap();
+    assert_eq!(svm.lamports(&claimer), before + claim_rent());
+    assert_eq!(svm.prayer(prayer_id).reward_lamports, 1_500);
+    assert_eq!(
+        svm.lamports(&prayer_pda(prayer_id).0),
+        prayer_rent() + 1_500
+    );
+
+    // The slashed stake is refunded with the bounty on cancel
+    let before = svm.lamports(&requester);
+    svm.process(&ix::cancel_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(svm.lamports(&requester), before + 1_500);
+}
+
+#[test]
+fn pending_claim_stake_never_slashed() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id) = staked(&mut svm, 1_000, 500, OPTION_APPROVAL);
+    let applicant = svm.agent_wallet();
+    let before = svm.lamports(&applicant);
+    svm.claim_prayer(prayer_id, &applicant);
+
+    svm.now += svm.chain().config.claim_timeout_seconds + 1;
+    svm.process(&ix::unclaim_prayer(&requester, prayer_id, &applicant))
+        .unwrap();
+    assert_eq!(svm.lamports(&applicant), before);
+    assert_eq!(svm.prayer(prayer_id).reward_lamports, 1_000);
+}
+
+#[test]
+fn sweep_returns_claim_rent_and_stakes() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id) = staked(&mut svm, 1_000, 500, 0);
+    let claimers: Vec<Pubkey> = (0..2).map(|_| svm.agent_wallet()).collect();
+    let before = balances(&svm, &claimers);
+    for claimer in &claimers {
+        svm.claim_prayer(prayer_id, claimer);
+    }
+    svm.process(&ix::answer_prayer(
+        &claimers[0],
+        prayer_id,
+        [9u8; 32],
+        vec![],
+    ))
+    .unwrap();
+    svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    // One sweep by anyone closes both claims; the idle claimer's stake comes back too
+    let keeper = svm.wallet();
+    svm.process(&ix::sweep_claims(&keeper, prayer_id, &claimers))
+        .unwrap();
+    let after = balances(&svm, &claimers);
+    assert_eq!(after, vec![before[0] + 500, before[1] + 500]);
+    for claimer in &claimers {
+        assert!(svm.account(&claim_pda(prayer_id, claimer).0).is_none());
+        assert_eq!(svm.agent(claimer).open_claims, 0);
+    }
+    assert_eq!(svm.prayer(prayer_id).num_claimers, 0);
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+}
+
+#[test]
+fn failed_confirm_moves_nothing() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_000, 2);
+    let total = svm.total_lamports();
+    let prayer = prayer_pda(prayer_id).0;
+
+    // Second payout goes to the wrong wallet after the first claimer was paid
+    let res = svm.process(&ix::confirm_prayer(
+        &requester,
+        prayer_id,
+        &claimers[0],
+        None,
+        &[(claimers[0], claimers[0]), (claimers[1], requester)],
+    ));
+    assert!(res.is_err());
+    assert_eq!(svm.lamports(&prayer), prayer_rent() + 1_000);
+    assert_eq!(svm.total_lamports(), total);
+}
+
+#[test]
+fn finalize_pays_like_confirm() {
+    let mut svm = Svm::new();
+    let (_, prayer_id, claimers) = fulfilled(&mut svm, 1_001, 2);
+    let keeper = svm.wallet();
+    let before = balances(&svm, &claimers);
+
+    svm.now += REVIEW + 1;
+    svm.process(&ix::finalize_prayer(
+        &keeper,
+        prayer_id,
+        &claimers[0],
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    for (before, after) in before.iter().zip(balances(&svm, &claimers)) {
+        assert_eq!(after, before + 500);
+    }
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent() + 1);
+}
+
+#[test]
+fn resolve_dispute_refunds_unawarded_share() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled(&mut svm, 1_001, 2);
+    let arbiter = svm.wallet();
+    let authority = svm.authority;
+    svm.process(&ix::set_arbiters(&authority, vec![arbiter]))
+        .unwrap();
+    svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]))
+        .unwrap();
+    let requester_before = svm.lamports(&requester);
+    let claimers_before = balances(&svm, &claimers);
+
+    // 25% of 1_001 = 250, split 125 each; the other 751 goes back
+    svm.process(&ix::resolve_dispute(
+        &arbiter,
+        prayer_id,
+        &requester,
+        &claimers[0],
+        2_500,
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+
+    for (before, after) in claimers_before.iter().zip(balances(&svm, &claimers)) {
+        assert_eq!(after, before + 125);
+    }
+    assert_eq!(svm.lamports(&requester), requester_before + 751);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn resolve_dispute_applies_remainder_policy_to_award() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id, claimers) = fulfilled_with(&mut svm, with_policy(1_003, 2, RemainderPolicy::Answerer));
+    let arbiter = svm.wallet();
+    let authority = svm.authority;
+    svm.process(&ix::set_arbiters(&authority, vec![arbiter]))
+        .unwrap();
+    svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]))
+        .unwrap();
+    let requester_before = svm.lamports(&requester);
+    let claimers_before = balances(&svm, &claimers);
+
+    // 50% of 1_003 = 501: 250 each plus the remainder to the answerer; 502 goes back
+    svm.process(&ix::resolve_dispute(
+        &arbiter,
+        prayer_id,
+        &requester,
+        &claimers[0],
+        5_000,
+        None,
+        &payouts(&claimers),
+    ))
+    .unwrap();
+    let after = balances(&svm, &claimers);
+    assert_eq!(after[0], claimers_before[0] + 251);
+    assert_eq!(after[1], claimers_before[1] + 250);
+    assert_eq!(svm.lamports(&requester), requester_before + 502);
+}
+
+#[test]
+fn cancel_refunds_bounty_and_close_refunds_rent() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 5_000, 1);
+    let prayer = prayer_pda(prayer_id).0;
+    let before = svm.lamports(&requester);
+
+    svm.process(&ix::cancel_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(svm.lamports(&requester), before + 5_000);
+    assert_eq!(svm.lamports(&prayer), prayer_rent());
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Cancelled);
+
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(svm.lamports(&requester), before + 5_000 + prayer_rent());
+    assert!(svm.account(&prayer).is_none());
+}
+
+#[test]
+fn close_expired_refunds_bounty_and_rent() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let prayer_id = svm.post(&requester, 7_000, 2);
+    svm.claim_prayer(prayer_id, &claimer);
+    let before = svm.lamports(&requester);
+
+    svm.now += TTL + 1;
+    // The stale claim must be released first; it's past the claim timeout
+    svm.process(&ix::unclaim_prayer(&requester, prayer_id, &claimer))
+        .unwrap();
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+
+    assert_eq!(svm.lamports(&requester), before + 7_000 + prayer_rent());
+    assert!(svm.account(&prayer_pda(prayer_id).0).is_none());
+    assert_eq!(svm.agent(&claimer).open_claims, 0);
+}
+
+#[test]
+fn expire_refunds_bounty_and_pays_keeper_from_rent() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let keeper = svm.wallet();
+    let prayer_id = svm.post(&requester, 7_000, 1);
+    let before = balances(&svm, &[requester, keeper]);
+
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None))
+        .unwrap();
+    let after = balances(&svm, &[requester, keeper]);
+    assert_eq!(after[0], before[0] + 7_000 + prayer_rent() - KEEPER_FEE_LAMPORTS);
+    assert_eq!(after[1], before[1] + KEEPER_FEE_LAMPORTS);
+    assert!(svm.account(&prayer_pda(prayer_id).0).is_none());
+    assert_eq!(svm.agent(&requester).open_prayers, 0);
+}
+
+#[test]
+fn expire_with_claims_refunds_then_closes_once_released() {
+    let mut svm = Svm::new();
+    let (requester, prayer_id) = staked(&mut svm, 7_000, 500, 0);
+    let claimer = svm.agent_wallet();
+    let keeper = svm.wallet();
+    svm.claim_prayer(prayer_id, &claimer);
+    let before = balances(&svm, &[requester, claimer, keeper]);
+
+    // The bounty comes back at once; the prayer stays open for its claim
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None))
+        .unwrap();
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Expired);
+    assert_eq!(svm.lamports(&requester), before[0] + 7_000);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+    assert_eq!(svm.lamports(&keeper), before[2]);
+
+    // Expired counts as resolved: the keeper releases the claim, stake intact
+    svm.process(&ix::unclaim_prayer(&keeper, prayer_id, &claimer))
+        .unwrap();
+    assert_eq!(svm.lamports(&claimer), before[1] + claim_rent() + 500);
+
+    svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(
+        svm.lamports(&requester),
+        before[0] + 7_000 + prayer_rent() - KEEPER_FEE_LAMPORTS
+    );
+    assert_eq!(svm.lamports(&keeper), before[2] + KEEPER_FEE_LAMPORTS);
+}
+
+#[test]
+fn cancel_refunds_funders_pro_rata() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let funders = [svm.wallet(), svm.wallet()];
+    let keeper = svm.wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.fund(prayer_id, &funders[0], 2_000);
+    svm.fund(prayer_id, &funders[1], 1_001);
+    let prayer = prayer_pda(prayer_id).0;
+    let before = balances(&svm, &[requester, funders[0], funders[1]]);
+
+    // Only the requester's own 1_000 comes back; the funders' 3_001 waits for them
+    svm.process(&ix::cancel_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(svm.lamports(&requester), before[0] + 1_000);
+    assert_eq!(svm.lamports(&prayer), prayer_rent() + 3_001);
+
+    // Anyone can pay a funder out; the Contribution's rent goes back with it
+    for (i, (funder, amount)) in funders.iter().zip([2_000, 1_001]).enumerate() {
+        svm.process(&ix::withdraw_contribution(&keeper, prayer_id, funder))
+            .unwrap();
+        assert_eq!(svm.lamports(funder), before[i + 1] + amount + contribution_rent());
+        assert!(svm.account(&contribution_pda(prayer_id, funder).0).is_none());
+    }
+    assert_eq!(svm.lamports(&prayer), prayer_rent());
+    assert_eq!(svm.prayer(prayer_id).num_funders, 0);
+
+    svm.process(&ix::close_prayer(&requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(svm.lamports(&requester), before[0] + 1_000 + prayer_rent());
+}
+
+#[test]
+fn resolve_dispute_splits_refund_with_funders() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let funder = svm.wallet();
+    let arbiter = svm.wallet();
+    let authority = svm.authority;
+    svm.process(&ix::set_arbiters(&authority, vec![arbiter]))
+        .unwrap();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    svm.fund(prayer_id, &funder, 3_000);
+    svm.process(&ix::answer_prayer(&claimer, prayer_id, [9u8; 32], vec![]))
+        .unwrap();
+    svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]))
+        .unwrap();
+    let before = balances(&svm, &[requester, claimer, funder]);
+
+    // Half of 4_000 is awarded; the 2_000 refund splits 1:3 like the bounty
+    svm.process(&ix::resolve_dispute(
+        &arbiter,
+        prayer_id,
+        &requester,
+        &claimer,
+        5_000,
+        None,
+        &[(claimer, claimer)],
+    ))
+    .unwrap();
+    assert_eq!(svm.lamports(&claimer), before[1] + 2_000);
+    assert_eq!(svm.lamports(&requester), before[0] + 500);
+    assert_eq!(svm.prayer(prayer_id).funder_refund, 1_500);
+
+    svm.process(&ix::withdraw_contribution(&funder, prayer_id, &funder))
+        .unwrap();
+    assert_eq!(svm.lamports(&funder), before[2] + 1_500 + contribution_rent());
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn expire_dispute_refunds_requester_and_funders() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let funder = svm.wallet();
+    let keeper = svm.wallet();
+    let arbiter = svm.wallet();
+    let authority = svm.authority;
+    svm.process(&ix::set_arbiters(&authority, vec![arbiter]))
+        .unwrap();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    svm.fund(prayer_id, &funder, 3_000);
+    svm.process(&ix::answer_prayer(&claimer, prayer_id, [9u8; 32], vec![]))
+        .unwrap();
+    svm.process(&ix::open_dispute(&requester, prayer_id, [0u8; 32]))
+        .unwrap();
+    let before = balances(&svm, &[requester, claimer, funder]);
+
+    // The arbiters never rule, so the whole bounty goes back 1:3
+    svm.now = svm.prayer(prayer_id).ruling_deadline + 1;
+    svm.process(&ix::expire_dispute(&keeper, &requester, prayer_id, None))
+        .unwrap();
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Cancelled);
+    assert_eq!(svm.lamports(&claimer), before[1]);
+    assert_eq!(svm.lamports(&requester), before[0] + 1_000);
+    assert_eq!(svm.prayer(prayer_id).funder_refund, 3_000);
+
+    svm.process(&ix::withdraw_contribution(&funder, prayer_id, &funder))
+        .unwrap();
+    assert_eq!(svm.lamports(&funder), before[2] + 3_000 + contribution_rent());
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent());
+}
+
+#[test]
+fn votes_finalize_before_review_window() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let funders = [svm.wallet(), svm.wallet()];
+    let keeper = svm.wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    svm.fund(prayer_id, &funders[0], 1_500);
+    svm.fund(prayer_id, &funders[1], 500);
+    svm.process(&ix::answer_prayer(&claimer, prayer_id, [9u8; 32], vec![]))
+        .unwrap();
+    let before = balances(&svm, &[claimer, funders[0]]);
+
+    // Funders hold 2_000 of the 3_000 bounty: without the requester they
+    // carry the vote once half the review window has passed
+    for funder in &funders {
+        svm.process(&ix::vote_confirm(funder, prayer_id)).unwrap();
+    }
+    let prayer = svm.prayer(prayer_id);
+    assert_eq!(prayer.confirm_votes, 2_000);
+    assert!(!prayer.funders_confirmed(svm.now));
+    svm.now = prayer.funder_vote_deadline() + 1;
+    assert!(svm.prayer(prayer_id).funders_confirmed(svm.now));
+    svm.process(&ix::finalize_prayer(
+        &keeper,
+        prayer_id,
+        &claimer,
+        None,
+        &[(claimer, claimer)],
+    ))
+    .unwrap();
+    assert_eq!(svm.lamports(&claimer), before[0] + 3_000);
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Confirmed);
+
+    // Nothing to refund, so withdrawing only returns the Contribution's rent
+    svm.process(&ix::withdraw_contribution(&keeper, prayer_id, &funders[0]))
+        .unwrap();
+    assert_eq!(svm.lamports(&funders[0]), before[1] + contribution_rent());
+}
+
+#[test]
+fn requester_vote_finalizes_at_once() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let claimer = svm.agent_wallet();
+    let funder = svm.wallet();
+    let prayer_id = svm.post(&requester, 1_000, 1);
+    svm.claim_prayer(prayer_id, &claimer);
+    svm.fund(prayer_id, &funder, 500);
+    svm.process(&ix::answer_prayer(&claimer, prayer_id, [9u8; 32], vec![]))
+        .unwrap();
+    let before = svm.lamports(&claimer);
+
+    // The requester's 1_000 of the 1_500 bounty is a majority on its own
+    svm.process(&ix::vote_confirm_requester(&requester, prayer_id))
+        .unwrap();
+    assert!(svm.prayer(prayer_id).funders_confirmed(svm.now));
+    svm.process(&ix::finalize_prayer(
+        &funder,
+        prayer_id,
+        &claimer,
+        None,
+        &[(claimer, claimer)],
+    ))
+    .unwrap();
+    assert_eq!(svm.lamports(&claimer), before + 1_500);
+}
+
+#[test]
+fn expire_with_funders_closes_once_withdrawn() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let funder = svm.wallet();
+    let keeper = svm.wallet();
+    let prayer_id = svm.post(&requester, 7_000, 1);
+    svm.fund(prayer_id, &funder, 3_000);
+    let before = balances(&svm, &[requester, funder, keeper]);
+
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None))
+        .unwrap();
+    assert!(svm.prayer(prayer_id).status == PrayerStatus::Expired);
+    assert_eq!(svm.lamports(&requester), before[0] + 7_000);
+    assert_eq!(svm.lamports(&prayer_pda(prayer_id).0), prayer_rent() + 3_000);
+
+    svm.process(&ix::withdraw_contribution(&keeper, prayer_id, &funder))
+        .unwrap();
+    assert_eq!(svm.lamports(&funder), before[1] + 3_000 + contribution_rent());
+
+    svm.process(&ix::expire_prayer(&keeper, &requester, prayer_id, None))
+        .unwrap();
+    assert_eq!(
+        svm.lamports(&requester),
+        before[0] + 7_000 + prayer_rent() - KEEPER_FEE_LAMPORTS
+    );
+    assert_eq!(svm.lamports(&keeper), before[2] + KEEPER_FEE_LAMPORTS);
+}
+
+#[test]
+fn close_agent_returns_rent() {
+    let mut svm = Svm::new();
+    let wallet = svm.agent_wallet();
+    let agent = agent_pda(&wallet).0;
+    let agent_lamports = svm.lamports(&agent);
+    let before = svm.lamports(&wallet);
+
+    svm.process(&ix::close_agent(&wallet)).unwrap();
+    assert_eq!(svm.lamports(&wallet), before + agent_lamports);
+    assert!(svm.account(&agent).is_none());
+    assert_eq!(svm.chain().total_agents, 0);
+}
@@ -0,0 +1,160 @@
+//! Token bounties end to end: the escrow is funded, paid out and refunded
+//! through the SPL Token and Token-2022 programs themselves.
+#![cfg(sbf_program_built)]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{token, token_2022};
+use chorus_prayers::PrayerStatus;
+use chorus_prayers_client::instructions as ix;
+use chorus_prayers_client::pda::escrow_pda;
+use chorus_prayers_client::TokenBounty;
+use common::{post_args, Svm, TTL};
+
+const TOKEN_PROGRAMS: [Pubkey; 2] = [token::ID, token_2022::ID];
+
+#[test]
+fn confirm_splits_token_bounty_and_close_returns_dust() {
+    for token_program in TOKEN_PROGRAMS {
+        let mut svm = Svm::new();
+        let requester = svm.agent_wallet();
+        let token = svm.token_bounty(&requester, &token_program, 1_001);
+        // The split's dust stays in escrow under the default Requester policy
+        let prayer_id = svm.post_with(&requester, post_args(1_001, 2), Some(&token));
+        let (escrow, _) = escrow_pda(prayer_id);
+        assert_eq!(svm.token_balance(&escrow), 1_001);
+        assert_eq!(svm.token_balance(&token.requester_token_account), 0);
+
+        let claimers = [svm.agent_wallet(), svm.agent_wallet()];
+        let mut payouts = Vec::new();
+        for claimer in &claimers {
+            svm.claim_prayer(prayer_id, claimer);
+            let account = svm.token_account(claimer, &token.mint, &token_program);
+            payouts.push((*claimer, account));
+        }
+        svm.process(&ix::answer_prayer(
+            &claimers[0],
+            prayer_id,
+            [9u8; 32],
+            vec![],
+        ))
+        .unwrap();
+        svm.process(&ix::confirm_prayer(
+            &requester,
+            prayer_id,
+            &claimers[0],
+            Some(&token),
+            &payouts,
+        ))
+        .unwrap();
+
+        for (_, account) in &payouts {
+            assert_eq!(svm.token_balance(account), 500);
+        }
+        assert_eq!(svm.token_balance(&escrow), 1);
+        assert!(svm.prayer(prayer_id).status == PrayerStatus::Confirmed);
+
+        for claimer in &claimers {
+            svm.process(&ix::unclaim_prayer(&requester, prayer_id, claimer))
+                .unwrap();
+        }
+        svm.process(&ix::close_prayer(&requester, prayer_id, Some(&token)))
+            .unwrap();
+        assert_eq!(svm.token_balance(&token.requester_token_account), 1);
+        assert!(svm.account(&escrow).is_none());
+    }
+}
+
+#[test]
+fn cancel_refunds_token_bounty_with_top_up() {
+    for token_program in TOKEN_PROGRAMS {
+        let mut svm = Svm::new();
+        let requester = svm.agent_wallet();
+        let token = svm.token_bounty(&requester, &token_program, 1_500);
+        let prayer_id = svm.post_with(&requester, post_args(1_000, 1), Some(&token));
+        let (escrow, _) = escrow_pda(prayer_id);
+
+        svm.process(&ix::top_up_bounty(&requester, prayer_id, 500, Some(&token)))
+            .unwrap();
+        assert_eq!(svm.token_balance(&escrow), 1_500);
+        assert_eq!(svm.prayer(prayer_id).reward_lamports, 1_500);
+
+        svm.process(&ix::cancel_prayer(&requester, prayer_id, Some(&token)))
+            .unwrap();
+        assert_eq!(svm.token_balance(&token.requester_token_account), 1_500);
+        svm.process(&ix::close_prayer(&requester, prayer_id, Some(&token)))
+            .unwrap();
+        assert!(svm.account(&escrow).is_none());
+    }
+}
+
+#[test]
+fn empty_token_prayer_escrows_top_ups() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let token = svm.token_bounty(&requester, &token::ID, 500);
+    let prayer_id = svm.post_with(&requester, post_args(0, 1), Some(&token));
+    let (escrow, _) = escrow_pda(prayer_id);
+    assert_eq!(svm.token_balance(&escrow), 0);
+    assert!(svm.prayer(prayer_id).is_token_bounty());
+
+    svm.process(&ix::top_up_bounty(&requester, prayer_id, 500, Some(&token)))
+        .unwrap();
+    assert_eq!(svm.token_balance(&escrow), 500);
+    assert_eq!(svm.prayer(prayer_id).reward_lamports, 500);
+}
+
+#[test]
+fn expire_refunds_token_bounty() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let keeper = svm.wallet();
+    let (prayer_id, token) = svm.post_token(&requester, 1_000);
+    let (escrow, _) = escrow_pda(prayer_id);
+
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(
+        &keeper,
+        &requester,
+        prayer_id,
+        Some(&token),
+    ))
+    .unwrap();
+    assert_eq!(svm.token_balance(&token.requester_token_account), 1_000);
+    assert!(svm.account(&escrow).is_none());
+}
+
+#[test]
+fn fee_mint_escrow_closes_with_withheld_fees() {
+    let mut svm = Svm::new();
+    let requester = svm.agent_wallet();
+    let keeper = svm.wallet();
+    // 1% withheld on every transfer, including the deposit into escrow
+    let mint = svm.fee_mint(100);
+    let token = TokenBounty::new(mint, token_2022::ID, &requester);
+    svm.token_account(&requester, &mint, &token_2022::ID);
+    svm.mint_to(&mint, &token.requester_token_account, 20_000);
+
+    let cancelled = svm.post_with(&requester, post_args(10_000, 1), Some(&token));
+    let expired = svm.post_with(&requester, post_args(10_000, 1), Some(&token));
+    for prayer_id in [cancelled, expired] {
+        assert_eq!(svm.prayer(prayer_id).reward_lamports, 9_900);
+        assert_eq!(svm.token_balance(&escrow_pda(prayer_id).0), 9_900);
+    }
+
+    svm.process(&ix::cancel_prayer(&requester, cancelled, Some(&token)))
+        .unwrap();
+    svm.process(&ix::close_prayer(&requester, cancelled, Some(&token)))
+        .unwrap();
+    assert!(svm.account(&escrow_pda(cancelled).0).is_none());
+
+    svm.now += TTL + 1;
+    svm.process(&ix::expire_prayer(&keeper, &requester, expired, Some(&token)))
+        .unwrap();
+    assert!(svm.account(&escrow_pda(expired).0).is_none());
+
+    // Both refunds pay the fee again on the way back
+    assert_eq!(svm.token_balance(&token.requester_token_account), 2 * (9_900 - 99));
+    assert_eq!(svm.agent(&requester).open_prayers, 0);
+}
@@ -14,17 +14,24 @@
  *   chorus pray show <id>
  *   chorus pray claims <id>                           # List all claims for a prayer
  *   chorus pray claim <id>
+ *   chorus pray approve <id> <wallet>                 # Approve a pending claim (approval mode)
  *   chorus pray deliver <id> [--claimer <wallet>]     # Deliver to one or all claimers
  *   chorus pray answer <id> "SOFR is 4.55%"
  *   chorus pray confirm <id>
  *   chorus pray cancel <id>
+ *   chorus pray extend <id> <seconds>                 # Push an open prayer's expiry back
+ *   chorus pray topup <id> <SOL>                      # Raise an open prayer's bounty
+ *   chorus pray fund <id> <SOL>                       # Chip in to someone else's prayer
+ *   chorus pray vote <id>                             # Vote to accept an answer you funded or posted
+ *   chorus pray withdraw <id> [--funder <wallet>]     # Take back a funder's refund share
  *   chorus pray unclaim <id> [--claimer <wallet>]     # Unclaim own or expired claim
  *   chorus pray agent                                 # Show my on-chain agent
  *   chorus pray register "oberlin" "macro analysis"   # Register (auto-derives encryption key)
+ *   chorus pray deregister                            # Close my agent, reclaim rent
  *   chorus pray chain                                 # Show prayer chain stats
  */
 
-import { ChorusPrayerClient, PrayerType, PrayerAccount, ClaimAccount, getPrayerChainPDA, getAgentPDA, getPrayerPDA, getClaimPDA } from "./solana.js";
+import { ChorusPrayerClient, PrayerType, RemainderPolicy, ClaimRequirements, OPTION_COMPETITION, OPTION_APPROVAL, OPTION_REQUIRE_DELIVERY, PrayerAccount, ClaimAccount, getPrayerChainPDA, getAgentPDA, getPrayerPDA, getClaimPDA, DEFAULT_REVIEW_SECONDS, KEEPER_FEE_LAMPORTS } from "./solana.js";
 import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { createHash } from "crypto";
 import * as fs from "fs";
@@ -106,6 +113,14 @@ function formatSOL(lamports: number): string {
   return `${(lamports / LAMPORTS_PER_SOL).toFixed(4)} SOL`;
 }
 
+function formatRequirements(r: ClaimRequirements): string {
+  const parts: string[] = [];
+  if (r.minReputation) parts.push(`reputation ≥ ${r.minReputation}`);
+  if (r.minConfirmed) parts.push(`${r.minConfirmed}+ confirmed`);
+  if (r.minAgentAgeSeconds) parts.push(`agent ≥ ${(r.minAgentAgeSeconds / 86400).toFixed(1)}d old`);
+  return parts.join(", ");
+}
+
 function shortKey(key: PublicKey): string {
   const s = key.toBase58();
   if (s === "11111111111111111111111111111111") return "(none)";
@@ -145,6 +160,15 @@ async function main() {
       console.log(`  Total Prayers:  ${chain.totalPrayers}`);
       console.log(`  Total Answered: ${chain.totalAnswered}`);
       console.log(`  Total Agents:   ${chain.totalAgents}`);
+      console.log(`  Claim Timeout:  ${chain.config.claimTimeoutSeconds}s`);
+      console.log(`  Max Claimers:   ${chain.config.maxClaimers}`);
+      console.log(`  Max TTL:        ${chain.config.maxTtlSeconds}s`);
+      console.log(`  Max Review:     ${chain.config.maxReviewSeconds}s`);
+      console.log(`  Ruling Window:  ${chain.config.disputeTimeoutSeconds}s`);
+      console.log(`  Reputation:     +${chain.config.answerReputation} answer, +${chain.config.confirmReputation} confirm`);
+      if (chain.paused) {
+        console.log(`  Paused:         0x${chain.paused.toString(16)}`);
+      }
       console.log(`  RPC:            ${RPC_URL}`);
       console.log(`  Encryption:     X25519 + XSalsa20-Poly1305`);
       console.log("");
@@ -192,6 +216,29 @@ async function main() {
       break;
     }
 
+    case "update": {
+      const name = getArgValue("--name") ?? undefined;
+      const skills = getArgValue("--skills") ?? undefined;
+      const syncKey = args.includes("--sync-key");
+      if (name === undefined && skills === undefined && !syncKey) {
+        console.error('Usage: update [--name "<name>"] [--skills "<skills>"] [--sync-key]');
+        process.exit(1);
+      }
+      console.log(`\n🤖 Updating agent...`);
+      try {
+        const tx = await client.updateAgent({
+          name,
+          skills,
+          encryptionKey: syncKey ? client.getEncryptionPublicKey() : undefined,
+        });
+        console.log(`  ✓ Updated (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
     case "agent": {
       const wallet = args[1] ? new PublicKey(args[1]) : client.wallet;
       const agent = await client.getAgent(wallet);
@@ -206,20 +253,34 @@ async function main() {
       console.log(`  Wallet:           ${shortKey(agent.wallet)}`);
       console.log(`  Name:             ${agent.name}`);
       console.log(`  Skills:           ${agent.skills}`);
-      console.log(`  🔐 Encryption:    ${formatEncryptionKey(agent.encryptionKey)}`);
+      console.log(`  🔐 Encryption:    ${formatEncryptionKey(agent.encryptionKey)} (epoch ${agent.keyEpoch})`);
       console.log(`  Reputation:       ${agent.reputation}`);
       console.log(`  Prayers Posted:   ${agent.prayersPosted}`);
       console.log(`  Prayers Answered: ${agent.prayersAnswered}`);
       console.log(`  Prayers Confirmed: ${agent.prayersConfirmed}`);
+      console.log(`  Open Claims:      ${agent.openClaims}`);
+      console.log(`  Open Prayers:     ${agent.openPrayers}`);
       console.log(`  Registered:       ${formatTime(agent.registeredAt)}`);
       console.log("");
       break;
     }
 
+    case "deregister": {
+      console.log(`\n🤖 Closing agent...`);
+      try {
+        const tx = await client.closeAgent();
+        console.log(`  ✓ Agent closed, rent reclaimed (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
     case "post": {
       const content = args[1];
       if (!content) {
-        console.error('Usage: post "<content>" [--type knowledge] [--bounty 0.01] [--ttl 86400] [--claimers 1]');
+        console.error('Usage: post "<content>" [--type knowledge] [--bounty 0.01] [--ttl 86400] [--claimers 1] [--review 259200] [--remainder requester] [--competition] [--approval] [--require-delivery] [--invite <wallet,...>] [--min-rep 0] [--min-confirmed 0] [--min-age 0] [--stake 0] [--claim-timeout 0]');
         process.exit(1);
       }
 
@@ -227,6 +288,29 @@ async function main() {
       const bountySOL = parseFloat(getArgValue("--bounty") || "0");
       const ttl = parseInt(getArgValue("--ttl") || "86400");
       const maxClaimers = parseInt(getArgValue("--claimers") || "1");
+      const review = parseInt(getArgValue("--review") || String(DEFAULT_REVIEW_SECONDS));
+      const remainder = getArgValue("--remainder") || "requester";
+      const remainderPolicy = ({
+        "requester": RemainderPolicy.Requester,
+        "answerer": RemainderPolicy.Answerer,
+        "round-robin": RemainderPolicy.RoundRobin,
+      } as Record<string, RemainderPolicy>)[remainder];
+      if (remainderPolicy === undefined) {
+        console.error("--remainder must be requester, answerer or round-robin");
+        process.exit(1);
+      }
+      const competition = args.includes("--competition");
+      const approval = args.includes("--approval");
+      const requireDelivery = args.includes("--require-delivery");
+      const inviteArg = getArgValue("--invite");
+      const invited = inviteArg ? inviteArg.split(",").map((w) => new PublicKey(w.trim())) : [];
+      const requirements = {
+        minReputation: parseInt(getArgValue("--min-rep") || "0"),
+        minConfirmed: parseInt(getArgValue("--min-confirmed") || "0"),
+        minAgentAgeSeconds: parseInt(getArgValue("--min-age") || "0"),
+      };
+      const stakeSOL = parseFloat(getArgValue("--stake") || "0");
+      const claimTimeout = parseInt(getArgValue("--claim-timeout") || "0");
       const bountyLamports = Math.round(bountySOL * LAMPORTS_PER_SOL);
 
       console.log("");
@@ -236,6 +320,17 @@ async function main() {
       console.log(`  Bounty:      ${bountySOL > 0 ? `${bountySOL} SOL` : "none"}`);
       console.log(`  TTL:         ${ttl}s (${(ttl / 3600).toFixed(1)}h)`);
       console.log(`  Max Claimers: ${maxClaimers}${maxClaimers > 1 ? " (collaboration)" : " (solo)"}`);
+      console.log(`  Review:      ${review}s (${(review / 3600).toFixed(1)}h to confirm before auto-finalize)`);
+      if (maxClaimers > 1) console.log(`  Remainder:   ${remainder}`);
+      if (competition) console.log(`  Mode:        competition (every claimer answers, you rank them)`);
+      if (approval) console.log(`  Approval:    claims wait for you to approve them`);
+      if (requireDelivery) console.log(`  Delivery:    only claimers you deliver to can answer`);
+      if (invited.length > 0) console.log(`  Invited:     ${invited.map(shortKey).join(", ")}`);
+      if (requirements.minReputation || requirements.minConfirmed || requirements.minAgentAgeSeconds) {
+        console.log(`  Requires:    ${formatRequirements(requirements)}`);
+      }
+      if (stakeSOL > 0) console.log(`  Stake:       ${stakeSOL} SOL per claimer (slashed if they abandon the claim)`);
+      if (claimTimeout > 0) console.log(`  Claim Timeout: ${claimTimeout}s without a heartbeat`);
       console.log(`  🔐 Only hash goes on-chain. Content stored locally.`);
 
       try {
@@ -245,6 +340,15 @@ async function main() {
           bountyLamports,
           ttl,
           maxClaimers,
+          review,
+          undefined,
+          remainderPolicy,
+          (competition ? OPTION_COMPETITION : 0) | (approval ? OPTION_APPROVAL : 0)
+            | (requireDelivery ? OPTION_REQUIRE_DELIVERY : 0),
+          invited,
+          requirements,
+          Math.round(stakeSOL * LAMPORTS_PER_SOL),
+          claimTimeout,
         );
         console.log(`  ✓ Prayer #${prayerId} posted (tx: ${tx.slice(0, 16)}...)`);
         console.log(`  → Run 'deliver ${prayerId}' after someone claims it`);
@@ -380,7 +484,22 @@ async function main() {
       console.log(`  Type:         ${formatType(prayer.prayerType)}`);
       console.log(`  Requester:    ${shortKey(prayer.requester)}`);
       console.log(`  Bounty:       ${formatSOL(prayer.rewardLamports)}`);
+      if (prayer.numFunders > 0 || prayer.fundedLamports > 0) {
+        console.log(`  Funders:      ${prayer.numFunders} (${formatSOL(prayer.fundedLamports)} of the bounty)`);
+        if (prayer.confirmVotes > 0) console.log(`  Votes:        ${formatSOL(prayer.confirmVotes)} voted to confirm`);
+      }
       console.log(`  Claimers:     ${prayer.numClaimers}/${prayer.maxClaimers}${prayer.maxClaimers > 1 ? " (collaboration)" : " (solo)"}`);
+      if (prayer.maxClaimers > 1) console.log(`  Remainder:    ${String(prayer.remainderPolicy)}`);
+      if (prayer.options & OPTION_COMPETITION) console.log(`  Mode:         competition (${prayer.numAnswers} answer${prayer.numAnswers === 1 ? "" : "s"})`);
+      if (prayer.options & OPTION_APPROVAL) console.log(`  Approval:     required (${prayer.numPending} pending)`);
+      if (prayer.options & OPTION_REQUIRE_DELIVERY) console.log(`  Delivery:     required before answering`);
+      if (prayer.invited.length > 0) console.log(`  Invited:      ${prayer.invited.map(shortKey).join(", ")}`);
+      const { minReputation, minConfirmed, minAgentAgeSeconds } = prayer.requirements;
+      if (minReputation || minConfirmed || minAgentAgeSeconds) {
+        console.log(`  Requires:     ${formatRequirements(prayer.requirements)}`);
+      }
+      if (prayer.claimStake > 0) console.log(`  Claim Stake:  ${formatSOL(prayer.claimStake)}`);
+      console.log(`  Claim Timeout: ${prayer.claimTimeoutSeconds}s (${(prayer.claimTimeoutSeconds / 3600).toFixed(1)}h without a heartbeat)`);
       console.log(`  Created:      ${formatTime(prayer.createdAt)}`);
       console.log(`  Expires:      ${formatTime(prayer.expiresAt)}`);
       console.log(`  Content Hash: ${hashToHex(prayer.contentHash)}`);
@@ -399,7 +518,8 @@ async function main() {
         const claims = await client.getClaimsForPrayer(id);
         for (const claim of claims) {
           const delivered = claim.contentDelivered ? "✅ delivered" : "⏳ pending delivery";
-          console.log(`    🤝 ${shortKey(claim.claimer)} — claimed ${formatTime(claim.claimedAt)} — ${delivered}`);
+          const deadline = formatTime(claim.lastHeartbeat + prayer.claimTimeoutSeconds);
+          console.log(`    🤝 ${shortKey(claim.claimer)} — claimed ${formatTime(claim.claimedAt)} — ${delivered} — held until ${deadline}`);
         }
         if (claims.length === 0) {
           console.log(`    (could not enumerate — use 'claims ${id}' with known wallets)`);
@@ -421,6 +541,7 @@ async function main() {
           console.log("  Answer: 🔒 encrypted (not in local cache)");
         }
         console.log(`  Fulfilled:    ${formatTime(prayer.fulfilledAt)}`);
+        if (prayer.rulingDeadline > 0) console.log(`  Ruling Due:   ${formatTime(prayer.rulingDeadline)}`);
       }
       console.log("");
       break;
@@ -448,9 +569,11 @@ async function main() {
         console.log("  No claims found.");
       } else {
         for (const claim of claims) {
-          const delivered = claim.contentDelivered ? "✅ content delivered" : "⏳ awaiting delivery";
+          const delivered = !claim.approved ? "🕓 pending approval"
+            : claim.contentDelivered ? "✅ content delivered" : "⏳ awaiting delivery";
           console.log(`  🤝 ${claim.claimer.toBase58()}`);
-          console.log(`     Claimed: ${formatTime(claim.claimedAt)} | ${delivered}`);
+          const weight = prayer.weighted ? ` | weight ${(claim.weightBps / 100).toFixed(2)}%` : "";
+          console.log(`     Claimed: ${formatTime(claim.claimedAt)} | ${delivered}${weight}`);
         }
       }
       console.log("");
@@ -466,8 +589,36 @@ async function main() {
       console.log(`\n🤝 Claiming prayer #${id}...`);
       try {
         const tx = await client.claimPrayer(id);
-        console.log(`  ✓ Claimed (tx: ${tx.slice(0, 16)}...)`);
-        console.log(`  Waiting for requester to deliver encrypted content...`);
+        const claim = await client.getClaim(id, client.wallet);
+        if (claim && !claim.approved) {
+          console.log(`  ✓ Applied (tx: ${tx.slice(0, 16)}...)`);
+          console.log(`  Waiting for requester to approve your claim...`);
+        } else {
+          console.log(`  ✓ Claimed (tx: ${tx.slice(0, 16)}...)`);
+          console.log(`  Waiting for requester to deliver encrypted content...`);
+        }
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "heartbeat": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: heartbeat <prayer-id>");
+        process.exit(1);
+      }
+      console.log(`\n💓 Sending heartbeat for prayer #${id}...`);
+      try {
+        const tx = await client.heartbeatClaim(id);
+        console.log(`  ✓ Claim kept alive (tx: ${tx.slice(0, 16)}...)`);
+        const prayer = await client.getPrayer(id);
+        const claim = await client.getClaim(id, client.wallet);
+        if (prayer && claim) {
+          console.log(`  Held until ${formatTime(claim.lastHeartbeat + prayer.claimTimeoutSeconds)}`);
+        }
       } catch (err: any) {
         console.error(`  ✗ ${err.message}`);
       }
@@ -496,6 +647,50 @@ async function main() {
       break;
     }
 
+    case "rank": {
+      const id = parseInt(args[1]);
+      const pairs = args.slice(2).map((arg) => arg.split("="));
+      if (isNaN(id) || pairs.length === 0 || pairs.some((p) => p.length !== 2 || isNaN(parseInt(p[1])))) {
+        console.error("Usage: rank <prayer-id> <wallet>=<bps> [<wallet>=<bps> ...]  (best first)");
+        process.exit(1);
+      }
+      console.log(`\n🏆 Ranking answers for prayer #${id}...`);
+      try {
+        const ranking = pairs.map(([wallet, bps]) => ({ answerer: new PublicKey(wallet), bps: parseInt(bps) }));
+        const tx = await client.selectWinners(id, ranking);
+        ranking.forEach((r, i) => {
+          console.log(`  #${i + 1} ${shortKey(r.answerer)}  ${(r.bps / 100).toFixed(2)}%`);
+        });
+        console.log(`  ✓ Winners selected — confirm to pay out (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "weights": {
+      const id = parseInt(args[1]);
+      const pairs = args.slice(2).map((arg) => arg.split("="));
+      if (isNaN(id) || pairs.length === 0 || pairs.some((p) => p.length !== 2 || isNaN(parseInt(p[1])))) {
+        console.error("Usage: weights <prayer-id> <wallet>=<bps> [<wallet>=<bps> ...]");
+        process.exit(1);
+      }
+      console.log(`\n⚖️  Setting contribution weights for prayer #${id}...`);
+      try {
+        const weights = pairs.map(([wallet, bps]) => ({ claimer: new PublicKey(wallet), bps: parseInt(bps) }));
+        const tx = await client.setClaimWeights(id, weights);
+        for (const w of weights) {
+          console.log(`  ${shortKey(w.claimer)}  ${(w.bps / 100).toFixed(2)}%`);
+        }
+        console.log(`  ✓ Weights set — confirm pays out in proportion (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
     case "confirm": {
       const id = parseInt(args[1]);
       if (isNaN(id)) {
@@ -513,6 +708,40 @@ async function main() {
       break;
     }
 
+    case "finalize": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: finalize <prayer-id>");
+        process.exit(1);
+      }
+      console.log(`\n⏱️  Finalizing prayer #${id} (review window elapsed)...`);
+      try {
+        const tx = await client.finalizePrayer(id);
+        console.log(`  ✓ Finalized — bounty distributed to all claimers (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "settle": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: settle <prayer-id>");
+        process.exit(1);
+      }
+      console.log(`\n🏁 Settling competition #${id} (selection deadline passed)...`);
+      try {
+        const tx = await client.settleCompetition(id);
+        console.log(`  ✓ Settled — bounty split equally among every answer (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
     case "cancel": {
       const id = parseInt(args[1]);
       if (isNaN(id)) {
@@ -530,6 +759,123 @@ async function main() {
       break;
     }
 
+    case "extend": {
+      const id = parseInt(args[1]);
+      const seconds = parseInt(args[2]);
+      if (isNaN(id) || isNaN(seconds)) {
+        console.error("Usage: extend <prayer-id> <seconds>");
+        process.exit(1);
+      }
+      console.log(`\n⏳ Extending prayer #${id} by ${seconds}s...`);
+      try {
+        const tx = await client.extendPrayer(id, seconds);
+        const prayer = await client.getPrayer(id);
+        console.log(`  ✓ Extended — now expires ${prayer ? new Date(prayer.expiresAt * 1000).toISOString() : "later"} (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "topup": {
+      const id = parseInt(args[1]);
+      const sol = parseFloat(args[2]);
+      if (isNaN(id) || isNaN(sol)) {
+        console.error("Usage: topup <prayer-id> <SOL>");
+        process.exit(1);
+      }
+      console.log(`\n💰 Adding ${sol} SOL to prayer #${id}...`);
+      try {
+        const tx = await client.topUpBounty(id, Math.round(sol * LAMPORTS_PER_SOL));
+        const prayer = await client.getPrayer(id);
+        console.log(`  ✓ Topped up — bounty now ${prayer ? formatSOL(prayer.rewardLamports) : "updated"} (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "fund": {
+      const id = parseInt(args[1]);
+      const sol = parseFloat(args[2]);
+      if (isNaN(id) || isNaN(sol)) {
+        console.error("Usage: fund <prayer-id> <SOL>");
+        process.exit(1);
+      }
+      console.log(`\n🤲 Funding prayer #${id} with ${sol} SOL...`);
+      try {
+        const tx = await client.fundPrayer(id, Math.round(sol * LAMPORTS_PER_SOL));
+        const prayer = await client.getPrayer(id);
+        console.log(`  ✓ Funded — bounty now ${prayer ? formatSOL(prayer.rewardLamports) : "updated"} (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "vote": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: vote <prayer-id>");
+        process.exit(1);
+      }
+      console.log(`\n🗳️  Voting to confirm prayer #${id}...`);
+      try {
+        const tx = await client.voteConfirm(id);
+        const prayer = await client.getPrayer(id);
+        const tally = prayer ? `${formatSOL(prayer.confirmVotes)} of ${formatSOL(prayer.rewardLamports)}` : "updated";
+        console.log(`  ✓ Voted — ${tally} in favour (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "withdraw": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: withdraw <prayer-id> [--funder <wallet>]");
+        process.exit(1);
+      }
+      const funderArg = getArgValue("--funder");
+      const funderWallet = funderArg ? new PublicKey(funderArg) : undefined;
+      const target = funderWallet ? shortKey(funderWallet) : "self";
+      console.log(`\n💸 Withdrawing contribution to prayer #${id} (${target})...`);
+      try {
+        const tx = await client.withdrawContribution(id, funderWallet);
+        console.log(`  ✓ Withdrawn — refund share and rent returned to the funder (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "approve":
+    case "reject": {
+      const id = parseInt(args[1]);
+      if (isNaN(id) || !args[2]) {
+        console.error(`Usage: ${command} <prayer-id> <claimer-wallet>`);
+        process.exit(1);
+      }
+      const claimer = new PublicKey(args[2]);
+      console.log(`\n${command === "approve" ? "✅ Approving" : "🚫 Rejecting"} ${shortKey(claimer)} on prayer #${id}...`);
+      try {
+        const tx = command === "approve"
+          ? await client.approveClaim(id, claimer)
+          : await client.rejectClaim(id, claimer);
+        console.log(`  ✓ ${command === "approve" ? "Approved — deliver content next" : "Rejected, claim rent returned"} (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
     case "unclaim": {
       const id = parseInt(args[1]);
       if (isNaN(id)) {
@@ -567,6 +913,44 @@ async function main() {
       break;
     }
 
+    case "sweep": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: sweep <prayer-id>");
+        process.exit(1);
+      }
+      console.log(`\n🧹 Sweeping claims on prayer #${id}...`);
+      try {
+        const txs = await client.sweepClaims(id);
+        if (txs.length === 0) {
+          console.log("  No claims left to sweep");
+        } else {
+          console.log(`  ✓ Swept — rent and stakes returned to claimers (${txs.length} tx)`);
+        }
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
+    case "expire": {
+      const id = parseInt(args[1]);
+      if (isNaN(id)) {
+        console.error("Usage: expire <prayer-id>");
+        process.exit(1);
+      }
+      console.log(`\n⌛ Expiring prayer #${id}...`);
+      try {
+        const tx = await client.expirePrayer(id);
+        console.log(`  ✓ Expired — bounty refunded, ${formatSOL(KEEPER_FEE_LAMPORTS)} keeper fee earned (tx: ${tx.slice(0, 16)}...)`);
+      } catch (err: any) {
+        console.error(`  ✗ ${err.message}`);
+      }
+      console.log("");
+      break;
+    }
+
     default:
       console.log(`
 🙏 Prayer Chain CLI — Private by Default
@@ -577,25 +961,55 @@ Commands:
   chain                              Show prayer chain stats
   init                               Initialize the prayer chain
   register "<name>" "<skills>"       Register (auto-derives encryption key)
+  update [options]                   Update your agent profile
+    --name "<name>"                    New display name
+    --skills "<skills>"                New skills list
+    --sync-key                         Publish this wallet's encryption key (rotates it)
   agent [wallet]                     Show agent profile + encryption key
+  deregister                         Close your agent (no open claims or prayers)
 
   post "<content>" [options]         Post a private prayer (hash-only on-chain)
     --type <type>                      knowledge|compute|review|signal|collaboration
     --bounty <SOL>                     SOL bounty (e.g. 0.01)
     --ttl <seconds>                    Time to live (default 86400)
     --claimers <n>                     Max collaborators (1-10, default 1)
+    --review <seconds>                 Time to confirm an answer (default 259200)
+    --remainder <policy>               Uneven split remainder: requester|answerer|round-robin
+    --competition                      Every claimer answers; you rank the answers
+    --approval                         Claims stay pending until you approve them
+    --require-delivery                 Only claimers you deliver content to can answer
+    --invite <wallet,...>              Only these wallets can claim (max 5)
+    --min-rep <n>                      Minimum claimer reputation
+    --min-confirmed <n>                Minimum confirmed prayers for claimers
+    --min-age <seconds>                Minimum time since the claimer registered
+    --stake <SOL>                      Deposit each claimer puts up (slashed on timeout)
+    --claim-timeout <seconds>          Claim lifetime without a heartbeat (default: protocol)
 
   list [--status <s>] [--limit <n>]  List prayers
   show <id>                          Show prayer details + claims
   claims <id>                        List all claims for a prayer
 
   claim <id>                         Claim a prayer (creates Claim PDA)
+  heartbeat <id>                     Keep your claim alive (restarts its timeout)
+  approve <id> <wallet>              Approve a pending claim (approval mode)
+  reject <id> <wallet>               Reject a pending claim, returning its rent
   deliver <id> [--claimer <wallet>]  Deliver encrypted content (one or all)
   answer <id> "<answer>"             Answer with encrypted reply
+  rank <id> <wallet>=<bps> ...       Rank competing answers, best first (sums to 10000)
+  weights <id> <wallet>=<bps> ...    Weight the split by contribution (sums to 10000)
   confirm <id>                       Confirm — bounty splits among all claimers
+  finalize <id>                      Pay out an unreviewed answer after the review window (or a majority vote)
+  settle <id>                        Split an unranked competition among its answers after the selection deadline
   cancel <id>                        Cancel an open prayer (0 claims only)
-  unclaim <id> [--claimer <wallet>]  Remove a claim (self or expired)
-  close <id>                         Close resolved prayer, reclaim rent
+  extend <id> <seconds>              Push an open prayer's expiry back (within the max TTL)
+  topup <id> <SOL>                   Add SOL to an open prayer's bounty
+  fund <id> <SOL>                    Add SOL to someone else's prayer (refunded pro-rata)
+  vote <id>                          Vote to accept the answer on a prayer you funded or posted
+  withdraw <id> [--funder <wallet>]  Return a funder's refund share once the prayer resolves
+  unclaim <id> [--claimer <wallet>]  Remove a claim (self, expired, or resolved prayer)
+  close <id>                         Release claims, close resolved prayer, reclaim rent
+  sweep <id>                         Close every claim on a resolved prayer, refunding claimers
+  expire <id>                        Refund and close anyone's expired prayer for a keeper fee
 
 Privacy:
   🔐 No plaintext ever touches the blockchain
@@ -7,6 +7,9 @@
  * 
  * Supports multi-claimer collaboration: prayers can accept 1-10 claimers
  * who work together. Bounty splits equally among all claimers on confirm.
+ *
+ * Bounties are native SOL by default, or any SPL Token / Token-2022 mint
+ * escrowed in a token account owned by the prayer PDA.
  */
 
 import { Program, AnchorProvider, web3, Wallet } from "@coral-xyz/anchor";
@@ -26,19 +29,54 @@ import {
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
 
-// Program ID (deployed to devnet)
+// Program ID (localnet). The devnet program at this ID predates the current
+// account layouts; replace this with the fresh ID once redeployed (README, "Deploying").
 export const PROGRAM_ID = new PublicKey("Af61jGnh2AceK3E8FAxCh9j7Jt6JWtJz6PUtbciDjVJS");
 
 // Max plaintext size that fits in a Solana transaction after encryption overhead
 // Encrypted blob = plaintext + 40 bytes (24 nonce + 16 Poly1305 tag)
-// deliver_content (3 accounts): ~942 char max
+// deliver_content (4 accounts): ~910 char max
 // answer_prayer (5 accounts): ~842 char max
 export const MAX_CONTENT_LENGTH = 900;  // Conservative limit for deliver_content
 export const MAX_ANSWER_LENGTH = 800;   // Conservative limit for answer_prayer
 
-// Max collaborators per prayer (matches on-chain MAX_CLAIMERS_LIMIT)
+// Max collaborators per prayer (on-chain default; live limit is chain.config.maxClaimers)
 export const MAX_CLAIMERS = 10;
 
+// Max wallets a prayer's invite list can hold
+export const MAX_INVITED = 5;
+
+// Requester's window to confirm or dispute an answer before anyone can finalize
+export const DEFAULT_REVIEW_SECONDS = 259_200;  // 3 days
+export const MAX_REVIEW_SECONDS = 2_592_000;    // 30 days (on-chain default cap)
+
+// Claims closed per sweep_claims transaction (three accounts each)
+const SWEEP_BATCH = 6;
+
+// Paid from an expired prayer's rent to whoever closes it with expirePrayer()
+export const KEEPER_FEE_LAMPORTS = 100_000;
+
+// Token programs for SPL bounties
+export const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+export const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+export const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+// Pause bits for PrayerChain.paused, one per instruction family.
+// Refund paths (cancel, unclaim, reject, close, expire) are never paused.
+export const PAUSE_REGISTER = 1 << 0;  // register_agent
+export const PAUSE_POST = 1 << 1;      // post_prayer
+export const PAUSE_CLAIM = 1 << 2;     // claim_prayer, approve_claim
+export const PAUSE_ANSWER = 1 << 3;    // deliver_content, answer_prayer, submit_answer
+export const PAUSE_CONFIRM = 1 << 4;   // set_claim_weights, select_winners, confirm_prayer, finalize_prayer
+export const PAUSE_DISPUTE = 1 << 5;   // open_dispute, resolve_dispute
+export const PAUSE_ALL =
+  PAUSE_REGISTER | PAUSE_POST | PAUSE_CLAIM | PAUSE_ANSWER | PAUSE_CONFIRM | PAUSE_DISPUTE;
+
+// Prayer option bits (Prayer.options), chosen at post time
+export const OPTION_COMPETITION = 1 << 0; // Every claimer submits an answer; the requester ranks them
+export const OPTION_APPROVAL = 1 << 1;    // Claims stay pending until the requester approves them
+export const OPTION_REQUIRE_DELIVERY = 1 << 2; // Only claimers you delivered content to can answer
+
 // Prayer types matching the on-chain enum
 export enum PrayerType {
   Knowledge = 0,
@@ -54,15 +92,37 @@ export enum PrayerStatus {
   Active = 1,     // All slots filled, work in progress
   Fulfilled = 2,  // Answer submitted, awaiting confirmation
   Confirmed = 3,  // Requester approved, bounty distributed
-  Expired = 4,    // TTL elapsed
-  Cancelled = 5,  // Requester cancelled (only when 0 claims)
+  Expired = 4,    // TTL elapsed and the bounty was refunded by expire_prayer
+  Cancelled = 5,  // Requester cancelled (only when 0 claims), or refunded by an arbiter
+  Disputed = 6,   // Requester rejected the answer, awaiting an arbiter ruling
+}
+
+// Where the remainder of an uneven bounty split goes, matching the on-chain enum
+export enum RemainderPolicy {
+  Requester = 0,  // Left in escrow and refunded by close_prayer
+  Answerer = 1,   // Added to the answerer's share
+  RoundRobin = 2, // One unit each to the first paid claimers, in wallet address order
+}
+
+export interface ProtocolConfig {
+  claimTimeoutSeconds: number;   // Anyone can unclaim a claim older than this
+  maxClaimers: number;           // Upper bound for a prayer's max_claimers
+  maxTtlSeconds: number;         // Upper bound for a prayer's TTL
+  maxReviewSeconds: number;      // Upper bound for a prayer's review window
+  disputeTimeoutSeconds: number; // Arbiters' window to rule before anyone can expireDispute()
+  answerReputation: number;      // Reputation for answering
+  confirmReputation: number;     // Bonus reputation when an answer is confirmed
 }
 
 export interface PrayerChainState {
-  authority: PublicKey;
+  authority: PublicKey;          // Default pubkey once renounced
+  pendingAuthority: PublicKey;   // Proposed successor (default pubkey = none)
   totalPrayers: number;
   totalAnswered: number;
   totalAgents: number;
+  arbiters: PublicKey[];         // Can rule on disputed prayers
+  config: ProtocolConfig;
+  paused: number;                // PAUSE_* bits for stopped instruction families
 }
 
 export interface AgentAccount {
@@ -70,11 +130,14 @@ export interface AgentAccount {
   name: string;
   skills: string;
   encryptionKey: number[];       // X25519 public key for E2E encryption
+  keyEpoch: number;              // Bumped each time the encryption key is rotated
   prayersPosted: number;
   prayersAnswered: number;
   prayersConfirmed: number;
   reputation: number;
   registeredAt: number;
+  openClaims: number;            // Live Claim PDAs held by this agent
+  openPrayers: number;           // Prayers posted and not yet closed
 }
 
 export interface PrayerAccount {
@@ -82,22 +145,69 @@ export interface PrayerAccount {
   requester: PublicKey;
   prayerType: PrayerType;
   contentHash: number[];         // SHA-256 of plaintext content
-  rewardLamports: number;
+  rewardLamports: number;        // Lamports, or token base units if rewardMint is set
+  rewardMint: PublicKey;         // SPL mint for the bounty (default pubkey = native SOL)
   status: PrayerStatus;
   maxClaimers: number;           // How many agents can collaborate (1 = solo)
-  numClaimers: number;           // Current number of claims
+  numClaimers: number;           // Current number of approved claims
   answerer: PublicKey;           // Who submitted the answer (must be a claimer)
   answerHash: number[];          // SHA-256 of plaintext answer
   createdAt: number;
   expiresAt: number;
   fulfilledAt: number;
+  reviewSeconds: number;         // Review window after fulfilledAt before anyone can finalize
+  rulingDeadline: number;        // Once disputed, when anyone can expireDispute() (0 = never disputed)
+  remainderPolicy: RemainderPolicy; // Who gets what's left of an uneven split
+  weighted: boolean;             // Split by each claim's weightBps instead of equally
+  requesterWeighted: boolean;    // The requester set the weights; the answerer can't replace them
+  options: number;               // OPTION_* bits
+  numAnswers: number;            // Live Answer PDAs (competition prayers)
+  numPending: number;            // Claims awaiting approval (approval-mode prayers)
+  invited: PublicKey[];          // Only these wallets can claim (empty = anyone)
+  requirements: ClaimRequirements; // Minimum agent standing to claim
+  claimStake: number;            // Lamports each claimer deposits (0 = none)
+  claimTimeoutSeconds: number;   // Anyone can unclaim a claim this long past its last heartbeat
+  fundedLamports: number;        // Part of rewardLamports put in by other funders
+  numFunders: number;            // Live Contribution PDAs
+  funderRefund: number;          // Lamports set aside for funders to withdraw
+  confirmVotes: number;          // Lamports whose holders voted to confirm
+  requesterVoted: boolean;       // Without it, funder votes only count after half the review window
+}
+
+/** What an agent needs to claim a prayer (0 = no requirement) */
+export interface ClaimRequirements {
+  minReputation: number;
+  minConfirmed: number;          // Agent.prayersConfirmed
+  minAgentAgeSeconds: number;    // Time since the agent registered
 }
 
+export const NO_REQUIREMENTS: ClaimRequirements = { minReputation: 0, minConfirmed: 0, minAgentAgeSeconds: 0 };
+
 export interface ClaimAccount {
   prayerId: number;
   claimer: PublicKey;
   contentDelivered: boolean;
   claimedAt: number;
+  weightBps: number;             // Contribution weight, used once the prayer is weighted
+  approved: boolean;             // False while pending on an approval-mode prayer
+  stake: number;                 // Deposit held until answered, unclaimed or slashed
+  lastHeartbeat: number;         // Claim time, approval time or latest heartbeatClaim()
+  answered: boolean;             // Submitted a competition answer; nobody else can unclaim it before the prayer resolves
+}
+
+export interface AnswerAccount {
+  prayerId: number;
+  answerer: PublicKey;
+  answerHash: number[];          // SHA-256 of plaintext answer
+  submittedAt: number;
+  rank: number;                  // Position in the requester's ranking (1 = best, 0 = unranked)
+}
+
+export interface ContributionAccount {
+  prayerId: number;
+  funder: PublicKey;
+  amount: number;                // Lamports this funder added to the bounty
+  voted: boolean;                // Whether this funder voted to confirm
 }
 
 // Load IDL from the build output
@@ -150,6 +260,44 @@ export function getClaimPDA(prayerId: number, claimer: PublicKey): [PublicKey, n
   );
 }
 
+export function getAnswerPDA(prayerId: number, answerer: PublicKey): [PublicKey, number] {
+  const idBuf = Buffer.alloc(8);
+  idBuf.writeBigUInt64LE(BigInt(prayerId));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("answer"), idBuf, answerer.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getContributionPDA(prayerId: number, funder: PublicKey): [PublicKey, number] {
+  const idBuf = Buffer.alloc(8);
+  idBuf.writeBigUInt64LE(BigInt(prayerId));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("contribution"), idBuf, funder.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getEscrowPDA(prayerId: number): [PublicKey, number] {
+  const idBuf = Buffer.alloc(8);
+  idBuf.writeBigUInt64LE(BigInt(prayerId));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), idBuf],
+    PROGRAM_ID
+  );
+}
+
+export function getAssociatedTokenAddress(
+  owner: PublicKey,
+  mint: PublicKey,
+  tokenProgram = TOKEN_PROGRAM_ID,
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  )[0];
+}
+
 /**
  * CHORUS Prayer Chain Client — Private by Default
  * 
@@ -223,9 +371,21 @@ export class ChorusPrayerClient {
       const account = await (this.program.account as any).prayerChain.fetch(pda);
       return {
         authority: account.authority,
+        pendingAuthority: account.pendingAuthority,
         totalPrayers: account.totalPrayers.toNumber(),
         totalAnswered: account.totalAnswered.toNumber(),
         totalAgents: account.totalAgents.toNumber(),
+        arbiters: account.arbiters,
+        config: {
+          claimTimeoutSeconds: account.config.claimTimeoutSeconds.toNumber(),
+          maxClaimers: account.config.maxClaimers,
+          maxTtlSeconds: account.config.maxTtlSeconds.toNumber(),
+          maxReviewSeconds: account.config.maxReviewSeconds.toNumber(),
+          disputeTimeoutSeconds: account.config.disputeTimeoutSeconds.toNumber(),
+          answerReputation: account.config.answerReputation.toNumber(),
+          confirmReputation: account.config.confirmReputation.toNumber(),
+        },
+        paused: account.paused,
       };
     } catch {
       return null;
@@ -241,11 +401,14 @@ export class ChorusPrayerClient {
         name: account.name,
         skills: account.skills,
         encryptionKey: account.encryptionKey,
+        keyEpoch: account.keyEpoch,
         prayersPosted: account.prayersPosted.toNumber(),
         prayersAnswered: account.prayersAnswered.toNumber(),
         prayersConfirmed: account.prayersConfirmed.toNumber(),
         reputation: account.reputation.toNumber(),
         registeredAt: account.registeredAt.toNumber(),
+        openClaims: account.openClaims,
+        openPrayers: account.openPrayers,
       };
     } catch {
       return null;
@@ -262,6 +425,7 @@ export class ChorusPrayerClient {
         prayerType: Object.keys(account.prayerType)[0] as unknown as PrayerType,
         contentHash: account.contentHash,
         rewardLamports: account.rewardLamports.toNumber(),
+        rewardMint: account.rewardMint,
         status: Object.keys(account.status)[0] as unknown as PrayerStatus,
         maxClaimers: account.maxClaimers,
         numClaimers: account.numClaimers,
@@ -270,6 +434,27 @@ export class ChorusPrayerClient {
         createdAt: account.createdAt.toNumber(),
         expiresAt: account.expiresAt.toNumber(),
         fulfilledAt: account.fulfilledAt.toNumber(),
+        reviewSeconds: account.reviewSeconds.toNumber(),
+        rulingDeadline: account.rulingDeadline.toNumber(),
+        remainderPolicy: Object.keys(account.remainderPolicy)[0] as unknown as RemainderPolicy,
+        weighted: account.weighted,
+        requesterWeighted: account.requesterWeighted,
+        options: account.options,
+        numAnswers: account.numAnswers,
+        numPending: account.numPending,
+        invited: account.invited,
+        requirements: {
+          minReputation: account.requirements.minReputation.toNumber(),
+          minConfirmed: account.requirements.minConfirmed.toNumber(),
+          minAgentAgeSeconds: account.requirements.minAgentAgeSeconds.toNumber(),
+        },
+        claimStake: account.claimStake.toNumber(),
+        claimTimeoutSeconds: account.claimTimeoutSeconds.toNumber(),
+        fundedLamports: account.fundedLamports.toNumber(),
+        numFunders: account.numFunders,
+        funderRefund: account.funderRefund.toNumber(),
+        confirmVotes: account.confirmVotes.toNumber(),
+        requesterVoted: account.requesterVoted,
       };
     } catch {
       return null;
@@ -285,6 +470,11 @@ export class ChorusPrayerClient {
         claimer: account.claimer,
         contentDelivered: account.contentDelivered,
         claimedAt: account.claimedAt.toNumber(),
+        weightBps: account.weightBps,
+        approved: account.approved,
+        stake: account.stake.toNumber(),
+        lastHeartbeat: account.lastHeartbeat.toNumber(),
+        answered: account.answered,
       };
     } catch {
       return null;
@@ -332,6 +522,11 @@ export class ChorusPrayerClient {
             claimer: decoded.claimer,
             contentDelivered: decoded.contentDelivered,
             claimedAt: decoded.claimedAt.toNumber(),
+            weightBps: decoded.weightBps,
+            approved: decoded.approved,
+            stake: decoded.stake.toNumber(),
+            lastHeartbeat: decoded.lastHeartbeat.toNumber(),
+            answered: decoded.answered,
           });
         } catch {
           // Skip malformed accounts
@@ -344,6 +539,116 @@ export class ChorusPrayerClient {
     return claims;
   }
 
+  async getAnswer(prayerId: number, answerer: PublicKey): Promise<AnswerAccount | null> {
+    const [pda] = getAnswerPDA(prayerId, answerer);
+    try {
+      const account = await (this.program.account as any).answer.fetch(pda);
+      return {
+        prayerId: account.prayerId.toNumber(),
+        answerer: account.answerer,
+        answerHash: account.answerHash,
+        submittedAt: account.submittedAt.toNumber(),
+        rank: account.rank,
+      };
+    } catch {
+      return null;
+    }
+  }
+
+  /** Every competing Answer submitted on a prayer (requires getProgramAccounts) */
+  async getAnswersForPrayer(prayerId: number): Promise<AnswerAccount[]> {
+    const answers: AnswerAccount[] = [];
+    const idBuf = Buffer.alloc(8);
+    idBuf.writeBigUInt64LE(BigInt(prayerId));
+
+    try {
+      const accounts = await this.provider.connection.getProgramAccounts(
+        this.program.programId,
+        {
+          filters: [
+            // Anchor discriminator for Answer account
+            { memcmp: { offset: 0, bytes: Buffer.from([119, 227, 103, 4, 0, 164, 56, 37]).toString("base64"), encoding: "base64" } },
+            // prayer_id at offset 8
+            { memcmp: { offset: 8, bytes: idBuf.toString("base64"), encoding: "base64" } },
+          ],
+        }
+      );
+
+      for (const { account } of accounts) {
+        try {
+          const decoded = this.program.coder.accounts.decode("answer", account.data);
+          answers.push({
+            prayerId: decoded.prayerId.toNumber(),
+            answerer: decoded.answerer,
+            answerHash: decoded.answerHash,
+            submittedAt: decoded.submittedAt.toNumber(),
+            rank: decoded.rank,
+          });
+        } catch {
+          // Skip malformed accounts
+        }
+      }
+    } catch {
+      // getProgramAccounts may not be available on all RPC endpoints
+    }
+
+    return answers;
+  }
+
+  async getContribution(prayerId: number, funder: PublicKey): Promise<ContributionAccount | null> {
+    const [pda] = getContributionPDA(prayerId, funder);
+    try {
+      const account = await (this.program.account as any).contribution.fetch(pda);
+      return {
+        prayerId: account.prayerId.toNumber(),
+        funder: account.funder,
+        amount: account.amount.toNumber(),
+        voted: account.voted,
+      };
+    } catch {
+      return null;
+    }
+  }
+
+  /** Every Contribution to a crowdfunded prayer (requires getProgramAccounts) */
+  async getContributionsForPrayer(prayerId: number): Promise<ContributionAccount[]> {
+    const contributions: ContributionAccount[] = [];
+    const idBuf = Buffer.alloc(8);
+    idBuf.writeBigUInt64LE(BigInt(prayerId));
+
+    try {
+      const accounts = await this.provider.connection.getProgramAccounts(
+        this.program.programId,
+        {
+          filters: [
+            // Anchor discriminator for Contribution account
+            { memcmp: { offset: 0, bytes: Buffer.from([182, 187, 14, 111, 72, 167, 242, 212]).toString("base64"), encoding: "base64" } },
+            // prayer_id at offset 8
+            { memcmp: { offset: 8, bytes: idBuf.toString("base64"), encoding: "base64" } },
+          ],
+        }
+      );
+
+      for (const { account } of accounts) {
+        try {
+          const decoded = this.program.coder.accounts.decode("contribution", account.data);
+          contributions.push({
+            prayerId: decoded.prayerId.toNumber(),
+            funder: decoded.funder,
+            amount: decoded.amount.toNumber(),
+            voted: decoded.voted,
+          });
+        } catch {
+          // Skip malformed accounts
+        }
+      }
+    } catch {
+      // getProgramAccounts may not be available on all RPC endpoints
+    }
+
+    return contributions;
+  }
+
   async listOpenPrayers(limit = 20): Promise<PrayerAccount[]> {
     const chain = await this.getPrayerChain();
     if (!chain) return [];
@@ -366,6 +671,57 @@ export class ChorusPrayerClient {
     return prayers;
   }
 
+  /** Token program that owns a mint (SPL Token or Token-2022) */
+  async getTokenProgramForMint(mint: PublicKey): Promise<PublicKey> {
+    const info = await this.provider.connection.getAccountInfo(mint);
+    if (!info) throw new Error(`Mint ${mint.toBase58()} not found`);
+    return info.owner;
+  }
+
+  /**
+   * Token accounts for instructions that move a prayer's escrow.
+   * All null for native SOL bounties.
+   */
+  private async tokenEscrowAccounts(prayer: PrayerAccount) {
+    if (prayer.rewardMint.equals(PublicKey.default)) {
+      return { rewardMint: null, escrow: null, requesterTokenAccount: null, tokenProgram: null };
+    }
+    const tokenProgram = await this.getTokenProgramForMint(prayer.rewardMint);
+    return {
+      rewardMint: prayer.rewardMint,
+      escrow: getEscrowPDA(prayer.id)[0],
+      requesterTokenAccount: getAssociatedTokenAddress(prayer.requester, prayer.rewardMint, tokenProgram),
+      tokenProgram,
+    };
+  }
+
+  /**
+   * Remaining accounts for a bounty payout: [claim PDA, recipient (writable)]
+   * for every approved claimer. Recipients are claimer wallets, or their
+   * associated token accounts for SPL bounties. Looks claimers up if not provided.
+   */
+  private async payoutAccounts(prayer: PrayerAccount, claimerWallets?: PublicKey[]) {
+    let wallets = claimerWallets;
+    if (!wallets) {
+      const claims = await this.getClaimsForPrayer(prayer.id);
+      wallets = claims.filter(c => c.approved).map(c => c.claimer);
+    }
+
+    const { rewardMint, tokenProgram } = await this.tokenEscrowAccounts(prayer);
+    return wallets.flatMap(w => [
+      {
+        pubkey: getClaimPDA(prayer.id, w)[0],
+        isSigner: false,
+        isWritable: false,
+      },
+      {
+        pubkey: rewardMint ? getAssociatedTokenAddress(w, rewardMint, tokenProgram!) : w,
+        isSigner: false,
+        isWritable: true,
+      },
+    ]);
+  }
+
   // ── Write Methods ─────────────────────────────────────────
 
   async initialize(): Promise<string> {
@@ -383,6 +739,108 @@ export class ChorusPrayerClient {
     return tx;
   }
 
+  /**
+   * Propose a new authority (authority only). The handover completes when
+   * the proposed wallet calls acceptAuthority(). Pass PublicKey.default to
+   * withdraw a pending proposal.
+   */
+  async proposeAuthority(newAuthority: PublicKey): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+
+    const tx = await this.program.methods
+      .proposeAuthority(newAuthority)
+      .accounts({
+        prayerChain: prayerChainPda,
+        authority: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /** Accept a pending authority proposal made to this wallet. */
+  async acceptAuthority(): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+
+    const tx = await this.program.methods
+      .acceptAuthority()
+      .accounts({
+        prayerChain: prayerChainPda,
+        newAuthority: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /** Permanently give up authority. Config and arbiters become immutable. Unpause everything first. */
+  async renounceAuthority(): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+
+    const tx = await this.program.methods
+      .renounceAuthority()
+      .accounts({
+        prayerChain: prayerChainPda,
+        authority: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /** Update protocol limits and reputation amounts (authority only). */
+  async updateConfig(config: ProtocolConfig): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+
+    const tx = await this.program.methods
+      .updateConfig({
+        claimTimeoutSeconds: new BN(config.claimTimeoutSeconds),
+        maxClaimers: config.maxClaimers,
+        maxTtlSeconds: new BN(config.maxTtlSeconds),
+        maxReviewSeconds: new BN(config.maxReviewSeconds),
+        disputeTimeoutSeconds: new BN(config.disputeTimeoutSeconds),
+        answerReputation: new BN(config.answerReputation),
+        confirmReputation: new BN(config.confirmReputation),
+      })
+      .accounts({
+        prayerChain: prayerChainPda,
+        authority: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /** Replace the dispute arbiter set (authority only, max 5). */
+  async setArbiters(arbiters: PublicKey[]): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+
+    const tx = await this.program.methods
+      .setArbiters(arbiters)
+      .accounts({
+        prayerChain: prayerChainPda,
+        authority: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /** Pause instruction families (PAUSE_* bits, authority only). Pass 0 to unpause. */
+  async setPause(paused: number): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+
+    const tx = await this.program.methods
+      .setPause(paused)
+      .accounts({
+        prayerChain: prayerChainPda,
+        authority: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   async registerAgent(name: string, skills: string): Promise<string> {
     const [prayerChainPda] = getPrayerChainPDA();
     const [agentPda] = getAgentPDA(this.wallet);
@@ -402,11 +860,77 @@ export class ChorusPrayerClient {
     return tx;
   }
 
+  /**
+   * Update this agent's profile. Omitted fields are left unchanged; the
+   * account is resized on-chain to fit. Passing an encryption key that
+   * differs from the on-chain one rotates it and bumps keyEpoch.
+   */
+  async updateAgent(update: {
+    name?: string;
+    skills?: string;
+    encryptionKey?: number[];
+  }): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+    const [agentPda] = getAgentPDA(this.wallet);
+
+    const tx = await this.program.methods
+      .updateAgent(update.name ?? null, update.skills ?? null, update.encryptionKey ?? null)
+      .accounts({
+        prayerChain: prayerChainPda,
+        agent: agentPda,
+        wallet: this.wallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Deregister this agent and reclaim its rent. Fails while the agent still
+   * holds claims or has prayers that are not closed.
+   */
+  async closeAgent(): Promise<string> {
+    const [prayerChainPda] = getPrayerChainPDA();
+    const [agentPda] = getAgentPDA(this.wallet);
+
+    const tx = await this.program.methods
+      .closeAgent()
+      .accounts({
+        prayerChain: prayerChainPda,
+        agent: agentPda,
+        wallet: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   /**
    * Post a prayer. Content is stored locally and as a hash on-chain.
    * No plaintext ever touches the blockchain.
    * 
    * @param maxClaimers How many agents can collaborate (1 = solo, up to 10)
+   * @param reviewSeconds How long you have to confirm or dispute an answer
+   *   before anyone can finalize it and release the bounty
+   * @param rewardMint SPL / Token-2022 mint for the bounty (omit for SOL).
+   *   rewardLamports is then in the mint's base units, paid from this wallet's ATA.
+   * @param remainderPolicy Who gets what's left when the bounty doesn't split
+   *   evenly across the claimers (default: refunded to you on close)
+   * @param options OPTION_* bits, e.g. OPTION_COMPETITION to have every
+   *   claimer answer and rank the answers yourself with selectWinners(), or
+   *   OPTION_APPROVAL to vet each claimer with approveClaim()/rejectClaim(), or
+   *   OPTION_REQUIRE_DELIVERY to refuse answers from claimers you haven't
+   *   delivered the content to with deliverContent()
+   * @param invited Restrict claiming to these wallets (up to MAX_INVITED;
+   *   empty = any registered agent)
+   * @param requirements Minimum reputation, confirmed prayers and agent age
+   *   a claimer needs (default: none)
+   * @param claimStake Lamports each claimer deposits. Returned when they answer
+   *   or unclaim; slashed into the bounty if their claim times out and someone
+   *   else removes it.
+   * @param claimTimeoutSeconds How long a claim lasts without a heartbeat
+   *   before anyone can remove it (at most ttlSeconds; 0 = protocol default)
    * After someone claims, call deliverContent() to send them the encrypted text.
    */
   async postPrayer(
@@ -415,13 +939,45 @@ export class ChorusPrayerClient {
     rewardLamports = 0,
     ttlSeconds = 86400,
     maxClaimers = 1,
+    reviewSeconds = DEFAULT_REVIEW_SECONDS,
+    rewardMint?: PublicKey,
+    remainderPolicy = RemainderPolicy.Requester,
+    options = 0,
+    invited: PublicKey[] = [],
+    requirements: ClaimRequirements = NO_REQUIREMENTS,
+    claimStake = 0,
+    claimTimeoutSeconds = 0,
   ): Promise<{ tx: string; prayerId: number }> {
     const chain = await this.getPrayerChain();
     if (!chain) throw new Error("PrayerChain not initialized");
 
-    if (maxClaimers < 1 || maxClaimers > MAX_CLAIMERS) {
-      throw new Error(`max_claimers must be 1-${MAX_CLAIMERS}`);
+    const { config } = chain;
+    if (maxClaimers < 1 || maxClaimers > config.maxClaimers) {
+      throw new Error(`max_claimers must be 1-${config.maxClaimers}`);
+    }
+    if (ttlSeconds < 1 || ttlSeconds > config.maxTtlSeconds) {
+      throw new Error(`TTL must be 1-${config.maxTtlSeconds} seconds`);
+    }
+    if (reviewSeconds < 1 || reviewSeconds > config.maxReviewSeconds) {
+      throw new Error(`review window must be 1-${config.maxReviewSeconds} seconds`);
+    }
+    if (invited.length > MAX_INVITED) {
+      throw new Error(`at most ${MAX_INVITED} wallets can be invited`);
+    }
+    if (requirements.minAgentAgeSeconds < 0) {
+      throw new Error("minimum agent age must not be negative");
+    }
+    if (claimStake < 0) {
+      throw new Error("claim stake must not be negative");
     }
+    if (claimTimeoutSeconds < 0 || claimTimeoutSeconds > ttlSeconds) {
+      throw new Error(`claim timeout must be 0-${ttlSeconds} seconds`);
+    }
+    const requirementsArg = {
+      minReputation: new BN(requirements.minReputation),
+      minConfirmed: new BN(requirements.minConfirmed),
+      minAgentAgeSeconds: new BN(requirements.minAgentAgeSeconds),
+    };
 
     const prayerId = chain.totalPrayers;
     const [prayerChainPda] = getPrayerChainPDA();
@@ -430,15 +986,31 @@ export class ChorusPrayerClient {
 
     const typeName = typeof prayerType === "string" ? (prayerType as string).toLowerCase() : PrayerType[prayerType as number].toLowerCase();
     const typeArg = { [typeName]: {} };
+    const policyName = RemainderPolicy[remainderPolicy];
+    const policyArg = { [policyName[0].toLowerCase() + policyName.slice(1)]: {} };
     const contentHash = Array.from(createHash("sha256").update(content).digest());
 
+    let tokenAccounts: Record<string, PublicKey | null> = {
+      rewardMint: null, escrow: null, requesterTokenAccount: null, tokenProgram: null,
+    };
+    if (rewardMint) {
+      const tokenProgram = await this.getTokenProgramForMint(rewardMint);
+      tokenAccounts = {
+        rewardMint,
+        escrow: getEscrowPDA(prayerId)[0],
+        requesterTokenAccount: getAssociatedTokenAddress(this.wallet, rewardMint, tokenProgram),
+        tokenProgram,
+      };
+    }
+
     const tx = await this.program.methods
-      .postPrayer(typeArg, contentHash, new BN(rewardLamports), new BN(ttlSeconds), maxClaimers)
+      .postPrayer(typeArg, contentHash, new BN(rewardLamports), new BN(ttlSeconds), maxClaimers, new BN(reviewSeconds), policyArg, options, invited, requirementsArg, new BN(claimStake), new BN(claimTimeoutSeconds))
       .accounts({
         prayerChain: prayerChainPda,
         requesterAgent: agentPda,
         prayer: prayerPda,
         requester: this.wallet,
+        ...tokenAccounts,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
@@ -447,19 +1019,90 @@ export class ChorusPrayerClient {
   }
 
   /**
-   * Claim a prayer. Creates a Claim PDA for this wallet.
-   * Multiple agents can claim until max_claimers is reached.
+   * Push your open prayer's expiry back by extraSeconds. The new expiry
+   * can be at most the protocol's max TTL from now.
    */
-  async claimPrayer(prayerId: number): Promise<string> {
-    const [prayerPda] = getPrayerPDA(prayerId);
-    const [claimPda] = getClaimPDA(prayerId, this.wallet);
+  async extendPrayer(prayerId: number, extraSeconds: number): Promise<string> {
+    if (extraSeconds < 1) throw new Error("extension must be at least 1 second");
+
+    const tx = await this.program.methods
+      .extendPrayer(new BN(extraSeconds))
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        requester: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Add to your open prayer's bounty: lamports for a SOL bounty, or the
+   * reward mint's base units (paid from your ATA) for a token bounty.
+   */
+  async topUpBounty(prayerId: number, amount: number): Promise<string> {
+    if (amount < 1) throw new Error("top-up must be positive");
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const tx = await this.program.methods
+      .topUpBounty(new BN(amount))
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        requester: this.wallet,
+        ...(await this.tokenEscrowAccounts(prayer)),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Add lamports to someone else's open SOL prayer. Your Contribution PDA
+   * records the amount: if the prayer is cancelled, expires or is partly
+   * refunded by an arbiter, you get your pro-rata share back through
+   * withdrawContribution(). A wallet that claimed the prayer can't fund it.
+   */
+  async fundPrayer(prayerId: number, lamports: number): Promise<string> {
+    if (lamports < 1) throw new Error("contribution must be positive");
+
+    const tx = await this.program.methods
+      .fundPrayer(new BN(lamports))
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        contribution: getContributionPDA(prayerId, this.wallet)[0],
+        funderClaim: getClaimPDA(prayerId, this.wallet)[0],
+        funder: this.wallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Claim a prayer. Creates a Claim PDA for this wallet.
+   * Multiple agents can claim until max_claimers is reached. On an
+   * approval-mode prayer the claim stays pending until the requester approves it.
+   * Deposits the prayer's claimStake, if any, into the Claim PDA. A wallet
+   * that funded the prayer can't claim it.
+   */
+  async claimPrayer(prayerId: number): Promise<string> {
+    const [prayerPda] = getPrayerPDA(prayerId);
+    const [claimPda] = getClaimPDA(prayerId, this.wallet);
     const [agentPda] = getAgentPDA(this.wallet);
 
     const tx = await this.program.methods
       .claimPrayer()
       .accounts({
+        prayerChain: getPrayerChainPDA()[0],
         prayer: prayerPda,
         claim: claimPda,
+        claimerContribution: getContributionPDA(prayerId, this.wallet)[0],
         claimerAgent: agentPda,
         claimer: this.wallet,
         systemProgram: SystemProgram.programId,
@@ -469,6 +1112,60 @@ export class ChorusPrayerClient {
     return tx;
   }
 
+  /**
+   * Approve a pending claim on your approval-mode prayer. The claim takes
+   * one of the prayer's max_claimers slots.
+   */
+  async approveClaim(prayerId: number, claimerWallet: PublicKey): Promise<string> {
+    const tx = await this.program.methods
+      .approveClaim()
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        claim: getClaimPDA(prayerId, claimerWallet)[0],
+        requester: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Reject a pending claim on your approval-mode prayer. The Claim PDA is
+   * closed and its rent returned to the claimer.
+   */
+  async rejectClaim(prayerId: number, claimerWallet: PublicKey): Promise<string> {
+    const tx = await this.program.methods
+      .rejectClaim()
+      .accounts({
+        prayer: getPrayerPDA(prayerId)[0],
+        claim: getClaimPDA(prayerId, claimerWallet)[0],
+        claimerAgent: getAgentPDA(claimerWallet)[0],
+        claimerWallet,
+        requester: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Keep your claim alive: restarts its timeout from now, up to the
+   * prayer's expiry. Must be sent before the current deadline passes.
+   */
+  async heartbeatClaim(prayerId: number): Promise<string> {
+    const tx = await this.program.methods
+      .heartbeatClaim()
+      .accounts({
+        prayer: getPrayerPDA(prayerId)[0],
+        claim: getClaimPDA(prayerId, this.wallet)[0],
+        claimer: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   /**
    * Deliver encrypted prayer content to a specific claimer.
    * Call this after someone claims your prayer.
@@ -494,6 +1191,7 @@ export class ChorusPrayerClient {
     const tx = await this.program.methods
       .deliverContent(Buffer.from(encryptedContent))
       .accounts({
+        prayerChain: getPrayerChainPDA()[0],
         prayer: prayerPda,
         claim: claimPda,
         requester: this.wallet,
@@ -504,11 +1202,11 @@ export class ChorusPrayerClient {
   }
 
   /**
-   * Deliver content to ALL current claimers of a prayer.
+   * Deliver content to ALL current (approved) claimers of a prayer.
    * Convenience method for multi-claimer prayers.
    */
   async deliverContentToAll(prayerId: number, plaintext: string): Promise<string[]> {
-    const claims = await this.getClaimsForPrayer(prayerId);
+    const claims = (await this.getClaimsForPrayer(prayerId)).filter(c => c.approved);
     if (claims.length === 0) throw new Error("No claimers to deliver to");
 
     const txs: string[] = [];
@@ -524,7 +1222,8 @@ export class ChorusPrayerClient {
   /**
    * Answer a claimed prayer with encrypted answer.
    * Encrypts the answer for the requester using their on-chain encryption key.
-   * The answerer must have a Claim PDA (be a claimer).
+   * The answerer must have a Claim PDA (be a claimer). On a competition prayer
+   * this submits a competing Answer instead of fulfilling the prayer.
    */
   async answerPrayer(prayerId: number, answer: string): Promise<string> {
     if (answer.length > MAX_ANSWER_LENGTH) {
@@ -548,6 +1247,20 @@ export class ChorusPrayerClient {
     // Encrypt answer for the requester
     const encryptedAnswer = this.encrypt(answer, requesterAgent.encryptionKey);
 
+    if (prayer.options & OPTION_COMPETITION) {
+      return this.program.methods
+        .submitAnswer(answerHash, Buffer.from(encryptedAnswer))
+        .accounts({
+          prayerChain: prayerChainPda,
+          prayer: prayerPda,
+          claim: claimPda,
+          answer: getAnswerPDA(prayerId, this.wallet)[0],
+          answerer: this.wallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
     const tx = await this.program.methods
       .answerPrayer(answerHash, Buffer.from(encryptedAnswer))
       .accounts({
@@ -562,10 +1275,100 @@ export class ChorusPrayerClient {
     return tx;
   }
 
+  /**
+   * Rank the answers on a competition prayer (requester only) and fulfill it.
+   * List the winners best first with their share in basis points: shares
+   * must not increase down the ranking and must sum to 10000. The top answer
+   * becomes the prayer's answer; confirm or dispute it as usual. Only possible
+   * until the prayer's selection deadline (expiry + review window).
+   */
+  async selectWinners(prayerId: number, ranking: { answerer: PublicKey; bps: number }[]): Promise<string> {
+    if (ranking.length === 0) throw new Error("rank at least one answer");
+    const total = ranking.reduce((sum, r) => sum + r.bps, 0);
+    if (total !== 10_000) throw new Error(`shares must sum to 10000 bps (got ${total})`);
+
+    const tx = await this.program.methods
+      .selectWinners(ranking.map((r) => r.bps))
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        winnerAgent: getAgentPDA(ranking[0].answerer)[0],
+        requester: this.wallet,
+      })
+      .remainingAccounts(ranking.flatMap((r) => [
+        { pubkey: getAnswerPDA(prayerId, r.answerer)[0], isSigner: false, isWritable: true },
+        { pubkey: getClaimPDA(prayerId, r.answerer)[0], isSigner: false, isWritable: true },
+      ]))
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Pay a competition whose requester let the selection deadline (expiry +
+   * review window) pass without ranking. Anyone can call this; the bounty
+   * splits equally among every submitted answer.
+   */
+  async settleCompetition(prayerId: number): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const { requesterTokenAccount, ...tokenAccounts } = await this.tokenEscrowAccounts(prayer);
+    const answers = await this.getAnswersForPrayer(prayerId);
+
+    const tx = await this.program.methods
+      .settleCompetition()
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        caller: this.wallet,
+        ...tokenAccounts,
+      })
+      .remainingAccounts(answers.flatMap((a) => [
+        { pubkey: getAnswerPDA(prayerId, a.answerer)[0], isSigner: false, isWritable: false },
+        {
+          pubkey: tokenAccounts.rewardMint
+            ? getAssociatedTokenAddress(a.answerer, tokenAccounts.rewardMint, tokenAccounts.tokenProgram!)
+            : a.answerer,
+          isSigner: false,
+          isWritable: true,
+        },
+      ]))
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Weight the bounty split by contribution (requester or answerer only).
+   * Pass one weight per active claimer, in basis points summing to 10000;
+   * a later call replaces the earlier weights until the prayer is confirmed.
+   */
+  async setClaimWeights(prayerId: number, weights: { claimer: PublicKey; bps: number }[]): Promise<string> {
+    const total = weights.reduce((sum, w) => sum + w.bps, 0);
+    if (total !== 10_000) throw new Error(`weights must sum to 10000 bps (got ${total})`);
+
+    const tx = await this.program.methods
+      .setClaimWeights(weights.map((w) => w.bps))
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        setter: this.wallet,
+      })
+      .remainingAccounts(weights.map((w) => ({
+        pubkey: getClaimPDA(prayerId, w.claimer)[0], isSigner: false, isWritable: true,
+      })))
+      .rpc();
+
+    return tx;
+  }
+
   /**
    * Confirm a prayer and distribute bounty.
-   * Bounty splits equally among ALL claimers.
-   * Pass claimer wallets as remaining accounts for bounty distribution.
+   * Bounty splits equally among ALL claimers, or by weight after setClaimWeights().
+   * Every active claimer must be passed as a [claim PDA, recipient] pair;
+   * the program rejects the payout otherwise. The recipient is the claimer
+   * wallet, or its associated token account for SPL bounties.
    */
   async confirmPrayer(prayerId: number, claimerWallets?: PublicKey[]): Promise<string> {
     const prayer = await this.getPrayer(prayerId);
@@ -574,34 +1377,155 @@ export class ChorusPrayerClient {
     const [prayerPda] = getPrayerPDA(prayerId);
     const [answererAgentPda] = getAgentPDA(prayer.answerer);
 
-    // If claimer wallets not provided, look them up
-    let wallets = claimerWallets;
-    if (!wallets) {
-      const claims = await this.getClaimsForPrayer(prayerId);
-      wallets = claims.map(c => c.claimer);
-    }
-
-    // Build remaining accounts: claimer wallets (writable) for bounty distribution
-    const remainingAccounts = wallets.map(w => ({
-      pubkey: w,
-      isSigner: false,
-      isWritable: true,
-    }));
+    const { requesterTokenAccount, ...tokenAccounts } = await this.tokenEscrowAccounts(prayer);
+    const remainingAccounts = await this.payoutAccounts(prayer, claimerWallets);
 
     const tx = await this.program.methods
       .confirmPrayer()
       .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: prayerPda,
+        answererAgent: answererAgentPda,
+        requester: this.wallet,
+        ...tokenAccounts,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Vote to accept the answer on a prayer you funded or posted, weighted by
+   * your share of the bounty. Once votes cover more than half the bounty,
+   * anyone can finalizePrayer() without waiting out the review window: at
+   * once if the requester voted, or after half the window if funders carried
+   * it alone.
+   */
+  async voteConfirm(prayerId: number): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+    const isRequester = prayer.requester.equals(this.wallet);
+
+    const tx = await this.program.methods
+      .voteConfirm()
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: getPrayerPDA(prayerId)[0],
+        contribution: isRequester ? null : getContributionPDA(prayerId, this.wallet)[0],
+        funder: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Finalize a fulfilled prayer whose review window has lapsed, or whose
+   * funders (and requester) voted to confirm. Anyone can call this; the bounty is paid out
+   * as if confirmed.
+   */
+  async finalizePrayer(prayerId: number, claimerWallets?: PublicKey[]): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const [prayerPda] = getPrayerPDA(prayerId);
+    const [answererAgentPda] = getAgentPDA(prayer.answerer);
+
+    const { requesterTokenAccount, ...tokenAccounts } = await this.tokenEscrowAccounts(prayer);
+    const remainingAccounts = await this.payoutAccounts(prayer, claimerWallets);
+
+    const tx = await this.program.methods
+      .finalizePrayer()
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
         prayer: prayerPda,
         answererAgent: answererAgentPda,
+        caller: this.wallet,
+        ...tokenAccounts,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Reject a fulfilled answer and open a dispute for the arbiters, who have
+   * the protocol's disputeTimeoutSeconds to rule. Fails while no arbiters
+   * are set. The reason is hashed on-chain; keep the text off-chain.
+   */
+  async openDispute(prayerId: number, reason: string): Promise<string> {
+    const [prayerPda] = getPrayerPDA(prayerId);
+    const reasonHash = Array.from(createHash("sha256").update(reason).digest());
+
+    const tx = await this.program.methods
+      .openDispute(reasonHash)
+      .accounts({
+        prayerChain: getPrayerChainPDA()[0],
+        prayer: prayerPda,
         requester: this.wallet,
       })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Rule on a disputed prayer (arbiters only). claimerBps of the bounty
+   * splits equally among the claimers; the rest is refunded to the requester.
+   */
+  async resolveDispute(prayerId: number, claimerBps: number, claimerWallets?: PublicKey[]): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const [prayerChainPda] = getPrayerChainPDA();
+    const [prayerPda] = getPrayerPDA(prayerId);
+    const [answererAgentPda] = getAgentPDA(prayer.answerer);
+    const remainingAccounts = claimerBps > 0 ? await this.payoutAccounts(prayer, claimerWallets) : [];
+
+    const tx = await this.program.methods
+      .resolveDispute(claimerBps)
+      .accounts({
+        prayerChain: prayerChainPda,
+        prayer: prayerPda,
+        answererAgent: answererAgentPda,
+        requester: prayer.requester,
+        arbiter: this.wallet,
+        ...(await this.tokenEscrowAccounts(prayer)),
+      })
       .remainingAccounts(remainingAccounts)
       .rpc();
 
     return tx;
   }
 
+  /**
+   * Refund a disputed prayer the arbiters didn't rule on before its
+   * rulingDeadline. Anyone can call this; the whole bounty goes back to the
+   * requester and any funders.
+   */
+  async expireDispute(prayerId: number): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const tx = await this.program.methods
+      .expireDispute()
+      .accounts({
+        prayer: getPrayerPDA(prayerId)[0],
+        requester: prayer.requester,
+        caller: this.wallet,
+        ...(await this.tokenEscrowAccounts(prayer)),
+      })
+      .rpc();
+
+    return tx;
+  }
+
   async cancelPrayer(prayerId: number): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
     const [prayerPda] = getPrayerPDA(prayerId);
 
     const tx = await this.program.methods
@@ -609,6 +1533,7 @@ export class ChorusPrayerClient {
       .accounts({
         prayer: prayerPda,
         requester: this.wallet,
+        ...(await this.tokenEscrowAccounts(prayer)),
       })
       .rpc();
 
@@ -617,7 +1542,8 @@ export class ChorusPrayerClient {
 
   /**
    * Remove a claim. Claimer can unclaim voluntarily, or anyone can
-   * unclaim after the 1-hour timeout.
+   * unclaim after the 1-hour timeout, unless the claimer has submitted a
+   * competition answer (then it stays until the prayer resolves).
    * 
    * @param claimerWallet The wallet of the claim to remove (defaults to self)
    */
@@ -629,8 +1555,10 @@ export class ChorusPrayerClient {
     const tx = await this.program.methods
       .unclaimPrayer()
       .accounts({
+        prayerChain: getPrayerChainPDA()[0],
         prayer: prayerPda,
         claim: claimPda,
+        claimerAgent: getAgentPDA(claimer)[0],
         claimerWallet: claimer,
         caller: this.wallet,
       })
@@ -639,22 +1567,159 @@ export class ChorusPrayerClient {
     return tx;
   }
 
+  /**
+   * Close every claim left on a confirmed, cancelled or expired prayer,
+   * returning each claim's rent and stake to its claimer. Anyone can sweep;
+   * claims are closed a few per transaction.
+   */
+  async sweepClaims(prayerId: number): Promise<string[]> {
+    const claims = await this.getClaimsForPrayer(prayerId);
+    const txs: string[] = [];
+
+    for (let i = 0; i < claims.length; i += SWEEP_BATCH) {
+      const tx = await this.program.methods
+        .sweepClaims()
+        .accounts({
+          prayer: getPrayerPDA(prayerId)[0],
+          caller: this.wallet,
+        })
+        .remainingAccounts(claims.slice(i, i + SWEEP_BATCH).flatMap((c) => [
+          { pubkey: getClaimPDA(prayerId, c.claimer)[0], isSigner: false, isWritable: true },
+          { pubkey: getAgentPDA(c.claimer)[0], isSigner: false, isWritable: true },
+          { pubkey: c.claimer, isSigner: false, isWritable: true },
+        ]))
+        .rpc();
+      txs.push(tx);
+    }
+
+    return txs;
+  }
+
+  /**
+   * Pay a funder their share of a confirmed, cancelled or expired prayer's
+   * refund and close their Contribution. Anyone can withdraw on a funder's
+   * behalf; the lamports always go to the funder.
+   *
+   * @param funderWallet The wallet of the contribution to close (defaults to self)
+   */
+  async withdrawContribution(prayerId: number, funderWallet?: PublicKey): Promise<string> {
+    const funder = funderWallet || this.wallet;
+
+    const tx = await this.program.methods
+      .withdrawContribution()
+      .accounts({
+        prayer: getPrayerPDA(prayerId)[0],
+        contribution: getContributionPDA(prayerId, funder)[0],
+        funderWallet: funder,
+        caller: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Close a competing Answer and return its rent to the answerer. The answerer
+   * can withdraw while the prayer is open; anyone can close it once resolved.
+   *
+   * @param answererWallet The wallet of the answer to close (defaults to self)
+   */
+  async closeAnswer(prayerId: number, answererWallet?: PublicKey): Promise<string> {
+    const answerer = answererWallet || this.wallet;
+
+    const tx = await this.program.methods
+      .closeAnswer()
+      .accounts({
+        prayer: getPrayerPDA(prayerId)[0],
+        answer: getAnswerPDA(prayerId, answerer)[0],
+        answererWallet: answerer,
+        caller: this.wallet,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   /**
    * Close a resolved prayer and return rent to requester.
+   * Any claims, answers and contributions still open are released first (rent
+   * goes back to each claimer, and funders get their refund share).
    */
   async closePrayer(prayerId: number): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const status = String(prayer.status).toLowerCase();
+    if (["confirmed", "cancelled", "expired"].includes(status)) {
+      await this.sweepClaims(prayerId);
+    } else {
+      for (const claim of await this.getClaimsForPrayer(prayerId)) {
+        await this.unclaimPrayer(prayerId, claim.claimer);
+      }
+    }
+    if (prayer.numAnswers > 0) {
+      for (const answer of await this.getAnswersForPrayer(prayerId)) {
+        await this.closeAnswer(prayerId, answer.answerer);
+      }
+    }
+    if (prayer.numFunders > 0) {
+      for (const contribution of await this.getContributionsForPrayer(prayerId)) {
+        await this.withdrawContribution(prayerId, contribution.funder);
+      }
+    }
+
     const [prayerPda] = getPrayerPDA(prayerId);
 
     const tx = await this.program.methods
       .closePrayer()
       .accounts({
         prayer: prayerPda,
+        requesterAgent: getAgentPDA(this.wallet)[0],
         requester: this.wallet,
+        ...(await this.tokenEscrowAccounts(prayer)),
       })
       .rpc();
 
     return tx;
   }
+
+  /**
+   * Expire anyone's prayer once it is past its expiry. Refunds the bounty to
+   * the requester and any funders, releases any claims left on it, then
+   * closes it; this wallet earns KEEPER_FEE_LAMPORTS out of the prayer's rent.
+   * A competition with submitted answers can't expire: settleCompetition()
+   * pays them once the selection deadline passes.
+   */
+  async expirePrayer(prayerId: number): Promise<string> {
+    const prayer = await this.getPrayer(prayerId);
+    if (!prayer) throw new Error("Prayer not found");
+
+    const expire = async () => this.program.methods
+      .expirePrayer()
+      .accounts({
+        prayer: getPrayerPDA(prayerId)[0],
+        requesterAgent: getAgentPDA(prayer.requester)[0],
+        requester: prayer.requester,
+        keeper: this.wallet,
+        ...(await this.tokenEscrowAccounts(prayer)),
+      })
+      .rpc();
+
+    if (String(prayer.status).toLowerCase() !== "expired") {
+      const tx = await expire();
+      // Closed straight away when nothing was left on it
+      if (!(await this.getPrayer(prayerId))) return tx;
+    }
+
+    await this.sweepClaims(prayerId);
+    if (prayer.numFunders > 0) {
+      for (const contribution of await this.getContributionsForPrayer(prayerId)) {
+        await this.withdrawContribution(prayerId, contribution.funder);
+      }
+    }
+
+    return expire();
+  }
 }
 
 // ── CLI Helper ──────────────────────────────────────────────
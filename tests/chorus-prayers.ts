@@ -1,6 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID,
+  createMint, getOrCreateAssociatedTokenAccount, mintTo, getAccount,
+} from "@solana/spl-token";
 import { assert } from "chai";
 import { createRequire } from "module";
 import { createHash } from "crypto";
@@ -65,16 +69,46 @@ function getClaimPDA(prayerId: number, claimer: PublicKey): [PublicKey, number]
     [Buffer.from("claim"), buf, claimer.toBuffer()], programId
   );
 }
+function getAnswerPDA(prayerId: number, answerer: PublicKey): [PublicKey, number] {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(prayerId));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("answer"), buf, answerer.toBuffer()], programId
+  );
+}
+function getContributionPDA(prayerId: number, funder: PublicKey): [PublicKey, number] {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(prayerId));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("contribution"), buf, funder.toBuffer()], programId
+  );
+}
+function getEscrowPDA(prayerId: number): [PublicKey, number] {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(prayerId));
+  return PublicKey.findProgramAddressSync([Buffer.from("escrow"), buf], programId);
+}
+function payoutAccounts(prayerId: number, pairs: [PublicKey, PublicKey][]) {
+  return pairs.flatMap(([claimer, recipient]) => [
+    { pubkey: getClaimPDA(prayerId, claimer)[0], isSigner: false, isWritable: false },
+    { pubkey: recipient, isSigner: false, isWritable: true },
+  ]);
+}
 function sha256(text: string): number[] {
   return Array.from(createHash("sha256").update(text).digest());
 }
 
 // ── Helpers ─────────────────────────────────────────────────
+const REVIEW_SECONDS = 259_200;
+const NO_REQUIREMENTS = { minReputation: new anchor.BN(0), minConfirmed: new anchor.BN(0), minAgentAgeSeconds: new anchor.BN(0) };
+
 async function airdrop(conn: anchor.web3.Connection, to: PublicKey, sol: number) {
   const sig = await conn.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
   await conn.confirmTransaction(sig);
 }
 
+const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
 async function expectError(fn: () => Promise<any>, errorName: string) {
   try {
     await fn();
@@ -211,6 +245,250 @@ describe("chorus-prayers", () => {
         "already in use"
       );
     });
+
+    it("Update resizes the account to fit new skills", async () => {
+      const [agentPda] = getAgentPDA(agent4.publicKey);
+      const before = await provider.connection.getAccountInfo(agentPda);
+      const skills = "security review, red-teaming, formal verification, fuzzing";
+
+      await program.methods.updateAgent(null, skills, null).accounts({
+        prayerChain: getPrayerChainPDA()[0], agent: agentPda,
+        wallet: agent4.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent4]).rpc();
+
+      const after = await provider.connection.getAccountInfo(agentPda);
+      const agent = await (program.account as any).agent.fetch(agentPda);
+      assert.equal(agent.skills, skills);
+      assert.equal(agent.keyEpoch, 0);
+      assert.isAbove(after!.data.length, before!.data.length);
+    });
+
+    it("Rotating the encryption key bumps key_epoch", async () => {
+      const [agentPda] = getAgentPDA(agent4.publicKey);
+      const accounts = {
+        prayerChain: getPrayerChainPDA()[0], agent: agentPda,
+        wallet: agent4.publicKey, systemProgram: SystemProgram.programId,
+      };
+
+      await expectError(
+        () => program.methods.updateAgent(null, null, Array(32).fill(0))
+          .accounts(accounts).signers([agent4]).rpc(),
+        "InvalidEncryptionKey"
+      );
+
+      const rotated = Array.from(nacl.box.keyPair().publicKey);
+      await program.methods.updateAgent(null, null, rotated).accounts(accounts).signers([agent4]).rpc();
+      let agent = await (program.account as any).agent.fetch(agentPda);
+      assert.deepEqual(agent.encryptionKey, rotated);
+      assert.equal(agent.keyEpoch, 1);
+
+      // Rotate back to the wallet-derived key used by the rest of the suite
+      await program.methods.updateAgent(null, null, Array.from(enc4.publicKey)).accounts(accounts).signers([agent4]).rpc();
+      agent = await (program.account as any).agent.fetch(agentPda);
+      assert.equal(agent.keyEpoch, 2);
+    });
+  });
+
+  // ── Protocol Config ─────────────────────────────────────
+
+  describe("Protocol Config", () => {
+    const defaults = {
+      claimTimeoutSeconds: new anchor.BN(3600),
+      maxClaimers: 10,
+      maxTtlSeconds: new anchor.BN(604_800),
+      maxReviewSeconds: new anchor.BN(2_592_000),
+      disputeTimeoutSeconds: new anchor.BN(604_800),
+      answerReputation: new anchor.BN(10),
+      confirmReputation: new anchor.BN(5),
+    };
+
+    it("Initializes with default limits", async () => {
+      const chain = await (program.account as any).prayerChain.fetch(getPrayerChainPDA()[0]);
+      assert.equal(chain.config.claimTimeoutSeconds.toNumber(), 3600);
+      assert.equal(chain.config.maxClaimers, 10);
+      assert.equal(chain.config.maxTtlSeconds.toNumber(), 604_800);
+      assert.equal(chain.config.answerReputation.toNumber(), 10);
+      assert.equal(chain.config.confirmReputation.toNumber(), 5);
+    });
+
+    it("Non-authority cannot update config", async () => {
+      await expectError(
+        () => program.methods.updateConfig({ ...defaults, maxClaimers: 20 }).accounts({
+          prayerChain: getPrayerChainPDA()[0], authority: agent2.publicKey,
+        }).signers([agent2]).rpc(),
+        "NotAuthority"
+      );
+    });
+
+    it("Rejects a zero claim timeout", async () => {
+      await expectError(
+        () => program.methods.updateConfig({ ...defaults, claimTimeoutSeconds: new anchor.BN(0) }).accounts({
+          prayerChain: getPrayerChainPDA()[0], authority: authority.publicKey,
+        }).rpc(),
+        "InvalidConfig"
+      );
+    });
+
+    it("post_prayer enforces the updated max_claimers", async () => {
+      const [chainPda] = getPrayerChainPDA();
+      await program.methods.updateConfig({ ...defaults, maxClaimers: 2 }).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+
+      await expectError(
+        () => program.methods.postPrayer(
+          { knowledge: {} }, sha256("too many"), new anchor.BN(0), new anchor.BN(86400), 3, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+        ).accounts({
+          prayerChain: chainPda, requesterAgent: getAgentPDA(authority.publicKey)[0],
+          prayer: getPrayerPDA(nextPrayerId)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+        }).rpc(),
+        "InvalidMaxClaimers"
+      );
+
+      // Restore defaults for the rest of the suite
+      await program.methods.updateConfig(defaults).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+      const chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.equal(chain.config.maxClaimers, 10);
+    });
+  });
+
+  // ── Authority Handover ──────────────────────────────────
+
+  describe("Authority Handover", () => {
+    it("Non-authority cannot propose", async () => {
+      await expectError(
+        () => program.methods.proposeAuthority(agent2.publicKey).accounts({
+          prayerChain: getPrayerChainPDA()[0], authority: agent2.publicKey,
+        }).signers([agent2]).rpc(),
+        "NotAuthority"
+      );
+    });
+
+    it("Only the proposed key can accept", async () => {
+      const [chainPda] = getPrayerChainPDA();
+      await program.methods.proposeAuthority(agent2.publicKey).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+
+      const chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.ok(chain.pendingAuthority.equals(agent2.publicKey));
+      assert.ok(chain.authority.equals(authority.publicKey)); // Not yet transferred
+
+      await expectError(
+        () => program.methods.acceptAuthority().accounts({
+          prayerChain: chainPda, newAuthority: agent3.publicKey,
+        }).signers([agent3]).rpc(),
+        "NotPendingAuthority"
+      );
+    });
+
+    it("Proposed key accepts, then hands authority back", async () => {
+      const [chainPda] = getPrayerChainPDA();
+      await program.methods.acceptAuthority().accounts({
+        prayerChain: chainPda, newAuthority: agent2.publicKey,
+      }).signers([agent2]).rpc();
+
+      let chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.ok(chain.authority.equals(agent2.publicKey));
+      assert.ok(chain.pendingAuthority.equals(PublicKey.default));
+
+      // Old authority lost its powers
+      await expectError(
+        () => program.methods.proposeAuthority(authority.publicKey).accounts({
+          prayerChain: chainPda, authority: authority.publicKey,
+        }).rpc(),
+        "NotAuthority"
+      );
+
+      await program.methods.proposeAuthority(authority.publicKey).accounts({
+        prayerChain: chainPda, authority: agent2.publicKey,
+      }).signers([agent2]).rpc();
+      await program.methods.acceptAuthority().accounts({
+        prayerChain: chainPda, newAuthority: authority.publicKey,
+      }).rpc();
+
+      chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.ok(chain.authority.equals(authority.publicKey));
+    });
+  });
+
+  // ── Pause ───────────────────────────────────────────────
+
+  describe("Pause", () => {
+    const PAUSE_POST = 1 << 1;
+    const PAUSE_CLAIM = 1 << 2;
+
+    it("Non-authority cannot pause", async () => {
+      await expectError(
+        () => program.methods.setPause(PAUSE_POST).accounts({
+          prayerChain: getPrayerChainPDA()[0], authority: agent2.publicKey,
+        }).signers([agent2]).rpc(),
+        "NotAuthority"
+      );
+    });
+
+    it("Rejects unknown pause bits", async () => {
+      await expectError(
+        () => program.methods.setPause(1 << 15).accounts({
+          prayerChain: getPrayerChainPDA()[0], authority: authority.publicKey,
+        }).rpc(),
+        "InvalidPauseFlags"
+      );
+    });
+
+    it("Paused families fail while cancel stays available", async () => {
+      const pausePrayerId = nextPrayerId;
+      const [chainPda] = getPrayerChainPDA();
+      const [agentPda] = getAgentPDA(authority.publicKey);
+      const [prayerPda] = getPrayerPDA(pausePrayerId);
+
+      await program.methods.postPrayer(
+        { signal: {} }, sha256("paused"), new anchor.BN(0.01 * LAMPORTS_PER_SOL), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
+        requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await program.methods.setPause(PAUSE_POST | PAUSE_CLAIM).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+      let chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.equal(chain.paused, PAUSE_POST | PAUSE_CLAIM);
+
+      await expectError(
+        () => program.methods.postPrayer(
+          { signal: {} }, sha256("blocked"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+        ).accounts({
+          prayerChain: chainPda, requesterAgent: agentPda, prayer: getPrayerPDA(nextPrayerId)[0],
+          requester: authority.publicKey, systemProgram: SystemProgram.programId,
+        }).rpc(),
+        "Paused"
+      );
+
+      await expectError(
+        () => program.methods.claimPrayer().accounts({
+          prayerChain: chainPda, prayer: prayerPda, claim: getClaimPDA(pausePrayerId, agent2.publicKey)[0],
+          claimerAgent: getAgentPDA(agent2.publicKey)[0], claimer: agent2.publicKey, systemProgram: SystemProgram.programId,
+        }).signers([agent2]).rpc(),
+        "Paused"
+      );
+
+      // Refund path is never paused
+      await program.methods.cancelPrayer().accounts({
+        prayer: prayerPda, requester: authority.publicKey,
+      }).rpc();
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { cancelled: {} });
+
+      await program.methods.setPause(0).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+      chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.equal(chain.paused, 0);
+    });
   });
 
   // ── Multi-Claimer Collaborative Prayer ──────────────────
@@ -229,7 +507,7 @@ describe("chorus-prayers", () => {
       const balBefore = await provider.connection.getBalance(authority.publicKey);
 
       await program.methods.postPrayer(
-        { knowledge: {} }, sha256(content), new anchor.BN(bountyLamports), new anchor.BN(86400), 3
+        { knowledge: {} }, sha256(content), new anchor.BN(bountyLamports), new anchor.BN(86400), 3, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
       ).accounts({
         prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
         requester: authority.publicKey, systemProgram: SystemProgram.programId,
@@ -240,6 +518,7 @@ describe("chorus-prayers", () => {
       assert.equal(prayer.numClaimers, 0);
       assert.deepEqual(prayer.status, { open: {} });
       assert.equal(prayer.rewardLamports.toNumber(), bountyLamports);
+      assert.deepEqual(prayer.remainderPolicy, { requester: {} });
 
       // Bounty escrowed in prayer PDA
       const prayerBal = await provider.connection.getBalance(prayerPda);
@@ -254,7 +533,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.postPrayer(
-          { knowledge: {} }, sha256("bad"), new anchor.BN(0), new anchor.BN(86400), 0
+          { knowledge: {} }, sha256("bad"), new anchor.BN(0), new anchor.BN(86400), 0, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
         ).accounts({
           prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
           requester: authority.publicKey, systemProgram: SystemProgram.programId,
@@ -270,7 +549,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.postPrayer(
-          { knowledge: {} }, sha256("bad"), new anchor.BN(0), new anchor.BN(86400), 11
+          { knowledge: {} }, sha256("bad"), new anchor.BN(0), new anchor.BN(86400), 11, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
         ).accounts({
           prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
           requester: authority.publicKey, systemProgram: SystemProgram.programId,
@@ -285,7 +564,7 @@ describe("chorus-prayers", () => {
       const [agentPda] = getAgentPDA(agent2.publicKey);
 
       await program.methods.claimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
         claimer: agent2.publicKey, systemProgram: SystemProgram.programId,
       }).signers([agent2]).rpc();
 
@@ -304,7 +583,7 @@ describe("chorus-prayers", () => {
       const [agentPda] = getAgentPDA(agent3.publicKey);
 
       await program.methods.claimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
         claimer: agent3.publicKey, systemProgram: SystemProgram.programId,
       }).signers([agent3]).rpc();
 
@@ -319,7 +598,7 @@ describe("chorus-prayers", () => {
       const [agentPda] = getAgentPDA(agent4.publicKey);
 
       await program.methods.claimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
         claimer: agent4.publicKey, systemProgram: SystemProgram.programId,
       }).signers([agent4]).rpc();
 
@@ -335,7 +614,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.claimPrayer().accounts({
-          prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
           claimer: agent2.publicKey, systemProgram: SystemProgram.programId,
         }).signers([agent2]).rpc(),
         "already in use" // Claim PDA already exists
@@ -350,7 +629,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.claimPrayer().accounts({
-          prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
           claimer: outsider.publicKey, systemProgram: SystemProgram.programId,
         }).signers([outsider]).rpc(),
         "AccountNotInitialized"
@@ -365,7 +644,7 @@ describe("chorus-prayers", () => {
         const encrypted = encryptFor(content, enc.publicKey, enc1.secretKey);
 
         await program.methods.deliverContent(Buffer.from(encrypted)).accounts({
-          prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
         }).rpc();
 
         const claim = await (program.account as any).claim.fetch(claimPda);
@@ -384,7 +663,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.deliverContent(Buffer.from(blob)).accounts({
-          prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
         }).rpc(),
         "AlreadyDelivered"
       );
@@ -397,7 +676,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.deliverContent(Buffer.from(blob)).accounts({
-          prayer: prayerPda, claim: claimPda, requester: agent2.publicKey,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, requester: agent2.publicKey,
         }).signers([agent2]).rpc(),
         "NotRequester"
       );
@@ -441,6 +720,75 @@ describe("chorus-prayers", () => {
       );
     });
 
+    it("Confirm rejects a payout that skips a claimer", async () => {
+      const [prayerPda] = getPrayerPDA(prayerId);
+
+      await expectError(
+        () => program.methods.confirmPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent2.publicKey)[0], requester: authority.publicKey,
+        }).remainingAccounts(payoutAccounts(prayerId, [
+          [agent2.publicKey, agent2.publicKey],
+          [agent3.publicKey, agent3.publicKey],
+        ])).rpc(),
+        "PayoutMismatch"
+      );
+    });
+
+    it("Confirm rejects paying the same claimer twice", async () => {
+      const [prayerPda] = getPrayerPDA(prayerId);
+
+      await expectError(
+        () => program.methods.confirmPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent2.publicKey)[0], requester: authority.publicKey,
+        }).remainingAccounts(payoutAccounts(prayerId, [
+          [agent2.publicKey, agent2.publicKey],
+          [agent2.publicKey, agent2.publicKey],
+          [agent3.publicKey, agent3.publicKey],
+        ])).rpc(),
+        "PayoutMismatch"
+      );
+    });
+
+    it("Confirm rejects routing a claimer's share to the requester", async () => {
+      const [prayerPda] = getPrayerPDA(prayerId);
+
+      await expectError(
+        () => program.methods.confirmPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent2.publicKey)[0], requester: authority.publicKey,
+        }).remainingAccounts(payoutAccounts(prayerId, [
+          [agent2.publicKey, agent2.publicKey],
+          [agent3.publicKey, agent3.publicKey],
+          [agent4.publicKey, authority.publicKey],
+        ])).rpc(),
+        "RecipientMismatch"
+      );
+    });
+
+    it("Only the requester or answerer can weight the split", async () => {
+      const weightClaims = (claimers: PublicKey[]) => claimers.map((c) => ({
+        pubkey: getClaimPDA(prayerId, c)[0], isSigner: false, isWritable: true,
+      }));
+
+      await expectError(
+        () => program.methods.setClaimWeights([3_334, 3_333, 3_333]).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(prayerId)[0], setter: agent3.publicKey,
+        }).remainingAccounts(weightClaims([agent2.publicKey, agent3.publicKey, agent4.publicKey]))
+          .signers([agent3]).rpc(),
+        "NotRequesterOrAnswerer"
+      );
+
+      await expectError(
+        () => program.methods.setClaimWeights([5_000, 5_000, 5_000]).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(prayerId)[0], setter: agent2.publicKey,
+        }).remainingAccounts(weightClaims([agent2.publicKey, agent3.publicKey, agent4.publicKey]))
+          .signers([agent2]).rpc(),
+        "InvalidWeights"
+      );
+
+      const prayer = await (program.account as any).prayer.fetch(getPrayerPDA(prayerId)[0]);
+      assert.isFalse(prayer.weighted);
+    });
+
     it("Confirm distributes bounty equally to all 3 claimers", async () => {
       const [prayerPda] = getPrayerPDA(prayerId);
       const [answererAgentPda] = getAgentPDA(agent2.publicKey);
@@ -452,12 +800,12 @@ describe("chorus-prayers", () => {
       const perClaimer = Math.floor(bountyLamports / 3);
 
       await program.methods.confirmPrayer().accounts({
-        prayer: prayerPda, answererAgent: answererAgentPda, requester: authority.publicKey,
-      }).remainingAccounts([
-        { pubkey: agent2.publicKey, isSigner: false, isWritable: true },
-        { pubkey: agent3.publicKey, isSigner: false, isWritable: true },
-        { pubkey: agent4.publicKey, isSigner: false, isWritable: true },
-      ]).rpc();
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: answererAgentPda, requester: authority.publicKey,
+      }).remainingAccounts(payoutAccounts(prayerId, [
+        [agent2.publicKey, agent2.publicKey],
+        [agent3.publicKey, agent3.publicKey],
+        [agent4.publicKey, agent4.publicKey],
+      ])).rpc();
 
       const prayer = await (program.account as any).prayer.fetch(prayerPda);
       assert.deepEqual(prayer.status, { confirmed: {} });
@@ -493,7 +841,7 @@ describe("chorus-prayers", () => {
       const [prayerPda] = getPrayerPDA(soloPrayerId);
 
       await program.methods.postPrayer(
-        { review: {} }, sha256("review my thesis"), new anchor.BN(0), new anchor.BN(86400), 1
+        { review: {} }, sha256("review my thesis"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
       ).accounts({
         prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
         requester: authority.publicKey, systemProgram: SystemProgram.programId,
@@ -512,7 +860,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.claimPrayer().accounts({
-          prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
           claimer: authority.publicKey, systemProgram: SystemProgram.programId,
         }).rpc(),
         "CannotClaimOwn"
@@ -525,7 +873,7 @@ describe("chorus-prayers", () => {
       const [agentPda] = getAgentPDA(agent3.publicKey);
 
       await program.methods.claimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
         claimer: agent3.publicKey, systemProgram: SystemProgram.programId,
       }).signers([agent3]).rpc();
 
@@ -540,7 +888,7 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.claimPrayer().accounts({
-          prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
           claimer: agent2.publicKey, systemProgram: SystemProgram.programId,
         }).signers([agent2]).rpc(),
         "NotOpen"
@@ -555,7 +903,7 @@ describe("chorus-prayers", () => {
       // Deliver
       const encrypted = encryptFor("review my thesis", enc3.publicKey, enc1.secretKey);
       await program.methods.deliverContent(Buffer.from(encrypted)).accounts({
-        prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
       }).rpc();
 
       // Answer
@@ -569,10 +917,10 @@ describe("chorus-prayers", () => {
       // Confirm (zero bounty — should still work)
       const [answererAgentPda] = getAgentPDA(agent3.publicKey);
       await program.methods.confirmPrayer().accounts({
-        prayer: prayerPda, answererAgent: answererAgentPda, requester: authority.publicKey,
-      }).remainingAccounts([
-        { pubkey: agent3.publicKey, isSigner: false, isWritable: true },
-      ]).rpc();
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: answererAgentPda, requester: authority.publicKey,
+      }).remainingAccounts(payoutAccounts(soloPrayerId, [
+        [agent3.publicKey, agent3.publicKey],
+      ])).rpc();
 
       const prayer = await (program.account as any).prayer.fetch(prayerPda);
       assert.deepEqual(prayer.status, { confirmed: {} });
@@ -593,7 +941,7 @@ describe("chorus-prayers", () => {
 
       // Post with bounty
       await program.methods.postPrayer(
-        { signal: {} }, sha256("cancel me"), new anchor.BN(0.01 * LAMPORTS_PER_SOL), new anchor.BN(86400), 1
+        { signal: {} }, sha256("cancel me"), new anchor.BN(0.01 * LAMPORTS_PER_SOL), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
       ).accounts({
         prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
         requester: authority.publicKey, systemProgram: SystemProgram.programId,
@@ -620,7 +968,7 @@ describe("chorus-prayers", () => {
       const [prayerPda] = getPrayerPDA(unclaimPrayerId);
 
       await program.methods.postPrayer(
-        { collaboration: {} }, sha256("unclaim test"), new anchor.BN(0), new anchor.BN(86400), 2
+        { collaboration: {} }, sha256("unclaim test"), new anchor.BN(0), new anchor.BN(86400), 2, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
       ).accounts({
         prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
         requester: authority.publicKey, systemProgram: SystemProgram.programId,
@@ -630,7 +978,7 @@ describe("chorus-prayers", () => {
       // Agent 2 claims
       const [claimPda] = getClaimPDA(unclaimPrayerId, agent2.publicKey);
       await program.methods.claimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent2.publicKey)[0],
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent2.publicKey)[0],
         claimer: agent2.publicKey, systemProgram: SystemProgram.programId,
       }).signers([agent2]).rpc();
 
@@ -650,7 +998,7 @@ describe("chorus-prayers", () => {
       const [prayerPda] = getPrayerPDA(freshId);
 
       await program.methods.postPrayer(
-        { knowledge: {} }, sha256("agent2 prayer"), new anchor.BN(0), new anchor.BN(86400), 1
+        { knowledge: {} }, sha256("agent2 prayer"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
       ).accounts({
         prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
         requester: agent2.publicKey, systemProgram: SystemProgram.programId,
@@ -676,8 +1024,8 @@ describe("chorus-prayers", () => {
       const [claimPda] = getClaimPDA(unclaimPrayerId, agent2.publicKey);
 
       await program.methods.unclaimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerWallet: agent2.publicKey,
-        caller: agent2.publicKey,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+        claimerAgent: getAgentPDA(agent2.publicKey)[0], claimerWallet: agent2.publicKey, caller: agent2.publicKey,
       }).signers([agent2]).rpc();
 
       const prayer = await (program.account as any).prayer.fetch(prayerPda);
@@ -697,7 +1045,7 @@ describe("chorus-prayers", () => {
       const [agentPda] = getAgentPDA(agent3.publicKey);
 
       await program.methods.claimPrayer().accounts({
-        prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: agentPda,
         claimer: agent3.publicKey, systemProgram: SystemProgram.programId,
       }).signers([agent3]).rpc();
 
@@ -710,20 +1058,46 @@ describe("chorus-prayers", () => {
       const balBefore = await provider.connection.getBalance(authority.publicKey);
 
       await program.methods.closePrayer().accounts({
-        prayer: prayerPda, requester: authority.publicKey,
+        prayer: prayerPda, requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
       }).rpc();
 
       const balAfter = await provider.connection.getBalance(authority.publicKey);
       assert.isAbove(balAfter, balBefore); // Rent recovered
     });
 
+    it("Cannot close a confirmed prayer with live claims", async () => {
+      await expectError(
+        () => program.methods.closePrayer().accounts({
+          prayer: getPrayerPDA(0)[0], requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
+        }).rpc(),
+        "ClaimsOutstanding"
+      );
+    });
+
+    it("Anyone can release claims on a confirmed prayer", async () => {
+      // Prayer 0 is confirmed with three claimers
+      const [prayerPda] = getPrayerPDA(0);
+      for (const claimer of [agent2, agent3, agent4]) {
+        const before = (await (program.account as any).agent.fetch(getAgentPDA(claimer.publicKey)[0])).openClaims;
+        await program.methods.unclaimPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: getClaimPDA(0, claimer.publicKey)[0],
+          claimerAgent: getAgentPDA(claimer.publicKey)[0], claimerWallet: claimer.publicKey, caller: authority.publicKey,
+        }).rpc();
+        const after = (await (program.account as any).agent.fetch(getAgentPDA(claimer.publicKey)[0])).openClaims;
+        assert.equal(after, before - 1);
+      }
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.numClaimers, 0);
+      assert.deepEqual(prayer.status, { confirmed: {} });
+    });
+
     it("Close confirmed prayer returns rent", async () => {
-      // Prayer 0 is confirmed
       const [prayerPda] = getPrayerPDA(0);
       const balBefore = await provider.connection.getBalance(authority.publicKey);
 
       await program.methods.closePrayer().accounts({
-        prayer: prayerPda, requester: authority.publicKey,
+        prayer: prayerPda, requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
       }).rpc();
 
       const balAfter = await provider.connection.getBalance(authority.publicKey);
@@ -735,84 +1109,1071 @@ describe("chorus-prayers", () => {
 
       await expectError(
         () => program.methods.closePrayer().accounts({
-          prayer: prayerPda, requester: authority.publicKey,
+          prayer: prayerPda, requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
         }).rpc(),
         "CannotClose"
       );
     });
   });
 
-  // ── Encryption / Privacy ────────────────────────────────
+  // ── Disputes ────────────────────────────────────────────
 
-  describe("E2E Encryption", () => {
-    it("Third party cannot decrypt content", async () => {
-      const content = "secret alpha signal";
-      const blob = encryptFor(content, enc2.publicKey, enc1.secretKey);
-      const eavesdropper = Keypair.generate();
-      const eavesEnc = deriveEncryptionKeypair(eavesdropper);
-      const dec = decryptFrom(blob, enc1.publicKey, eavesEnc.secretKey);
-      assert.isNull(dec);
-    });
+  describe("Disputes", () => {
+    const arbiter = outsider; // Arbiters need no Agent PDA
+    const bounty = 0.02 * LAMPORTS_PER_SOL;
+    let disputeId: number;
 
-    it("Third party cannot decrypt answer", async () => {
-      const answer = "SOFR will spike 25bps on March 31";
-      const blob = encryptFor(answer, enc1.publicKey, enc2.secretKey);
-      const dec = decryptFrom(blob, enc2.publicKey, enc3.secretKey);
-      assert.isNull(dec);
+    it("Non-authority cannot set arbiters", async () => {
+      await expectError(
+        () => program.methods.setArbiters([agent2.publicKey]).accounts({
+          prayerChain: getPrayerChainPDA()[0], authority: agent2.publicKey,
+        }).signers([agent2]).rpc(),
+        "NotAuthority"
+      );
     });
 
-    it("Correct recipient decrypts successfully", async () => {
-      const msg = "classified: BTC to $150K by Q3";
-      const blob = encryptFor(msg, enc3.publicKey, enc1.secretKey);
-      const dec = decryptFrom(blob, enc1.publicKey, enc3.secretKey);
-      assert.equal(dec, msg);
-    });
+    it("Authority sets the arbiter set", async () => {
+      const [chainPda] = getPrayerChainPDA();
+      await program.methods.setArbiters([arbiter.publicKey]).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
 
-    it("Encryption keys are deterministic from wallet", async () => {
-      const kp1a = deriveEncryptionKeypair(agent2);
-      const kp1b = deriveEncryptionKeypair(agent2);
-      assert.deepEqual(Array.from(kp1a.publicKey), Array.from(kp1b.publicKey));
-      assert.deepEqual(Array.from(kp1a.secretKey), Array.from(kp1b.secretKey));
+      const chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.equal(chain.arbiters.length, 1);
+      assert.ok(chain.arbiters[0].equals(arbiter.publicKey));
     });
 
-    it("DH shared secret is symmetric", async () => {
-      // A encrypts for B, B decrypts from A — and vice versa
-      const msg = "symmetric test";
-      const blobAtoB = encryptFor(msg, enc2.publicKey, enc1.secretKey);
-      const decB = decryptFrom(blobAtoB, enc1.publicKey, enc2.secretKey);
-      assert.equal(decB, msg);
+    it("Cannot dispute a prayer that is not fulfilled", async () => {
+      disputeId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(disputeId);
 
-      const blobBtoA = encryptFor(msg, enc1.publicKey, enc2.secretKey);
-      const decA = decryptFrom(blobBtoA, enc2.publicKey, enc1.secretKey);
-      assert.equal(decA, msg);
+      await program.methods.postPrayer(
+        { review: {} }, sha256("dispute me"), new anchor.BN(bounty), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await expectError(
+        () => program.methods.openDispute(sha256("too early")).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, requester: authority.publicKey,
+        }).rpc(),
+        "NotFulfilled"
+      );
     });
-  });
 
-  // ── Prayer Types ────────────────────────────────────────
+    it("Requester rejects the answer and opens a dispute", async () => {
+      const [prayerPda] = getPrayerPDA(disputeId);
+      const [claimPda] = getClaimPDA(disputeId, agent2.publicKey);
 
-  describe("All Prayer Types", () => {
-    const types = [
-      { name: "Knowledge", arg: { knowledge: {} } },
-      { name: "Compute", arg: { compute: {} } },
-      { name: "Review", arg: { review: {} } },
-      { name: "Signal", arg: { signal: {} } },
-      { name: "Collaboration", arg: { collaboration: {} } },
-    ];
+      await program.methods.claimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent2.publicKey)[0],
+        claimer: agent2.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent2]).rpc();
 
-    for (const t of types) {
-      it(`Posts ${t.name} prayer`, async () => {
-        const id = nextPrayerId;
-        const [chainPda] = getPrayerChainPDA();
-        const [agentPda] = getAgentPDA(authority.publicKey);
-        const [prayerPda] = getPrayerPDA(id);
+      const answer = "Looks fine to me";
+      await program.methods.answerPrayer(sha256(answer), Buffer.from(encryptFor(answer, enc1.publicKey, enc2.secretKey))).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+        answererAgent: getAgentPDA(agent2.publicKey)[0], answerer: agent2.publicKey,
+      }).signers([agent2]).rpc();
 
-        await program.methods.postPrayer(
-          t.arg, sha256(`${t.name} test`), new anchor.BN(0), new anchor.BN(3600), 1
-        ).accounts({
-          prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
-          requester: authority.publicKey, systemProgram: SystemProgram.programId,
-        }).rpc();
-        nextPrayerId++;
+      await program.methods.openDispute(sha256("answer ignores the attached thesis")).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, requester: authority.publicKey,
+      }).rpc();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { disputed: {} });
+      assert.isAbove(prayer.rulingDeadline.toNumber(), prayer.fulfilledAt.toNumber());
+    });
+
+    it("Nobody can refund the dispute while the arbiters can still rule", async () => {
+      await expectError(
+        () => program.methods.expireDispute().accounts({
+          prayer: getPrayerPDA(disputeId)[0], requester: authority.publicKey, caller: agent3.publicKey,
+        }).signers([agent3]).rpc(),
+        "RulingWindowOpen"
+      );
+    });
+
+    it("Non-arbiter cannot rule", async () => {
+      const [prayerPda] = getPrayerPDA(disputeId);
+
+      await expectError(
+        () => program.methods.resolveDispute(10_000).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda,
+          answererAgent: getAgentPDA(agent2.publicKey)[0], requester: authority.publicKey,
+          arbiter: agent2.publicKey,
+        }).remainingAccounts(payoutAccounts(disputeId, [
+          [agent2.publicKey, agent2.publicKey],
+        ])).signers([agent2]).rpc(),
+        "NotArbiter"
+      );
+    });
+
+    it("Arbiter splits the escrow between claimer and requester", async () => {
+      const [prayerPda] = getPrayerPDA(disputeId);
+      const claimerBefore = await provider.connection.getBalance(agent2.publicKey);
+      const escrowBefore = await provider.connection.getBalance(prayerPda);
+
+      await program.methods.resolveDispute(4_000).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda,
+        answererAgent: getAgentPDA(agent2.publicKey)[0], requester: authority.publicKey,
+        arbiter: arbiter.publicKey,
+      }).remainingAccounts(payoutAccounts(disputeId, [
+        [agent2.publicKey, agent2.publicKey],
+      ])).signers([arbiter]).rpc();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { confirmed: {} });
+
+      // 40% to the claimer, the remaining 60% refunded — escrow fully drained
+      const claimerAfter = await provider.connection.getBalance(agent2.publicKey);
+      const escrowAfter = await provider.connection.getBalance(prayerPda);
+      assert.equal(claimerAfter - claimerBefore, bounty * 0.4);
+      assert.equal(escrowBefore - escrowAfter, bounty);
+    });
+  });
+
+  // ── Competition Prayers ──────────────────────────────────
+
+  describe("Competitions", () => {
+    const bounty = 0.01 * LAMPORTS_PER_SOL;
+    let compId: number;
+
+    it("Every claimer submits their own answer", async () => {
+      compId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(compId);
+
+      await program.methods.postPrayer(
+        { knowledge: {} }, sha256("best gas optimisation"), new anchor.BN(bounty), new anchor.BN(86400), 2, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 1, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      for (const [agent, enc] of [[agent2, enc2], [agent3, enc3]] as const) {
+        const [claimPda] = getClaimPDA(compId, agent.publicKey);
+        await program.methods.claimPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent.publicKey)[0],
+          claimer: agent.publicKey, systemProgram: SystemProgram.programId,
+        }).signers([agent]).rpc();
+
+        const answer = `pack the structs (${agent.publicKey.toBase58().slice(0, 4)})`;
+        await program.methods.submitAnswer(sha256(answer), Buffer.from(encryptFor(answer, enc1.publicKey, enc.secretKey))).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+          answer: getAnswerPDA(compId, agent.publicKey)[0], answerer: agent.publicKey, systemProgram: SystemProgram.programId,
+        }).signers([agent]).rpc();
+      }
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.options, 1);
+      assert.equal(prayer.numAnswers, 2);
+      assert.deepEqual(prayer.status, { active: {} });
+    });
+
+    it("A competition cannot be answered directly", async () => {
+      await expectError(
+        () => program.methods.answerPrayer(sha256("me first"), Buffer.from("x")).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(compId)[0], claim: getClaimPDA(compId, agent2.publicKey)[0],
+          answererAgent: getAgentPDA(agent2.publicKey)[0], answerer: agent2.publicKey,
+        }).signers([agent2]).rpc(),
+        "WrongMode"
+      );
+    });
+
+    it("Requester ranks the answers and confirm pays the winners", async () => {
+      const [prayerPda] = getPrayerPDA(compId);
+      const ranked = (order: PublicKey[]) => order.flatMap((w) => [
+        { pubkey: getAnswerPDA(compId, w)[0], isSigner: false, isWritable: true },
+        { pubkey: getClaimPDA(compId, w)[0], isSigner: false, isWritable: true },
+      ]);
+
+      await expectError(
+        () => program.methods.selectWinners([3_000, 7_000]).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, winnerAgent: getAgentPDA(agent3.publicKey)[0], requester: authority.publicKey,
+        }).remainingAccounts(ranked([agent3.publicKey, agent2.publicKey])).rpc(),
+        "InvalidRanking"
+      );
+
+      await program.methods.selectWinners([7_000, 3_000]).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, winnerAgent: getAgentPDA(agent3.publicKey)[0], requester: authority.publicKey,
+      }).remainingAccounts(ranked([agent3.publicKey, agent2.publicKey])).rpc();
+
+      let prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { fulfilled: {} });
+      assert.ok(prayer.answerer.equals(agent3.publicKey));
+      assert.equal((await (program.account as any).answer.fetch(getAnswerPDA(compId, agent3.publicKey)[0])).rank, 1);
+      assert.equal((await (program.account as any).answer.fetch(getAnswerPDA(compId, agent2.publicKey)[0])).rank, 2);
+
+      const bal2Before = await provider.connection.getBalance(agent2.publicKey);
+      const bal3Before = await provider.connection.getBalance(agent3.publicKey);
+      await program.methods.confirmPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent3.publicKey)[0], requester: authority.publicKey,
+      }).remainingAccounts(payoutAccounts(compId, [
+        [agent2.publicKey, agent2.publicKey],
+        [agent3.publicKey, agent3.publicKey],
+      ])).rpc();
+
+      prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { confirmed: {} });
+      assert.equal(await provider.connection.getBalance(agent3.publicKey) - bal3Before, bounty * 0.7);
+      assert.equal(await provider.connection.getBalance(agent2.publicKey) - bal2Before, bounty * 0.3);
+    });
+
+    it("Anyone can close answers once the prayer resolves", async () => {
+      const [prayerPda] = getPrayerPDA(compId);
+      for (const agent of [agent2, agent3]) {
+        await program.methods.closeAnswer().accounts({
+          prayer: prayerPda, answer: getAnswerPDA(compId, agent.publicKey)[0],
+          answererWallet: agent.publicKey, caller: outsider.publicKey,
+        }).signers([outsider]).rpc();
+      }
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.numAnswers, 0);
+      assert.isNull(await provider.connection.getAccountInfo(getAnswerPDA(compId, agent2.publicKey)[0]));
+    });
+  });
+
+  // ── Approval Mode ────────────────────────────────────────
+
+  describe("Applications", () => {
+    let applyId: number;
+
+    const claimAccounts = (agent: Keypair) => ({
+      prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(applyId)[0], claim: getClaimPDA(applyId, agent.publicKey)[0],
+      claimerAgent: getAgentPDA(agent.publicKey)[0], claimer: agent.publicKey, systemProgram: SystemProgram.programId,
+    });
+
+    it("Claims start pending and take no slot", async () => {
+      applyId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(applyId);
+
+      await program.methods.postPrayer(
+        { review: {} }, sha256("audit our treasury multisig"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 2, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      for (const agent of [agent2, agent3]) {
+        await program.methods.claimPrayer().accounts(claimAccounts(agent)).signers([agent]).rpc();
+      }
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { open: {} });
+      assert.equal(prayer.numClaimers, 0);
+      assert.equal(prayer.numPending, 2);
+      const claim = await (program.account as any).claim.fetch(getClaimPDA(applyId, agent2.publicKey)[0]);
+      assert.isFalse(claim.approved);
+    });
+
+    it("Pending claimers receive no content", async () => {
+      await expectError(
+        () => program.methods.deliverContent(Buffer.from(encryptFor("treasury", enc2.publicKey, enc1.secretKey))).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(applyId)[0],
+          claim: getClaimPDA(applyId, agent2.publicKey)[0], requester: authority.publicKey,
+        }).rpc(),
+        "ClaimPending"
+      );
+    });
+
+    it("Only the requester can approve or reject", async () => {
+      await expectError(
+        () => program.methods.approveClaim().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(applyId)[0],
+          claim: getClaimPDA(applyId, agent3.publicKey)[0], requester: agent3.publicKey,
+        }).signers([agent3]).rpc(),
+        "NotRequester"
+      );
+    });
+
+    it("Requester rejects one applicant and approves the other", async () => {
+      const [prayerPda] = getPrayerPDA(applyId);
+      const bal3Before = await provider.connection.getBalance(agent3.publicKey);
+
+      await program.methods.rejectClaim().accounts({
+        prayer: prayerPda, claim: getClaimPDA(applyId, agent3.publicKey)[0],
+        claimerAgent: getAgentPDA(agent3.publicKey)[0], claimerWallet: agent3.publicKey, requester: authority.publicKey,
+      }).rpc();
+      assert.isNull(await provider.connection.getAccountInfo(getClaimPDA(applyId, agent3.publicKey)[0]));
+      assert.isAbove(await provider.connection.getBalance(agent3.publicKey), bal3Before); // Rent returned
+
+      await program.methods.approveClaim().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda,
+        claim: getClaimPDA(applyId, agent2.publicKey)[0], requester: authority.publicKey,
+      }).rpc();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { active: {} });
+      assert.equal(prayer.numClaimers, 1);
+      assert.equal(prayer.numPending, 0);
+      const claim = await (program.account as any).claim.fetch(getClaimPDA(applyId, agent2.publicKey)[0]);
+      assert.isTrue(claim.approved);
+
+      await expectError(
+        () => program.methods.approveClaim().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda,
+          claim: getClaimPDA(applyId, agent2.publicKey)[0], requester: authority.publicKey,
+        }).rpc(),
+        "ClaimNotPending"
+      );
+    });
+  });
+
+  // ── Invite-Only Prayers ──────────────────────────────────
+
+  describe("Invite-Only Prayers", () => {
+    let inviteId: number;
+
+    it("Only invited agents can claim", async () => {
+      inviteId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(inviteId);
+
+      await program.methods.postPrayer(
+        { compute: {} }, sha256("rerun the backtest"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [agent3.publicKey], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      const claimAccounts = (agent: Keypair) => ({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: getClaimPDA(inviteId, agent.publicKey)[0],
+        claimerAgent: getAgentPDA(agent.publicKey)[0], claimer: agent.publicKey, systemProgram: SystemProgram.programId,
+      });
+
+      await expectError(
+        () => program.methods.claimPrayer().accounts(claimAccounts(agent4)).signers([agent4]).rpc(),
+        "NotInvited"
+      );
+      await program.methods.claimPrayer().accounts(claimAccounts(agent3)).signers([agent3]).rpc();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.invited.length, 1);
+      assert.ok(prayer.invited[0].equals(agent3.publicKey));
+      assert.equal(prayer.numClaimers, 1);
+    });
+
+    it("Rejects more than 5 invitees", async () => {
+      const invited = Array.from({ length: 6 }, () => Keypair.generate().publicKey);
+      await expectError(
+        () => program.methods.postPrayer(
+          { compute: {} }, sha256("too many"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, invited, NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+        ).accounts({
+          prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+          prayer: getPrayerPDA(nextPrayerId)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+        }).rpc(),
+        "TooManyInvited"
+      );
+    });
+  });
+
+  // ── Claim Requirements ───────────────────────────────────
+
+  describe("Claim Requirements", () => {
+    const requiring = (reqs: Partial<Record<keyof typeof NO_REQUIREMENTS, number>>) => ({
+      ...NO_REQUIREMENTS,
+      ...Object.fromEntries(Object.entries(reqs).map(([k, v]) => [k, new anchor.BN(v)])),
+    });
+
+    async function postRequiring(reqs: ReturnType<typeof requiring>): Promise<number> {
+      const id = nextPrayerId;
+      await program.methods.postPrayer(
+        { knowledge: {} }, sha256(`gated ${id}`), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], reqs, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: getPrayerPDA(id)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+      return id;
+    }
+
+    const claim = (id: number, agent: Keypair) => program.methods.claimPrayer().accounts({
+      prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(id)[0], claim: getClaimPDA(id, agent.publicKey)[0],
+      claimerAgent: getAgentPDA(agent.publicKey)[0], claimer: agent.publicKey, systemProgram: SystemProgram.programId,
+    }).signers([agent]).rpc();
+
+    it("Rejects negative requirements", async () => {
+      await expectError(() => postRequiring(requiring({ minAgentAgeSeconds: -1 })), "InvalidRequirements");
+    });
+
+    it("Keeps agents below the minimum reputation out", async () => {
+      const id = await postRequiring(requiring({ minReputation: 1 }));
+      const newcomer = await (program.account as any).agent.fetch(getAgentPDA(agent4.publicKey)[0]);
+      assert.equal(newcomer.reputation.toNumber(), 0);
+
+      await expectError(() => claim(id, agent4), "ReputationTooLow");
+      await claim(id, agent2); // Earned reputation answering earlier prayers
+
+      const prayer = await (program.account as any).prayer.fetch(getPrayerPDA(id)[0]);
+      assert.equal(prayer.requirements.minReputation.toNumber(), 1);
+    });
+
+    it("Requires confirmed prayers and agent age", async () => {
+      await expectError(async () => claim(await postRequiring(requiring({ minConfirmed: 1_000 })), agent2), "TooFewConfirmed");
+      await expectError(async () => claim(await postRequiring(requiring({ minAgentAgeSeconds: 365 * 86400 })), agent2), "AgentTooNew");
+    });
+  });
+
+  // ── Claim Stakes ─────────────────────────────────────────
+
+  describe("Claim Stakes", () => {
+    const stake = 0.01 * LAMPORTS_PER_SOL;
+    let stakedId: number;
+
+    it("Deposits the stake into the Claim PDA", async () => {
+      stakedId = nextPrayerId;
+      await program.methods.postPrayer(
+        { knowledge: {} }, sha256("staked"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(stake), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: getPrayerPDA(stakedId)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      const [claimPda] = getClaimPDA(stakedId, agent3.publicKey);
+      await program.methods.claimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(stakedId)[0], claim: claimPda,
+        claimerAgent: getAgentPDA(agent3.publicKey)[0], claimer: agent3.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent3]).rpc();
+
+      const claim = await (program.account as any).claim.fetch(claimPda);
+      assert.equal(claim.stake.toNumber(), stake);
+      const info = await provider.connection.getAccountInfo(claimPda);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(info!.data.length);
+      assert.equal(info!.lamports, rent + stake);
+    });
+
+    it("Returns the stake on a voluntary unclaim", async () => {
+      const unclaim = (caller: Keypair) => program.methods.unclaimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(stakedId)[0], claim: getClaimPDA(stakedId, agent3.publicKey)[0],
+        claimerAgent: getAgentPDA(agent3.publicKey)[0], claimerWallet: agent3.publicKey, caller: caller.publicKey,
+      }).signers([caller]).rpc();
+
+      // Nobody else can take the stake before the claim times out
+      await expectError(() => unclaim(agent2), "NotClaimer");
+
+      const before = await provider.connection.getBalance(agent3.publicKey);
+      await unclaim(agent3);
+      const after = await provider.connection.getBalance(agent3.publicKey);
+      assert.isAbove(after - before, stake); // Stake plus claim rent, less the fee
+
+      const prayer = await (program.account as any).prayer.fetch(getPrayerPDA(stakedId)[0]);
+      assert.equal(prayer.rewardLamports.toNumber(), 0);
+    });
+  });
+
+  // ── Claim Heartbeats ─────────────────────────────────────
+
+  describe("Claim Heartbeats", () => {
+    async function postWithTimeout(ttl: number, claimTimeout: number): Promise<number> {
+      const id = nextPrayerId;
+      await program.methods.postPrayer(
+        { compute: {} }, sha256(`long job ${id}`), new anchor.BN(0), new anchor.BN(ttl), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(claimTimeout)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: getPrayerPDA(id)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+      return id;
+    }
+
+    it("Rejects a claim timeout longer than the TTL", async () => {
+      await expectError(() => postWithTimeout(3600, 3601), "InvalidClaimTimeout");
+    });
+
+    it("Uses the prayer's own timeout and extends it on heartbeat", async () => {
+      const id = await postWithTimeout(86400, 6 * 3600);
+      const prayer = await (program.account as any).prayer.fetch(getPrayerPDA(id)[0]);
+      assert.equal(prayer.claimTimeoutSeconds.toNumber(), 6 * 3600);
+
+      const [claimPda] = getClaimPDA(id, agent3.publicKey);
+      await program.methods.claimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(id)[0], claim: claimPda,
+        claimerAgent: getAgentPDA(agent3.publicKey)[0], claimer: agent3.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent3]).rpc();
+      const claimed = await (program.account as any).claim.fetch(claimPda);
+      assert.equal(claimed.lastHeartbeat.toNumber(), claimed.claimedAt.toNumber());
+
+      await sleep(1500);
+      await program.methods.heartbeatClaim().accounts({
+        prayer: getPrayerPDA(id)[0], claim: claimPda, claimer: agent3.publicKey,
+      }).signers([agent3]).rpc();
+      const beat = await (program.account as any).claim.fetch(claimPda);
+      assert.isAbove(beat.lastHeartbeat.toNumber(), claimed.lastHeartbeat.toNumber());
+
+      await program.methods.unclaimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(id)[0], claim: claimPda,
+        claimerAgent: getAgentPDA(agent3.publicKey)[0], claimerWallet: agent3.publicKey, caller: agent3.publicKey,
+      }).signers([agent3]).rpc();
+    });
+
+    it("Falls back to the protocol timeout", async () => {
+      const id = await postWithTimeout(86400, 0);
+      const prayer = await (program.account as any).prayer.fetch(getPrayerPDA(id)[0]);
+      assert.equal(prayer.claimTimeoutSeconds.toNumber(), 3600);
+    });
+  });
+
+  // ── Expiry ───────────────────────────────────────────────
+
+  describe("Expiry", () => {
+    const KEEPER_FEE_LAMPORTS = 100_000;
+    const bounty = 0.01 * LAMPORTS_PER_SOL;
+    let expiringId: number;
+
+    const expire = (id: number) => program.methods.expirePrayer().accounts({
+      prayer: getPrayerPDA(id)[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+      requester: authority.publicKey, keeper: outsider.publicKey,
+      rewardMint: null, escrow: null, requesterTokenAccount: null, tokenProgram: null,
+    }).signers([outsider]).rpc();
+
+    it("Cannot expire before the TTL", async () => {
+      expiringId = nextPrayerId;
+      await program.methods.postPrayer(
+        { signal: {} }, sha256("nobody came"), new anchor.BN(bounty), new anchor.BN(2), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: getPrayerPDA(expiringId)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await expectError(() => expire(expiringId), "NotExpired");
+    });
+
+    it("Anyone can expire it for a keeper fee", async () => {
+      await sleep(3500);
+      const prayerPda = getPrayerPDA(expiringId)[0];
+      const rent = await provider.connection.getBalance(prayerPda) - bounty;
+      const requesterBefore = await provider.connection.getBalance(authority.publicKey);
+      const keeperBefore = await provider.connection.getBalance(outsider.publicKey);
+
+      await expire(expiringId);
+
+      assert.isNull(await provider.connection.getAccountInfo(prayerPda));
+      const keeperAfter = await provider.connection.getBalance(outsider.publicKey);
+      assert.equal(keeperAfter - keeperBefore, KEEPER_FEE_LAMPORTS);
+      // The requester pays this transaction's fee as the provider wallet
+      const requesterAfter = await provider.connection.getBalance(authority.publicKey);
+      assert.approximately(requesterAfter - requesterBefore, bounty + rent - KEEPER_FEE_LAMPORTS, 10_000);
+    });
+  });
+
+  describe("Sweep Claims", () => {
+    const stake = 0.002 * LAMPORTS_PER_SOL;
+    let sweepId: number;
+
+    const sweep = (id: number, claimers: PublicKey[]) => program.methods.sweepClaims().accounts({
+      prayer: getPrayerPDA(id)[0], caller: outsider.publicKey,
+    }).remainingAccounts(claimers.flatMap((c) => [
+      { pubkey: getClaimPDA(id, c)[0], isSigner: false, isWritable: true },
+      { pubkey: getAgentPDA(c)[0], isSigner: false, isWritable: true },
+      { pubkey: c, isSigner: false, isWritable: true },
+    ])).signers([outsider]).rpc();
+
+    it("Cannot sweep claims on a live prayer", async () => {
+      sweepId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(sweepId);
+      const [claimPda] = getClaimPDA(sweepId, agent4.publicKey);
+
+      await program.methods.postPrayer(
+        { review: {} }, sha256("sweep me"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(stake), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await program.methods.claimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent4.publicKey)[0],
+        claimer: agent4.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent4]).rpc();
+
+      await expectError(() => sweep(sweepId, [agent4.publicKey]), "CannotClose");
+    });
+
+    it("Anyone can sweep claims once the prayer is confirmed", async () => {
+      const [prayerPda] = getPrayerPDA(sweepId);
+      const [claimPda] = getClaimPDA(sweepId, agent4.publicKey);
+
+      const answer = "looks fine";
+      await program.methods.answerPrayer(sha256(answer), Buffer.from(encryptFor(answer, enc1.publicKey, enc4.secretKey))).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+        answererAgent: getAgentPDA(agent4.publicKey)[0], answerer: agent4.publicKey,
+      }).signers([agent4]).rpc();
+      await program.methods.confirmPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent4.publicKey)[0], requester: authority.publicKey,
+      }).remainingAccounts(payoutAccounts(sweepId, [
+        [agent4.publicKey, agent4.publicKey],
+      ])).rpc();
+
+      const rent = await provider.connection.getBalance(claimPda);
+      const before = await provider.connection.getBalance(agent4.publicKey);
+      await sweep(sweepId, [agent4.publicKey]);
+
+      assert.isNull(await provider.connection.getAccountInfo(claimPda));
+      const after = await provider.connection.getBalance(agent4.publicKey);
+      assert.equal(after - before, rent);
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.numClaimers, 0);
+    });
+  });
+
+  describe("Require Delivery", () => {
+    it("Refuses a competing answer until content is delivered", async () => {
+      const prayerId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(prayerId);
+      const [claimPda] = getClaimPDA(prayerId, agent4.publicKey);
+      const OPTION_COMPETITION = 1 << 0;
+      const OPTION_REQUIRE_DELIVERY = 1 << 2;
+
+      await program.methods.postPrayer(
+        { review: {} }, sha256("read the brief first"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, OPTION_COMPETITION | OPTION_REQUIRE_DELIVERY, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await program.methods.claimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent4.publicKey)[0],
+        claimer: agent4.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent4]).rpc();
+
+      const answer = "guessed without reading";
+      const submit = () => program.methods.submitAnswer(sha256(answer), Buffer.from(encryptFor(answer, enc1.publicKey, enc4.secretKey))).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+        answer: getAnswerPDA(prayerId, agent4.publicKey)[0], answerer: agent4.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent4]).rpc();
+      await expectError(submit, "ContentNotDelivered");
+
+      const brief = "the brief";
+      await program.methods.deliverContent(Buffer.from(encryptFor(brief, enc4.publicKey, enc1.secretKey))).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, requester: authority.publicKey,
+      }).rpc();
+      await submit();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.numAnswers, 1);
+    });
+  });
+
+  describe("Extend and Top Up", () => {
+    const bounty = 0.01 * LAMPORTS_PER_SOL;
+    let repricedId: number;
+
+    it("Extends an open prayer within the max TTL", async () => {
+      repricedId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(repricedId);
+      await program.methods.postPrayer(
+        { knowledge: {} }, sha256("still waiting"), new anchor.BN(bounty), new anchor.BN(3600), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      const before = await (program.account as any).prayer.fetch(prayerPda);
+      await program.methods.extendPrayer(new anchor.BN(7200)).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, requester: authority.publicKey,
+      }).rpc();
+      const after = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(after.expiresAt.toNumber(), before.expiresAt.toNumber() + 7200);
+
+      const { config } = await (program.account as any).prayerChain.fetch(getPrayerChainPDA()[0]);
+      await expectError(
+        () => program.methods.extendPrayer(config.maxTtlSeconds).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, requester: authority.publicKey,
+        }).rpc(),
+        "InvalidTTL"
+      );
+    });
+
+    it("Only the requester can top up the bounty", async () => {
+      await expectError(
+        () => program.methods.topUpBounty(new anchor.BN(bounty)).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(repricedId)[0], requester: outsider.publicKey,
+          rewardMint: null, escrow: null, requesterTokenAccount: null, tokenProgram: null, systemProgram: SystemProgram.programId,
+        }).signers([outsider]).rpc(),
+        "NotRequester"
+      );
+    });
+
+    it("Tops up the bounty and escrows the extra lamports", async () => {
+      const [prayerPda] = getPrayerPDA(repricedId);
+      const balanceBefore = await provider.connection.getBalance(prayerPda);
+
+      await program.methods.topUpBounty(new anchor.BN(bounty)).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, requester: authority.publicKey,
+        rewardMint: null, escrow: null, requesterTokenAccount: null, tokenProgram: null, systemProgram: SystemProgram.programId,
+      }).rpc();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.rewardLamports.toNumber(), 2 * bounty);
+      assert.equal(await provider.connection.getBalance(prayerPda), balanceBefore + bounty);
+    });
+  });
+
+  describe("Crowdfunding", () => {
+    const bounty = 0.01 * LAMPORTS_PER_SOL;
+    let fundedId: number;
+
+    const fund = (prayerId: number, funder: Keypair, lamports: number) =>
+      program.methods.fundPrayer(new anchor.BN(lamports)).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(prayerId)[0],
+        contribution: getContributionPDA(prayerId, funder.publicKey)[0],
+        funderClaim: getClaimPDA(prayerId, funder.publicKey)[0],
+        funder: funder.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([funder]).rpc();
+
+    it("Lets anyone but the requester fund an open prayer", async () => {
+      fundedId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(fundedId);
+      await program.methods.postPrayer(
+        { knowledge: {} }, sha256("many hands"), new anchor.BN(bounty), new anchor.BN(3600), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await expectError(
+        () => program.methods.fundPrayer(new anchor.BN(bounty)).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda,
+          contribution: getContributionPDA(fundedId, authority.publicKey)[0],
+          funderClaim: getClaimPDA(fundedId, authority.publicKey)[0],
+          funder: authority.publicKey, systemProgram: SystemProgram.programId,
+        }).rpc(),
+        "CannotFundOwn"
+      );
+
+      // outsider has no agent; funding doesn't need one. A second call adds to the same Contribution
+      await fund(fundedId, outsider, 2 * bounty);
+      await fund(fundedId, outsider, bounty);
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.equal(prayer.rewardLamports.toNumber(), 4 * bounty);
+      assert.equal(prayer.fundedLamports.toNumber(), 3 * bounty);
+      assert.equal(prayer.numFunders, 1);
+      const contribution = await (program.account as any).contribution.fetch(
+        getContributionPDA(fundedId, outsider.publicKey)[0]
+      );
+      assert.equal(contribution.amount.toNumber(), 3 * bounty);
+    });
+
+    it("Refunds funders pro-rata on cancel", async () => {
+      const [prayerPda] = getPrayerPDA(fundedId);
+      const [contributionPda] = getContributionPDA(fundedId, outsider.publicKey);
+      const prayerBefore = await provider.connection.getBalance(prayerPda);
+
+      await program.methods.cancelPrayer().accounts({
+        prayer: prayerPda, requester: authority.publicKey,
+      }).rpc();
+      // Only the requester's own share left the prayer
+      assert.equal(await provider.connection.getBalance(prayerPda), prayerBefore - bounty);
+
+      await expectError(
+        () => program.methods.closePrayer().accounts({
+          prayer: prayerPda, requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
+        }).rpc(),
+        "ContributionsOutstanding"
+      );
+
+      // Anyone can withdraw for the funder; the share and rent go to the funder
+      const funderBefore = await provider.connection.getBalance(outsider.publicKey);
+      const contributionRent = await provider.connection.getBalance(contributionPda);
+      await program.methods.withdrawContribution().accounts({
+        prayer: prayerPda, contribution: contributionPda,
+        funderWallet: outsider.publicKey, caller: authority.publicKey,
+      }).rpc();
+      assert.equal(
+        await provider.connection.getBalance(outsider.publicKey),
+        funderBefore + 3 * bounty + contributionRent
+      );
+      assert.isNull(await provider.connection.getAccountInfo(contributionPda));
+
+      await program.methods.closePrayer().accounts({
+        prayer: prayerPda, requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
+      }).rpc();
+      assert.isNull(await provider.connection.getAccountInfo(prayerPda));
+    });
+  });
+
+  // ── Review Window / Finalize ─────────────────────────────
+
+  describe("Finalize After Review Window", () => {
+    const bounty = 0.01 * LAMPORTS_PER_SOL;
+    let finalizeId: number;
+
+    it("Rejects a zero review window", async () => {
+      await expectError(
+        () => program.methods.postPrayer(
+          { knowledge: {} }, sha256("no review"), new anchor.BN(0), new anchor.BN(86400), 1, new anchor.BN(0), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+        ).accounts({
+          prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+          prayer: getPrayerPDA(nextPrayerId)[0], requester: authority.publicKey, systemProgram: SystemProgram.programId,
+        }).rpc(),
+        "InvalidReviewWindow"
+      );
+    });
+
+    it("Cannot finalize while the review window is open", async () => {
+      finalizeId = nextPrayerId;
+      const [prayerPda] = getPrayerPDA(finalizeId);
+      const [claimPda] = getClaimPDA(finalizeId, agent4.publicKey);
+
+      await program.methods.postPrayer(
+        { signal: {} }, sha256("ghosted"), new anchor.BN(bounty), new anchor.BN(86400), 1, new anchor.BN(2), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+      ).accounts({
+        prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+        prayer: prayerPda, requester: authority.publicKey, systemProgram: SystemProgram.programId,
+      }).rpc();
+      nextPrayerId++;
+
+      await program.methods.claimPrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent4.publicKey)[0],
+        claimer: agent4.publicKey, systemProgram: SystemProgram.programId,
+      }).signers([agent4]).rpc();
+
+      const answer = "signal wired up";
+      await program.methods.answerPrayer(sha256(answer), Buffer.from(encryptFor(answer, enc1.publicKey, enc4.secretKey))).accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+        answererAgent: getAgentPDA(agent4.publicKey)[0], answerer: agent4.publicKey,
+      }).signers([agent4]).rpc();
+
+      await expectError(
+        () => program.methods.finalizePrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent4.publicKey)[0], caller: outsider.publicKey,
+        }).remainingAccounts(payoutAccounts(finalizeId, [
+          [agent4.publicKey, agent4.publicKey],
+        ])).signers([outsider]).rpc(),
+        "ReviewWindowOpen"
+      );
+    });
+
+    it("Cannot dispute after the review window", async () => {
+      await sleep(4000);
+      await expectError(
+        () => program.methods.openDispute(sha256("too late")).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: getPrayerPDA(finalizeId)[0], requester: authority.publicKey,
+        }).rpc(),
+        "ReviewWindowElapsed"
+      );
+    });
+
+    it("Anyone can finalize once the window lapses", async () => {
+      const [prayerPda] = getPrayerPDA(finalizeId);
+      const repBefore = (await (program.account as any).agent.fetch(getAgentPDA(agent4.publicKey)[0])).reputation.toNumber();
+      const balBefore = await provider.connection.getBalance(agent4.publicKey);
+
+      await program.methods.finalizePrayer().accounts({
+        prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent4.publicKey)[0], caller: outsider.publicKey,
+      }).remainingAccounts(payoutAccounts(finalizeId, [
+        [agent4.publicKey, agent4.publicKey],
+      ])).signers([outsider]).rpc();
+
+      const prayer = await (program.account as any).prayer.fetch(prayerPda);
+      assert.deepEqual(prayer.status, { confirmed: {} });
+
+      const balAfter = await provider.connection.getBalance(agent4.publicKey);
+      assert.equal(balAfter - balBefore, bounty);
+      const repAfter = (await (program.account as any).agent.fetch(getAgentPDA(agent4.publicKey)[0])).reputation.toNumber();
+      assert.equal(repAfter - repBefore, 5);
+    });
+  });
+
+  // ── SPL Token Bounties ──────────────────────────────────
+
+  describe("SPL Token Bounties", () => {
+    const bounty = 900_000;
+
+    for (const tokenProgram of [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID]) {
+      const label = tokenProgram.equals(TOKEN_PROGRAM_ID) ? "SPL Token" : "Token-2022";
+      let mint: PublicKey;
+      let requesterAta: PublicKey;
+      let claimerAta: PublicKey;
+
+      before(async () => {
+        mint = await createMint(
+          provider.connection, authorityKeypair, authority.publicKey, null, 6,
+          undefined, undefined, tokenProgram,
+        );
+        requesterAta = (await getOrCreateAssociatedTokenAccount(
+          provider.connection, authorityKeypair, mint, authority.publicKey,
+          false, undefined, undefined, tokenProgram,
+        )).address;
+        claimerAta = (await getOrCreateAssociatedTokenAccount(
+          provider.connection, authorityKeypair, mint, agent3.publicKey,
+          false, undefined, undefined, tokenProgram,
+        )).address;
+        await mintTo(
+          provider.connection, authorityKeypair, mint, requesterAta, authorityKeypair,
+          10 * bounty, [], undefined, tokenProgram,
+        );
+      });
+
+      function postTokenPrayer(id: number, text: string) {
+        return program.methods.postPrayer(
+          { knowledge: {} }, sha256(text), new anchor.BN(bounty), new anchor.BN(86400), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+        ).accounts({
+          prayerChain: getPrayerChainPDA()[0], requesterAgent: getAgentPDA(authority.publicKey)[0],
+          prayer: getPrayerPDA(id)[0], requester: authority.publicKey,
+          rewardMint: mint, escrow: getEscrowPDA(id)[0], requesterTokenAccount: requesterAta,
+          tokenProgram, systemProgram: SystemProgram.programId,
+        }).rpc();
+      }
+
+      it(`${label}: escrows bounty and pays claimer on confirm`, async () => {
+        const id = nextPrayerId;
+        await postTokenPrayer(id, `${label} bounty`);
+        nextPrayerId++;
+
+        const [prayerPda] = getPrayerPDA(id);
+        const prayer = await (program.account as any).prayer.fetch(prayerPda);
+        assert.ok(prayer.rewardMint.equals(mint));
+        const escrow = await getAccount(provider.connection, getEscrowPDA(id)[0], undefined, tokenProgram);
+        assert.equal(Number(escrow.amount), bounty);
+        assert.ok(escrow.owner.equals(prayerPda));
+
+        const [claimPda] = getClaimPDA(id, agent3.publicKey);
+        await program.methods.claimPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda, claimerAgent: getAgentPDA(agent3.publicKey)[0],
+          claimer: agent3.publicKey, systemProgram: SystemProgram.programId,
+        }).signers([agent3]).rpc();
+
+        const answer = `${label} answer`;
+        await program.methods.answerPrayer(sha256(answer), Buffer.from(encryptFor(answer, enc1.publicKey, enc3.secretKey))).accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+          answererAgent: getAgentPDA(agent3.publicKey)[0], answerer: agent3.publicKey,
+        }).signers([agent3]).rpc();
+
+        const before = await getAccount(provider.connection, claimerAta, undefined, tokenProgram);
+        await program.methods.confirmPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, answererAgent: getAgentPDA(agent3.publicKey)[0], requester: authority.publicKey,
+          rewardMint: mint, escrow: getEscrowPDA(id)[0], tokenProgram,
+        }).remainingAccounts(payoutAccounts(id, [
+          [agent3.publicKey, claimerAta],
+        ])).rpc();
+        const after = await getAccount(provider.connection, claimerAta, undefined, tokenProgram);
+        assert.equal(Number(after.amount - before.amount), bounty);
+
+        // Closing sweeps and closes the escrow
+        await program.methods.unclaimPrayer().accounts({
+          prayerChain: getPrayerChainPDA()[0], prayer: prayerPda, claim: claimPda,
+          claimerAgent: getAgentPDA(agent3.publicKey)[0], claimerWallet: agent3.publicKey, caller: agent3.publicKey,
+        }).signers([agent3]).rpc();
+        await program.methods.closePrayer().accounts({
+          prayer: prayerPda, requesterAgent: getAgentPDA(authority.publicKey)[0], requester: authority.publicKey,
+          rewardMint: mint, escrow: getEscrowPDA(id)[0], requesterTokenAccount: requesterAta, tokenProgram,
+        }).rpc();
+        assert.isNull(await provider.connection.getAccountInfo(getEscrowPDA(id)[0]));
+      });
+
+      it(`${label}: cancel refunds escrow to requester`, async () => {
+        const id = nextPrayerId;
+        const before = await getAccount(provider.connection, requesterAta, undefined, tokenProgram);
+        await postTokenPrayer(id, `${label} cancel`);
+        nextPrayerId++;
+
+        await program.methods.cancelPrayer().accounts({
+          prayer: getPrayerPDA(id)[0], requester: authority.publicKey,
+          rewardMint: mint, escrow: getEscrowPDA(id)[0], requesterTokenAccount: requesterAta, tokenProgram,
+        }).rpc();
+
+        const after = await getAccount(provider.connection, requesterAta, undefined, tokenProgram);
+        assert.equal(after.amount, before.amount);
+      });
+
+      it(`${label}: cancel without token accounts is rejected`, async () => {
+        const id = nextPrayerId;
+        await postTokenPrayer(id, `${label} missing accounts`);
+        nextPrayerId++;
+
+        await expectError(
+          () => program.methods.cancelPrayer().accounts({
+            prayer: getPrayerPDA(id)[0], requester: authority.publicKey,
+          }).rpc(),
+          "MissingTokenAccounts"
+        );
+      });
+    }
+  });
+
+  // ── Encryption / Privacy ────────────────────────────────
+
+  describe("E2E Encryption", () => {
+    it("Third party cannot decrypt content", async () => {
+      const content = "secret alpha signal";
+      const blob = encryptFor(content, enc2.publicKey, enc1.secretKey);
+      const eavesdropper = Keypair.generate();
+      const eavesEnc = deriveEncryptionKeypair(eavesdropper);
+      const dec = decryptFrom(blob, enc1.publicKey, eavesEnc.secretKey);
+      assert.isNull(dec);
+    });
+
+    it("Third party cannot decrypt answer", async () => {
+      const answer = "SOFR will spike 25bps on March 31";
+      const blob = encryptFor(answer, enc1.publicKey, enc2.secretKey);
+      const dec = decryptFrom(blob, enc2.publicKey, enc3.secretKey);
+      assert.isNull(dec);
+    });
+
+    it("Correct recipient decrypts successfully", async () => {
+      const msg = "classified: BTC to $150K by Q3";
+      const blob = encryptFor(msg, enc3.publicKey, enc1.secretKey);
+      const dec = decryptFrom(blob, enc1.publicKey, enc3.secretKey);
+      assert.equal(dec, msg);
+    });
+
+    it("Encryption keys are deterministic from wallet", async () => {
+      const kp1a = deriveEncryptionKeypair(agent2);
+      const kp1b = deriveEncryptionKeypair(agent2);
+      assert.deepEqual(Array.from(kp1a.publicKey), Array.from(kp1b.publicKey));
+      assert.deepEqual(Array.from(kp1a.secretKey), Array.from(kp1b.secretKey));
+    });
+
+    it("DH shared secret is symmetric", async () => {
+      // A encrypts for B, B decrypts from A — and vice versa
+      const msg = "symmetric test";
+      const blobAtoB = encryptFor(msg, enc2.publicKey, enc1.secretKey);
+      const decB = decryptFrom(blobAtoB, enc1.publicKey, enc2.secretKey);
+      assert.equal(decB, msg);
+
+      const blobBtoA = encryptFor(msg, enc1.publicKey, enc2.secretKey);
+      const decA = decryptFrom(blobBtoA, enc2.publicKey, enc1.secretKey);
+      assert.equal(decA, msg);
+    });
+  });
+
+  // ── Prayer Types ────────────────────────────────────────
+
+  describe("All Prayer Types", () => {
+    const types = [
+      { name: "Knowledge", arg: { knowledge: {} } },
+      { name: "Compute", arg: { compute: {} } },
+      { name: "Review", arg: { review: {} } },
+      { name: "Signal", arg: { signal: {} } },
+      { name: "Collaboration", arg: { collaboration: {} } },
+    ];
+
+    for (const t of types) {
+      it(`Posts ${t.name} prayer`, async () => {
+        const id = nextPrayerId;
+        const [chainPda] = getPrayerChainPDA();
+        const [agentPda] = getAgentPDA(authority.publicKey);
+        const [prayerPda] = getPrayerPDA(id);
+
+        await program.methods.postPrayer(
+          t.arg, sha256(`${t.name} test`), new anchor.BN(0), new anchor.BN(3600), 1, new anchor.BN(REVIEW_SECONDS), { requester: {} }, 0, [], NO_REQUIREMENTS, new anchor.BN(0), new anchor.BN(0)
+        ).accounts({
+          prayerChain: chainPda, requesterAgent: agentPda, prayer: prayerPda,
+          requester: authority.publicKey, systemProgram: SystemProgram.programId,
+        }).rpc();
+        nextPrayerId++;
 
         const prayer = await (program.account as any).prayer.fetch(prayerPda);
         assert.deepEqual(prayer.prayerType, t.arg);
@@ -820,6 +2181,45 @@ describe("chorus-prayers", () => {
     }
   });
 
+  // ── Close Agent ─────────────────────────────────────────
+
+  describe("Close Agent", () => {
+    it("Refuses while the agent has unclosed prayers", async () => {
+      const [agentPda] = getAgentPDA(authority.publicKey);
+      const agent = await (program.account as any).agent.fetch(agentPda);
+      assert.isAbove(agent.openPrayers, 0);
+
+      await expectError(
+        () => program.methods.closeAgent().accounts({
+          prayerChain: getPrayerChainPDA()[0], agent: agentPda, wallet: authority.publicKey,
+        }).rpc(),
+        "AgentHasOpenWork"
+      );
+    });
+
+    it("Closes an idle agent and decrements total_agents", async () => {
+      const throwaway = Keypair.generate();
+      await airdrop(provider.connection, throwaway.publicKey, 1);
+      const [chainPda] = getPrayerChainPDA();
+      const [agentPda] = getAgentPDA(throwaway.publicKey);
+
+      await program.methods.registerAgent("ci-bot", "none", Array.from(deriveEncryptionKeypair(throwaway).publicKey))
+        .accounts({ prayerChain: chainPda, agent: agentPda, wallet: throwaway.publicKey, systemProgram: SystemProgram.programId })
+        .signers([throwaway]).rpc();
+      const registered = (await (program.account as any).prayerChain.fetch(chainPda)).totalAgents.toNumber();
+      const balBefore = await provider.connection.getBalance(throwaway.publicKey);
+
+      await program.methods.closeAgent().accounts({
+        prayerChain: chainPda, agent: agentPda, wallet: throwaway.publicKey,
+      }).signers([throwaway]).rpc();
+
+      const chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.equal(chain.totalAgents.toNumber(), registered - 1);
+      assert.isNull(await provider.connection.getAccountInfo(agentPda));
+      assert.isAbove(await provider.connection.getBalance(throwaway.publicKey), balBefore); // Rent recovered
+    });
+  });
+
   // ── Reputation Tracking ─────────────────────────────────
 
   describe("Reputation", () => {
@@ -841,4 +2241,41 @@ describe("chorus-prayers", () => {
       assert.equal(chain.totalAgents.toNumber(), 4);
     });
   });
+
+  // ── Renounce (keep last — authority is gone afterwards) ─
+
+  describe("Renounce Authority", () => {
+    it("Cannot renounce while anything is paused", async () => {
+      const [chainPda] = getPrayerChainPDA();
+      await program.methods.setPause(1 << 1).accounts({ // PAUSE_POST
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+      await expectError(
+        () => program.methods.renounceAuthority().accounts({
+          prayerChain: chainPda, authority: authority.publicKey,
+        }).rpc(),
+        "PausedRenounce"
+      );
+      await program.methods.setPause(0).accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+    });
+
+    it("Renouncing leaves config permanently frozen", async () => {
+      const [chainPda] = getPrayerChainPDA();
+      await program.methods.renounceAuthority().accounts({
+        prayerChain: chainPda, authority: authority.publicKey,
+      }).rpc();
+
+      const chain = await (program.account as any).prayerChain.fetch(chainPda);
+      assert.ok(chain.authority.equals(PublicKey.default));
+
+      await expectError(
+        () => program.methods.setArbiters([]).accounts({
+          prayerChain: chainPda, authority: authority.publicKey,
+        }).rpc(),
+        "NotAuthority"
+      );
+    });
+  });
 });